@@ -89,14 +89,20 @@ pub mod entry {
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        if let ExecuteMsg::Mint {
-            extension:
-                Some(MetadataWithRoyalty {
-                    royalty_percentage: Some(royalty_percentage),
-                    ..
-                }),
+        let extension = match &msg {
+            ExecuteMsg::Mint { extension, .. } | ExecuteMsg::SafeMint { extension, .. } => {
+                extension
+            }
+            ExecuteMsg::MintWithVoucher { voucher, .. } => &voucher.extension,
+            ExecuteMsg::RegisterEditionMaster { extension, .. }
+            | ExecuteMsg::SetTemplate { extension, .. } => extension,
+            ExecuteMsg::MintFromTemplate { overrides, .. } => overrides,
+            _ => &None,
+        };
+        if let Some(MetadataWithRoyalty {
+            royalty_percentage: Some(royalty_percentage),
             ..
-        } = &msg
+        }) = extension
         {
             // validate royalty_percentage to be between 0 and 100
             // no need to check < 0 because royalty_percentage is u64
@@ -162,6 +168,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -188,6 +195,88 @@ mod tests {
         assert_eq!(res.extension, extension);
     }
 
+    #[test]
+    fn use_template_and_edition_metadata() {
+        let mut deps = mock_dependencies();
+        let contract = Cw2981Contract::default();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = Cw721InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: Empty {},
+            minter: None,
+            creator: None,
+            withdraw_address: None,
+            ics721_bridge: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+        let shared = MetadataWithRoyalty {
+            name: Some("Starship".to_string()),
+            royalty_percentage: Some(10),
+            royalty_payment_address: Some("john".to_string()),
+            ..MetadataWithRoyalty::default()
+        };
+
+        // overrides replace the template's metadata and royalty info
+        let exec_msg = ExecuteMsg::SetTemplate {
+            template_id: "starship".to_string(),
+            extension: Some(shared.clone()),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        let exec_msg = ExecuteMsg::MintFromTemplate {
+            token_id: "Enterprise".to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            template_id: "starship".to_string(),
+            overrides: Some(MetadataWithRoyalty {
+                name: Some("Starship USS Enterprise".to_string()),
+                royalty_percentage: Some(5),
+                ..MetadataWithRoyalty::default()
+            }),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        let res = contract
+            .query_nft_info(deps.as_ref().storage, "Enterprise".to_string())
+            .unwrap();
+        assert_eq!(
+            res.extension,
+            Some(MetadataWithRoyalty {
+                name: Some("Starship USS Enterprise".to_string()),
+                royalty_percentage: Some(5),
+                ..shared.clone()
+            })
+        );
+
+        // editions keep the master's royalty info
+        let exec_msg = ExecuteMsg::RegisterEditionMaster {
+            master_id: "starship".to_string(),
+            token_uri: None,
+            extension: Some(shared.clone()),
+            max_size: 1,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        let exec_msg = ExecuteMsg::MintEdition {
+            master_id: "starship".to_string(),
+            owner: "john".to_string(),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
+        let res = contract
+            .query_nft_info(deps.as_ref().storage, "starship/1".to_string())
+            .unwrap();
+        assert_eq!(
+            res.extension,
+            Some(MetadataWithRoyalty {
+                attributes: Some(vec![Trait {
+                    display_type: None,
+                    trait_type: "edition_number".to_string(),
+                    value: "1".to_string(),
+                }]),
+                ..shared
+            })
+        );
+    }
+
     #[test]
     fn validate_royalty_information() {
         let mut deps = mock_dependencies();
@@ -201,6 +290,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -217,6 +307,47 @@ mod tests {
             }),
         };
         // mint will return StdError
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
+
+        // same for the shared metadata of editions
+        let exec_msg = ExecuteMsg::RegisterEditionMaster {
+            master_id: token_id.to_string(),
+            token_uri: None,
+            extension: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(101),
+                ..MetadataWithRoyalty::default()
+            }),
+            max_size: 1,
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
+
+        // and for templates and their overrides
+        let exec_msg = ExecuteMsg::SetTemplate {
+            template_id: token_id.to_string(),
+            extension: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(101),
+                ..MetadataWithRoyalty::default()
+            }),
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
+        let exec_msg = ExecuteMsg::SetTemplate {
+            template_id: token_id.to_string(),
+            extension: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        let exec_msg = ExecuteMsg::MintFromTemplate {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            template_id: token_id.to_string(),
+            overrides: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(101),
+                ..MetadataWithRoyalty::default()
+            }),
+        };
         let err = entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
     }
@@ -234,6 +365,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -275,6 +407,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         let env = mock_env();
         entry::instantiate(deps.as_mut(), env.clone(), info.clone(), init_msg).unwrap();
@@ -4,13 +4,14 @@ use crate::{
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Deps, Empty, Env, MessageInfo, Uint128};
+pub use cw721::msg::RoyaltiesInfoResponse;
 use cw721::msg::{
     AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, MinterResponse, NftInfoResponse,
     NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
 use cw721::{
     error::Cw721ContractError,
-    execute::{assert_creator, assert_minter},
+    execute::{assert_creator, assert_creator_or_metadata_editor, assert_minter},
     msg::{empty_as_none, CollectionInfoAndExtensionResponse, Cw721QueryMsg},
     traits::StateFactory,
 };
@@ -224,14 +225,6 @@ impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtensionWithRoyalty,
     }
 }
 
-#[cw_serde]
-pub struct RoyaltiesInfoResponse {
-    pub address: String,
-    // Note that this must be the same denom as that passed in to RoyaltyInfo
-    // rounding up or down is at the discretion of the implementer
-    pub royalty_amount: Uint128,
-}
-
 /// Shows if the contract implements royalties
 /// if royalty_payments is true, marketplaces should pay them
 #[cw_serde]
@@ -310,7 +303,7 @@ impl StateFactory<MetadataWithRoyalty> for MetadataWithRoyaltyMsg {
     ) -> Result<(), Cw721ContractError> {
         // assert here is different to NFT Info:
         // - creator and minter can create NFT metadata
-        // - only creator can update NFT metadata
+        // - only creator or metadata editor can update NFT metadata
         if current.is_none() {
             let info = info.ok_or(Cw721ContractError::NoInfo)?;
             // current is none: minter and creator can create new NFT metadata
@@ -321,8 +314,8 @@ impl StateFactory<MetadataWithRoyalty> for MetadataWithRoyaltyMsg {
             }
         } else {
             let info = info.ok_or(Cw721ContractError::NoInfo)?;
-            // current is some: only creator can update NFT metadata
-            assert_creator(deps.storage, &info.sender)?;
+            // current is some: only creator or metadata editor can update NFT metadata
+            assert_creator_or_metadata_editor(deps.storage, &info.sender, None)?;
         }
         // check URLs
         let image = empty_as_none(self.image.clone());
@@ -1,7 +1,11 @@
 use std::marker::PhantomData;
 
 use cosmwasm_std::Empty;
-use cw721::{state::Cw721Config, state::NftInfo, traits::Contains};
+use cw721::{
+    state::{Cw721Config, NftInfo},
+    traits::{Contains, WithOverrides},
+    NftExtension,
+};
 
 use crate::{
     DefaultOptionMetadataExtensionWithRoyalty, DefaultOptionMetadataExtensionWithRoyaltyMsg,
@@ -79,3 +83,58 @@ impl Contains for MetadataWithRoyalty {
         true
     }
 }
+
+/// Onchain metadata of the token, without royalty info.
+impl From<&MetadataWithRoyalty> for NftExtension {
+    fn from(metadata: &MetadataWithRoyalty) -> Self {
+        NftExtension {
+            image: metadata.image.clone(),
+            image_data: metadata.image_data.clone(),
+            external_url: metadata.external_url.clone(),
+            description: metadata.description.clone(),
+            name: metadata.name.clone(),
+            attributes: metadata.attributes.clone(),
+            background_color: metadata.background_color.clone(),
+            animation_url: metadata.animation_url.clone(),
+            youtube_url: metadata.youtube_url.clone(),
+        }
+    }
+}
+
+impl MetadataWithRoyalty {
+    /// Replaces the onchain metadata, keeping the royalty info.
+    fn with_metadata(&self, metadata: NftExtension) -> Self {
+        MetadataWithRoyalty {
+            image: metadata.image,
+            image_data: metadata.image_data,
+            external_url: metadata.external_url,
+            description: metadata.description,
+            name: metadata.name,
+            attributes: metadata.attributes,
+            background_color: metadata.background_color,
+            animation_url: metadata.animation_url,
+            youtube_url: metadata.youtube_url,
+            royalty_percentage: self.royalty_percentage,
+            royalty_payment_address: self.royalty_payment_address.clone(),
+        }
+    }
+}
+
+/// Onchain metadata is merged as for `NftExtension`, royalty info set in the overrides replaces the template's.
+impl WithOverrides for MetadataWithRoyalty {
+    fn with_overrides(&self, overrides: &Self) -> Self {
+        let metadata = NftExtension::from(self).with_overrides(&NftExtension::from(overrides));
+        MetadataWithRoyalty {
+            royalty_percentage: overrides.royalty_percentage.or(self.royalty_percentage),
+            royalty_payment_address: overrides
+                .royalty_payment_address
+                .clone()
+                .or_else(|| self.royalty_payment_address.clone()),
+            ..self.with_metadata(metadata)
+        }
+    }
+
+    fn with_edition_number(&self, edition_number: u64) -> Self {
+        self.with_metadata(NftExtension::from(self).with_edition_number(edition_number))
+    }
+}
@@ -0,0 +1,335 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+
+use crate::error::ContractError;
+use crate::msg::{
+    AuctionsResponse, CurrentPriceResponse, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{Auction, AuctionKind, Bid, AUCTIONS, AUCTION_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-auction";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    AUCTION_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, env, info, receive_msg),
+        ExecuteMsg::PlaceBid { auction_id } => execute_place_bid(deps, env, info, auction_id),
+        ExecuteMsg::Settle { auction_id } => execute_settle(deps, env, auction_id),
+        ExecuteMsg::Cancel { auction_id } => execute_cancel(deps, info, auction_id),
+    }
+}
+
+fn load_auction(deps: Deps, auction_id: u64) -> Result<Auction, ContractError> {
+    AUCTIONS
+        .may_load(deps.storage, auction_id)?
+        .ok_or(ContractError::AuctionNotFound { auction_id })
+}
+
+fn transfer_nft_msg(auction: &Auction, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: auction.token_id.clone(),
+    };
+    DefaultCw721Helper::new(auction.collection.clone()).call(transfer_msg)
+}
+
+/// Returns the messages settling a sale: the NFT is transferred to the buyer, and the price is
+/// split between royalty recipient and seller.
+fn sale_msgs(
+    deps: Deps,
+    auction: &Auction,
+    buyer: &Addr,
+    price: Uint128,
+) -> StdResult<(Vec<CosmosMsg>, Uint128)> {
+    let send_msg = |to: &Addr, amount: Uint128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount.u128(), &auction.denom),
+        }
+        .into()
+    };
+    let mut msgs = vec![transfer_nft_msg(auction, buyer)?];
+    let mut seller_amount = price;
+    let royalty = DefaultCw721Helper::new(auction.collection.clone()).royalty(
+        &deps.querier,
+        &auction.token_id,
+        price,
+    );
+    let royalty_amount = royalty.as_ref().map(|r| r.amount).unwrap_or_default();
+    if let Some(royalty) = royalty {
+        seller_amount -= royalty.amount;
+        msgs.push(send_msg(&royalty.recipient, royalty.amount));
+    }
+    if !seller_amount.is_zero() {
+        msgs.push(send_msg(&auction.seller, seller_amount));
+    }
+    Ok((msgs, royalty_amount))
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let hook_msg: Cw721HookMsg = from_json(&receive_msg.msg)?;
+    let (denom, start_time, end_time, kind) = match hook_msg {
+        Cw721HookMsg::English {
+            denom,
+            reserve_price,
+            min_bid_increment,
+            extension_duration,
+            start_time,
+            end_time,
+        } => {
+            if min_bid_increment.is_zero() {
+                return Err(ContractError::InvalidPrice {
+                    reason: "min bid increment must not be zero".to_string(),
+                });
+            }
+            let kind = AuctionKind::English {
+                reserve_price,
+                min_bid_increment,
+                extension_duration,
+                highest_bid: None,
+            };
+            (denom, start_time, end_time, kind)
+        }
+        Cw721HookMsg::Dutch {
+            denom,
+            start_price,
+            end_price,
+            start_time,
+            end_time,
+        } => {
+            if start_price <= end_price {
+                return Err(ContractError::InvalidPrice {
+                    reason: "start price must be higher than end price".to_string(),
+                });
+            }
+            let kind = AuctionKind::Dutch {
+                start_price,
+                end_price,
+            };
+            (denom, start_time, end_time, kind)
+        }
+    };
+    let start_time = start_time.unwrap_or(env.block.time);
+    if end_time <= start_time || end_time <= env.block.time {
+        return Err(ContractError::InvalidTimes {});
+    }
+
+    let id = AUCTION_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    // the NFT contract is the sender, so collection can't be faked
+    let auction = Auction {
+        id,
+        collection: info.sender,
+        token_id: receive_msg.token_id,
+        seller: deps.api.addr_validate(&receive_msg.sender)?,
+        denom,
+        start_time,
+        end_time,
+        kind,
+    };
+    AUCTIONS.save(deps.storage, id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_auction")
+        .add_attribute("auction_id", id.to_string())
+        .add_attribute("collection", auction.collection.to_string())
+        .add_attribute("token_id", auction.token_id)
+        .add_attribute("seller", auction.seller.to_string()))
+}
+
+pub fn execute_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = load_auction(deps.as_ref(), auction_id)?;
+    let now = env.block.time;
+    if now < auction.start_time {
+        return Err(ContractError::NotStarted {});
+    }
+    if matches!(auction.kind, AuctionKind::English { .. }) && now >= auction.end_time {
+        return Err(ContractError::AuctionEnded {});
+    }
+    let amount = must_pay(&info, &auction.denom)?;
+    let min_bid = auction.current_price(now);
+    if amount < min_bid {
+        return Err(ContractError::BidTooLow {
+            min_bid: min_bid.to_string(),
+        });
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "place_bid")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", info.sender.to_string());
+    match &mut auction.kind {
+        AuctionKind::English {
+            extension_duration,
+            highest_bid,
+            ..
+        } => {
+            // refund outbid bidder
+            let refund_msg = highest_bid.take().map(|bid| BankMsg::Send {
+                to_address: bid.bidder.to_string(),
+                amount: coins(bid.amount.u128(), &auction.denom),
+            });
+            *highest_bid = Some(Bid {
+                bidder: info.sender,
+                amount,
+            });
+            // anti-snipe: late bids extend the auction
+            let extended_end_time = now.plus_seconds(*extension_duration);
+            if extended_end_time > auction.end_time {
+                auction.end_time = extended_end_time;
+            }
+            AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+            Ok(response
+                .add_messages(refund_msg)
+                .add_attribute("amount", amount.to_string())
+                .add_attribute("end_time", auction.end_time.to_string()))
+        }
+        AuctionKind::Dutch { .. } => {
+            AUCTIONS.remove(deps.storage, auction_id);
+            let (msgs, royalty) = sale_msgs(deps.as_ref(), &auction, &info.sender, min_bid)?;
+            // refund anything paid above the current price
+            let excess = amount - min_bid;
+            let refund_msg = (!excess.is_zero()).then(|| BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(excess.u128(), &auction.denom),
+            });
+
+            Ok(response
+                .add_messages(msgs)
+                .add_messages(refund_msg)
+                .add_attribute("amount", min_bid.to_string())
+                .add_attribute("royalty", royalty.to_string()))
+        }
+    }
+}
+
+pub fn execute_settle(deps: DepsMut, env: Env, auction_id: u64) -> Result<Response, ContractError> {
+    let auction = load_auction(deps.as_ref(), auction_id)?;
+    let AuctionKind::English { highest_bid, .. } = &auction.kind else {
+        return Err(ContractError::NotEnglishAuction {});
+    };
+    if env.block.time < auction.end_time {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+    AUCTIONS.remove(deps.storage, auction_id);
+
+    let response = Response::new()
+        .add_attribute("action", "settle")
+        .add_attribute("auction_id", auction_id.to_string());
+    match highest_bid {
+        Some(bid) => {
+            let (msgs, royalty) = sale_msgs(deps.as_ref(), &auction, &bid.bidder, bid.amount)?;
+            Ok(response
+                .add_messages(msgs)
+                .add_attribute("winner", bid.bidder.to_string())
+                .add_attribute("amount", bid.amount.to_string())
+                .add_attribute("royalty", royalty.to_string()))
+        }
+        None => Ok(response.add_message(transfer_nft_msg(&auction, &auction.seller)?)),
+    }
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = load_auction(deps.as_ref(), auction_id)?;
+    if auction.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let AuctionKind::English {
+        highest_bid: Some(_),
+        ..
+    } = auction.kind
+    {
+        return Err(ContractError::HasBids {});
+    }
+    AUCTIONS.remove(deps.storage, auction_id);
+
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&auction, &auction.seller)?)
+        .add_attribute("action", "cancel")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Auction { auction_id } => {
+            to_json_binary(&AUCTIONS.load(deps.storage, auction_id)?)
+        }
+        QueryMsg::CurrentPrice { auction_id } => {
+            let auction = AUCTIONS.load(deps.storage, auction_id)?;
+            to_json_binary(&CurrentPriceResponse {
+                price: auction.current_price(env.block.time),
+            })
+        }
+        QueryMsg::Auctions { start_after, limit } => {
+            to_json_binary(&query_auctions(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_auctions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let auctions = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, auction)| auction))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AuctionsResponse { auctions })
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Auction {auction_id} not found")]
+    AuctionNotFound { auction_id: u64 },
+
+    #[error("End time must be after start time and in the future")]
+    InvalidTimes {},
+
+    #[error("Invalid price: {reason}")]
+    InvalidPrice { reason: String },
+
+    #[error("Auction has not started yet")]
+    NotStarted {},
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Bid too low, minimum is {min_bid}")]
+    BidTooLow { min_bid: String },
+
+    #[error("Auction has bids and can't be cancelled")]
+    HasBids {},
+
+    #[error("Only English auctions can be settled")]
+    NotEnglishAuction {},
+}
@@ -0,0 +1,77 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Timestamp, Uint128};
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::Auction;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Starts an auction of the received NFT on behalf of its previous owner. Sent by the cw721
+    /// contract on `SendNft`, with `Cw721HookMsg` as msg.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Places a bid with the sent funds. In an English auction, the previous highest bidder is
+    /// refunded. In a Dutch auction, the NFT is sold right away and any excess is refunded.
+    PlaceBid { auction_id: u64 },
+    /// Settles an ended English auction: the NFT goes to the highest bidder, proceeds are split
+    /// between seller and royalty recipient. Without bids, the NFT is sent back to the seller.
+    /// Can be called by anyone.
+    Settle { auction_id: u64 },
+    /// Cancels an auction and sends the NFT back. Only seller can call this, and only as long as
+    /// there are no bids.
+    Cancel { auction_id: u64 },
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Starts an English auction.
+    English {
+        denom: String,
+        reserve_price: Uint128,
+        min_bid_increment: Uint128,
+        /// Anti-snipe extension in seconds.
+        extension_duration: u64,
+        /// Defaults to the current block time.
+        start_time: Option<Timestamp>,
+        end_time: Timestamp,
+    },
+    /// Starts a Dutch auction, with price decaying linearly from start price to end price.
+    Dutch {
+        denom: String,
+        start_price: Uint128,
+        end_price: Uint128,
+        /// Defaults to the current block time.
+        start_time: Option<Timestamp>,
+        end_time: Timestamp,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Auction)]
+    Auction { auction_id: u64 },
+
+    /// Returns the minimum bid of an English auction, or the current price of a Dutch auction.
+    #[returns(CurrentPriceResponse)]
+    CurrentPrice { auction_id: u64 },
+
+    /// Lists all auctions, ordered by id.
+    #[returns(AuctionsResponse)]
+    Auctions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct CurrentPriceResponse {
+    pub price: Uint128,
+}
+
+#[cw_serde]
+pub struct AuctionsResponse {
+    pub auctions: Vec<Auction>,
+}
@@ -0,0 +1,74 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub enum AuctionKind {
+    /// Ascending auction, won by the highest bid at the end.
+    English {
+        /// Minimum amount of the first bid.
+        reserve_price: Uint128,
+        /// Minimum amount a bid must exceed the highest bid.
+        min_bid_increment: Uint128,
+        /// Bids placed within this many seconds before the end extend the end time to
+        /// this many seconds after the bid.
+        extension_duration: u64,
+        highest_bid: Option<Bid>,
+    },
+    /// Descending auction, sold to the first bidder paying the current price. The price decays
+    /// linearly from start price at start time to end price at end time, and stays at end price
+    /// afterwards.
+    Dutch {
+        start_price: Uint128,
+        end_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct Auction {
+    pub id: u64,
+    pub collection: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    /// Native denom bids are paid in.
+    pub denom: String,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub kind: AuctionKind,
+}
+
+impl Auction {
+    /// Returns the minimum bid of an English auction, or the current price of a Dutch auction.
+    pub fn current_price(&self, now: Timestamp) -> Uint128 {
+        match &self.kind {
+            AuctionKind::English {
+                reserve_price,
+                min_bid_increment,
+                highest_bid,
+                ..
+            } => match highest_bid {
+                Some(bid) => bid.amount + min_bid_increment,
+                None => *reserve_price,
+            },
+            AuctionKind::Dutch {
+                start_price,
+                end_price,
+            } => {
+                let now = now.clamp(self.start_time, self.end_time);
+                let elapsed = now.seconds() - self.start_time.seconds();
+                let duration = self.end_time.seconds() - self.start_time.seconds();
+                let decay = (*start_price - *end_price).multiply_ratio(elapsed, duration);
+                *start_price - decay
+            }
+        }
+    }
+}
+
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
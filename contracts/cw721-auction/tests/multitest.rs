@@ -0,0 +1,370 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Timestamp, Uint128};
+use cw721::msg::{CollectionExtensionMsg, OwnerOfResponse, RoyaltyInfoResponse};
+use cw721_auction::msg::{
+    CurrentPriceResponse, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use cw721_auction::state::{Auction, AuctionKind};
+use cw721_auction::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+
+const DENOM: &str = "uatom";
+const DURATION: u64 = 1_000;
+const EXTENSION: u64 = 100;
+
+struct Contracts {
+    nft_contract: Addr,
+    auction_contract: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    seller: Addr,
+    alice: Addr,
+    bob: Addr,
+    royalty_recipient: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        creator: api.addr_make("creator"),
+        seller: api.addr_make("seller"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+        royalty_recipient: api.addr_make("royalty_recipient"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        for bidder in [&users.alice, &users.bob] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(10_000, DENOM))
+                .unwrap();
+        }
+    });
+    (app, users)
+}
+
+/// Setup a cw721-base collection with 10% royalty and the auction contract, and mint a token to seller
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_auction::contract::*;
+    use cw721_base::msg as base_msg;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            users.creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: Some(CollectionExtensionMsg {
+                    description: Some("description".to_string()),
+                    image: Some("https://example.com/image.png".to_string()),
+                    external_link: None,
+                    explicit_content: None,
+                    start_trading_time: None,
+                    royalty_info: Some(RoyaltyInfoResponse {
+                        payment_address: users.royalty_recipient.to_string(),
+                        share: Decimal::percent(10),
+                    }),
+                }),
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+
+    let auction_contract = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {},
+            &[],
+            "auction".to_string(),
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        users.creator.clone(),
+        nft_contract.clone(),
+        &base_msg::ExecuteMsg::Mint {
+            token_id: "1".to_string(),
+            owner: users.seller.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    Contracts {
+        nft_contract,
+        auction_contract,
+    }
+}
+
+fn start_auction(app: &mut App, contracts: &Contracts, users: &Users, hook_msg: &Cw721HookMsg) {
+    app.execute_contract(
+        users.seller.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.auction_contract.to_string(),
+            token_id: "1".to_string(),
+            msg: to_json_binary(hook_msg).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn english_auction(end_time: Timestamp) -> Cw721HookMsg {
+    Cw721HookMsg::English {
+        denom: DENOM.to_string(),
+        reserve_price: Uint128::new(1_000),
+        min_bid_increment: Uint128::new(100),
+        extension_duration: EXTENSION,
+        start_time: None,
+        end_time,
+    }
+}
+
+fn bid(app: &mut App, contracts: &Contracts, bidder: &Addr, amount: u128) -> Option<ContractError> {
+    app.execute_contract(
+        bidder.clone(),
+        contracts.auction_contract.clone(),
+        &ExecuteMsg::PlaceBid { auction_id: 1 },
+        &coins(amount, DENOM),
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, contracts: &Contracts) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+fn balance(app: &App, address: &Addr) -> u128 {
+    app.wrap()
+        .query_balance(address, DENOM)
+        .unwrap()
+        .amount
+        .u128()
+}
+
+#[test]
+fn test_english_auction() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let end_time = app.block_info().time.plus_seconds(DURATION);
+    start_auction(&mut app, &contracts, &users, &english_auction(end_time));
+    assert_eq!(owner_of(&app, &contracts), contracts.auction_contract);
+
+    // first bid must meet reserve price
+    assert_eq!(
+        bid(&mut app, &contracts, &users.alice, 999),
+        Some(ContractError::BidTooLow {
+            min_bid: "1000".to_string()
+        })
+    );
+    assert_eq!(bid(&mut app, &contracts, &users.alice, 1_000), None);
+
+    // next bid must exceed highest bid by min increment
+    assert_eq!(
+        bid(&mut app, &contracts, &users.bob, 1_050),
+        Some(ContractError::BidTooLow {
+            min_bid: "1100".to_string()
+        })
+    );
+    assert_eq!(bid(&mut app, &contracts, &users.bob, 1_100), None);
+    // outbid bidder is refunded
+    assert_eq!(balance(&app, &users.alice), 10_000);
+    assert_eq!(balance(&app, &users.bob), 10_000 - 1_100);
+
+    // seller can't cancel once there are bids
+    let err: ContractError = app
+        .execute_contract(
+            users.seller.clone(),
+            contracts.auction_contract.clone(),
+            &ExecuteMsg::Cancel { auction_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::HasBids {});
+
+    // late bid extends the auction
+    app.update_block(|block| block.time = block.time.plus_seconds(DURATION - 10));
+    assert_eq!(bid(&mut app, &contracts, &users.alice, 1_200), None);
+    let auction: Auction = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.auction_contract.clone(),
+            &QueryMsg::Auction { auction_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        auction.end_time,
+        app.block_info().time.plus_seconds(EXTENSION)
+    );
+    assert!(matches!(
+        auction.kind,
+        AuctionKind::English {
+            highest_bid: Some(_),
+            ..
+        }
+    ));
+
+    // can't settle before end, can't bid after end
+    let err: ContractError = app
+        .execute_contract(
+            users.bob.clone(),
+            contracts.auction_contract.clone(),
+            &ExecuteMsg::Settle { auction_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AuctionNotEnded {});
+    app.update_block(|block| block.time = block.time.plus_seconds(EXTENSION));
+    assert_eq!(
+        bid(&mut app, &contracts, &users.bob, 2_000),
+        Some(ContractError::AuctionEnded {})
+    );
+
+    // anyone can settle
+    app.execute_contract(
+        users.bob.clone(),
+        contracts.auction_contract.clone(),
+        &ExecuteMsg::Settle { auction_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts), users.alice);
+    assert_eq!(balance(&app, &users.royalty_recipient), 120);
+    assert_eq!(balance(&app, &users.seller), 1_080);
+    assert_eq!(balance(&app, &users.bob), 10_000);
+}
+
+#[test]
+fn test_english_auction_without_bids() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let end_time = app.block_info().time.plus_seconds(DURATION);
+    start_auction(&mut app, &contracts, &users, &english_auction(end_time));
+
+    app.update_block(|block| block.time = end_time);
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.auction_contract.clone(),
+        &ExecuteMsg::Settle { auction_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts), users.seller);
+}
+
+#[test]
+fn test_dutch_auction() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let end_time = app.block_info().time.plus_seconds(DURATION);
+    start_auction(
+        &mut app,
+        &contracts,
+        &users,
+        &Cw721HookMsg::Dutch {
+            denom: DENOM.to_string(),
+            start_price: Uint128::new(5_000),
+            end_price: Uint128::new(1_000),
+            start_time: None,
+            end_time,
+        },
+    );
+
+    // price decays linearly
+    app.update_block(|block| block.time = block.time.plus_seconds(DURATION / 4));
+    let res: CurrentPriceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.auction_contract.clone(),
+            &QueryMsg::CurrentPrice { auction_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(res.price, Uint128::new(4_000));
+
+    assert_eq!(
+        bid(&mut app, &contracts, &users.alice, 3_999),
+        Some(ContractError::BidTooLow {
+            min_bid: "4000".to_string()
+        })
+    );
+    // first bid at current price wins, excess is refunded
+    assert_eq!(bid(&mut app, &contracts, &users.alice, 4_500), None);
+    assert_eq!(owner_of(&app, &contracts), users.alice);
+    assert_eq!(balance(&app, &users.alice), 10_000 - 4_000);
+    assert_eq!(balance(&app, &users.royalty_recipient), 400);
+    assert_eq!(balance(&app, &users.seller), 3_600);
+
+    assert_eq!(
+        bid(&mut app, &contracts, &users.bob, 4_000),
+        Some(ContractError::AuctionNotFound { auction_id: 1 })
+    );
+}
+
+#[test]
+fn test_cancel() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let end_time = app.block_info().time.plus_seconds(DURATION);
+    start_auction(&mut app, &contracts, &users, &english_auction(end_time));
+
+    let err: ContractError = app
+        .execute_contract(
+            users.alice.clone(),
+            contracts.auction_contract.clone(),
+            &ExecuteMsg::Cancel { auction_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        users.seller.clone(),
+        contracts.auction_contract.clone(),
+        &ExecuteMsg::Cancel { auction_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts), users.seller);
+    assert_eq!(
+        bid(&mut app, &contracts, &users.alice, 1_000),
+        Some(ContractError::AuctionNotFound { auction_id: 1 })
+    );
+}
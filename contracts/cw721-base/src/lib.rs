@@ -97,6 +97,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
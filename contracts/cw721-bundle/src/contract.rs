@@ -0,0 +1,223 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
+use cw721::extension::Cw721Extensions;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::{Cw721Calls, Cw721Execute, Cw721Query};
+use cw721::{
+    DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
+    DefaultOptionalNftExtensionMsg,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    BundleExecuteMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use crate::state::{Bundle, BundledToken, BUNDLE_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-bundle";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub type Cw721BundleContract<'a> = Cw721Extensions<
+    'a,
+    Option<Bundle>,
+    Option<Bundle>,
+    DefaultOptionalCollectionExtension,
+    DefaultOptionalCollectionExtensionMsg,
+    BundleExecuteMsg,
+    Empty,
+    Empty,
+>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // bundles are only minted by the contract itself, when wrapping tokens
+    msg.minter = Some(env.contract.address.to_string());
+    BUNDLE_COUNT.save(deps.storage, &0)?;
+    Ok(Cw721BundleContract::default().instantiate_with_version(
+        deps,
+        &env,
+        &info,
+        msg,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+    )?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_wrap(deps, env, info, receive_msg),
+        ExecuteMsg::UpdateExtension {
+            msg: BundleExecuteMsg::Unwrap { bundle_id },
+        } => execute_unwrap(deps, env, info, bundle_id),
+        ExecuteMsg::Mint { .. }
+        | ExecuteMsg::SafeMint { .. }
+        | ExecuteMsg::MintWithVoucher { .. }
+        | ExecuteMsg::SetVoucherSigner { .. }
+        | ExecuteMsg::RegisterEditionMaster { .. }
+        | ExecuteMsg::MintEdition { .. }
+        | ExecuteMsg::SetTemplate { .. }
+        | ExecuteMsg::MintFromTemplate { .. } => Err(ContractError::MintDisabled {}),
+        // burning or editing a bundle directly would lock or change its contents
+        ExecuteMsg::UpdateNftInfo { .. }
+        | ExecuteMsg::SetMetadataEditor { .. }
+        | ExecuteMsg::RemoveMetadataEditor { .. }
+        | ExecuteMsg::Burn { .. } => Err(ContractError::ContentsImmutable {}),
+        // attached bundles would be owned by this contract, and couldn't be unwrapped
+        ExecuteMsg::AttachToken { .. } | ExecuteMsg::DetachToken { .. } => {
+            Err(ContractError::AttachmentDisabled {})
+        }
+        // only messages keeping the contents of a bundle intact are supported
+        #[allow(deprecated)]
+        ExecuteMsg::UpdateOwnership(_)
+        | ExecuteMsg::UpdateMinterOwnership(_)
+        | ExecuteMsg::UpdateCreatorOwnership(_)
+        | ExecuteMsg::UpdateCollectionInfo { .. }
+        | ExecuteMsg::TransferNft { .. }
+        | ExecuteMsg::SendNft { .. }
+        | ExecuteMsg::SafeTransferNft { .. }
+        | ExecuteMsg::SendNftBatch { .. }
+        | ExecuteMsg::ProposeTransfer { .. }
+        | ExecuteMsg::AcceptTransfer { .. }
+        | ExecuteMsg::CancelTransfer { .. }
+        | ExecuteMsg::Approve { .. }
+        | ExecuteMsg::Revoke { .. }
+        | ExecuteMsg::Permit { .. }
+        | ExecuteMsg::SetUser { .. }
+        | ExecuteMsg::ApproveAll { .. }
+        | ExecuteMsg::RevokeAll { .. }
+        | ExecuteMsg::LockToken { .. }
+        | ExecuteMsg::UnlockToken { .. }
+        | ExecuteMsg::SetUnlockDelay { .. }
+        | ExecuteMsg::EnableOwnerSnapshots {}
+        | ExecuteMsg::SetWithdrawAddress { .. }
+        | ExecuteMsg::RemoveWithdrawAddress {}
+        | ExecuteMsg::WithdrawFunds { .. } => {
+            Ok(Cw721BundleContract::default().execute(deps, &env, &info, msg)?)
+        }
+    }
+}
+
+/// Wraps the received token, either into a new bundle or an existing one of the sender.
+pub fn execute_wrap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw721HookMsg::Wrap { bundle_id } = from_json(&receive_msg.msg)?;
+    let token = BundledToken {
+        collection: info.sender,
+        token_id: receive_msg.token_id,
+    };
+    let contract = Cw721BundleContract::default();
+
+    let (res, bundle_id) = match bundle_id {
+        Some(bundle_id) => {
+            let mut nft_info = contract.config.nft_info.load(deps.storage, &bundle_id)?;
+            if nft_info.owner != receive_msg.sender {
+                return Err(ContractError::NotBundleOwner {});
+            }
+            nft_info
+                .extension
+                .get_or_insert_with(Bundle::default)
+                .tokens
+                .push(token.clone());
+            contract
+                .config
+                .nft_info
+                .save(deps.storage, &bundle_id, &nft_info)?;
+            (Response::new(), bundle_id)
+        }
+        None => {
+            let bundle_id = BUNDLE_COUNT
+                .update(deps.storage, |count| StdResult::Ok(count + 1))?
+                .to_string();
+            let minter_info = MessageInfo {
+                sender: env.contract.address.clone(),
+                funds: vec![],
+            };
+            let res = contract.mint(
+                deps,
+                &env,
+                &minter_info,
+                bundle_id.clone(),
+                receive_msg.sender,
+                None,
+                Some(Bundle {
+                    tokens: vec![token.clone()],
+                }),
+            )?;
+            (res, bundle_id)
+        }
+    };
+
+    Ok(res
+        .add_attribute("action", "wrap")
+        .add_attribute("bundle_id", bundle_id)
+        .add_attribute("collection", token.collection.to_string())
+        .add_attribute("token_id", token.token_id))
+}
+
+fn transfer_nft_msg(token: &BundledToken, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: token.token_id.clone(),
+    };
+    DefaultCw721Helper::new(token.collection.clone()).call(transfer_msg)
+}
+
+/// Burns the bundle and releases all wrapped tokens to its owner.
+pub fn execute_unwrap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bundle_id: String,
+) -> Result<Response, ContractError> {
+    let contract = Cw721BundleContract::default();
+    let nft_info = contract.config.nft_info.load(deps.storage, &bundle_id)?;
+    // checks the sender is the owner, approved or operator
+    let res = contract.burn_nft(deps, &env, &info, bundle_id.clone())?;
+
+    let tokens = nft_info.extension.unwrap_or_default().tokens;
+    let msgs = tokens
+        .iter()
+        .map(|token| transfer_nft_msg(token, &nft_info.owner))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(res
+        .add_messages(msgs)
+        .add_attribute("action", "unwrap")
+        .add_attribute("bundle_id", bundle_id)
+        .add_attribute("recipient", nft_info.owner.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    Ok(Cw721BundleContract::default().query(deps, &env, msg)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Cw721BundleContract::default().migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)?)
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::StdError;
+use cw721::error::Cw721ContractError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Base(#[from] Cw721ContractError),
+
+    #[error("Only the owner of the bundle can add tokens to it")]
+    NotBundleOwner {},
+
+    #[error("Bundles are minted by wrapping tokens")]
+    MintDisabled {},
+
+    #[error("The contents of a bundle can't be changed, unwrap it instead")]
+    ContentsImmutable {},
+
+    #[error("Bundles can't be attached to other tokens")]
+    AttachmentDisabled {},
+}
@@ -0,0 +1,30 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Empty;
+use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg};
+use cw721::traits::Cw721CustomMsg;
+use cw721::{DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg};
+
+use crate::state::Bundle;
+
+/// Bundle specific messages, sent via `ExecuteMsg::UpdateExtension`.
+#[cw_serde]
+pub enum BundleExecuteMsg {
+    /// Burns the bundle and releases all wrapped tokens to its owner.
+    Unwrap { bundle_id: String },
+}
+
+impl Cw721CustomMsg for BundleExecuteMsg {}
+
+/// Message passed along with `SendNft` to this contract.
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Wraps the received token. Mints a new bundle to the sender if `bundle_id` is not set,
+    /// otherwise adds the token to the sender's existing bundle.
+    Wrap { bundle_id: Option<String> },
+}
+
+pub type InstantiateMsg = Cw721InstantiateMsg<DefaultOptionalCollectionExtensionMsg>;
+pub type ExecuteMsg =
+    Cw721ExecuteMsg<Option<Bundle>, DefaultOptionalCollectionExtensionMsg, BundleExecuteMsg>;
+pub type QueryMsg = Cw721QueryMsg<Option<Bundle>, DefaultOptionalCollectionExtension, Empty>;
+pub type MigrateMsg = Cw721MigrateMsg;
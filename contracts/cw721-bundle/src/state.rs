@@ -0,0 +1,62 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, Env, MessageInfo};
+use cw721::error::Cw721ContractError;
+use cw721::traits::{Contains, Cw721CustomMsg, Cw721State, StateFactory, WithOverrides};
+use cw_storage_plus::Item;
+
+/// A token held by this contract on behalf of a bundle.
+#[cw_serde]
+pub struct BundledToken {
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+/// Onchain extension of a bundle NFT, listing the wrapped tokens.
+#[cw_serde]
+#[derive(Default)]
+pub struct Bundle {
+    pub tokens: Vec<BundledToken>,
+}
+
+impl Cw721State for Bundle {}
+impl Cw721CustomMsg for Bundle {}
+
+impl Contains for Bundle {
+    fn contains(&self, other: &Bundle) -> bool {
+        other.tokens.iter().all(|token| self.tokens.contains(token))
+    }
+}
+
+/// Bundles are never minted from templates or as editions, contents are unique.
+impl WithOverrides for Bundle {
+    fn with_overrides(&self, overrides: &Self) -> Self {
+        overrides.clone()
+    }
+}
+
+impl StateFactory<Bundle> for Bundle {
+    fn create(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: Option<&MessageInfo>,
+        current: Option<&Bundle>,
+    ) -> Result<Bundle, Cw721ContractError> {
+        self.validate(deps, env, info, current)?;
+        Ok(self.clone())
+    }
+
+    fn validate(
+        &self,
+        _deps: Deps,
+        _env: &Env,
+        _info: Option<&MessageInfo>,
+        _current: Option<&Bundle>,
+    ) -> Result<(), Cw721ContractError> {
+        // bundles are only created by this contract, from received tokens
+        Ok(())
+    }
+}
+
+/// Number of bundles minted so far, used to assign bundle ids.
+pub const BUNDLE_COUNT: Item<u64> = Item::new("bundle_count");
@@ -0,0 +1,393 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{to_json_binary, Addr};
+use cw721::error::Cw721ContractError;
+use cw721::msg::{NftInfoResponse, NumTokensResponse, OwnerOfResponse};
+use cw721::OwnershipError;
+use cw721_bundle::msg::{BundleExecuteMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw721_bundle::state::{Bundle, BundledToken};
+use cw721_bundle::ContractError;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+struct Contracts {
+    art: Addr,
+    games: Addr,
+    bundle: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    alice: Addr,
+    bob: Addr,
+}
+
+fn setup_users() -> Users {
+    let api = MockApi::default();
+    Users {
+        creator: api.addr_make("creator"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+    }
+}
+
+/// Setup two cw721-base collections "art" and "games" with tokens "1" and "2" owned by alice and
+/// bob, and the bundle contract
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_bundle::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let mut collections = vec![];
+    for name in ["art", "games"] {
+        let nft_contract = app
+            .instantiate_contract(
+                nft_code_id,
+                users.creator.clone(),
+                &base_msg::InstantiateMsg {
+                    name: name.to_string(),
+                    symbol: name.to_uppercase(),
+                    collection_info_extension: None,
+                    minter: None,
+                    creator: None,
+                    withdraw_address: None,
+                    ics721_bridge: None,
+                },
+                &[],
+                name.to_string(),
+                None,
+            )
+            .unwrap();
+        for (token_id, owner) in [("1", &users.alice), ("2", &users.bob)] {
+            app.execute_contract(
+                users.creator.clone(),
+                nft_contract.clone(),
+                &base_msg::ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: None,
+                    extension: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        collections.push(nft_contract);
+    }
+
+    let bundle = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {
+                name: "bundles".to_string(),
+                symbol: "BUNDLE".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "bundles".to_string(),
+            None,
+        )
+        .unwrap();
+
+    Contracts {
+        art: collections[0].clone(),
+        games: collections[1].clone(),
+        bundle,
+    }
+}
+
+fn wrap(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    bundle_id: Option<&str>,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        collection.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.bundle.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Cw721HookMsg::Wrap {
+                bundle_id: bundle_id.map(ToString::to_string),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn unwrap(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    bundle_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.bundle.clone(),
+        &ExecuteMsg::UpdateExtension {
+            msg: BundleExecuteMsg::Unwrap {
+                bundle_id: bundle_id.to_string(),
+            },
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, collection: &Addr, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            collection.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_wrap_and_unwrap() {
+    let mut app = App::default();
+    let users = setup_users();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // tokens from several collections end up in one bundle
+    assert_eq!(
+        wrap(
+            &mut app,
+            &contracts,
+            &users.alice,
+            &contracts.art,
+            "1",
+            None
+        ),
+        None
+    );
+    assert_eq!(
+        wrap(
+            &mut app,
+            &contracts,
+            &users.alice,
+            &contracts.games,
+            "1",
+            Some("1")
+        ),
+        None
+    );
+    assert_eq!(owner_of(&app, &contracts.art, "1"), contracts.bundle);
+    assert_eq!(owner_of(&app, &contracts.games, "1"), contracts.bundle);
+    assert_eq!(owner_of(&app, &contracts.bundle, "1"), users.alice);
+    let res: NftInfoResponse<Option<Bundle>> = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.bundle.clone(),
+            &QueryMsg::NftInfo {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.extension,
+        Some(Bundle {
+            tokens: vec![
+                BundledToken {
+                    collection: contracts.art.clone(),
+                    token_id: "1".to_string(),
+                },
+                BundledToken {
+                    collection: contracts.games.clone(),
+                    token_id: "1".to_string(),
+                },
+            ],
+        })
+    );
+
+    // only the bundle owner can add tokens to it
+    assert_eq!(
+        wrap(
+            &mut app,
+            &contracts,
+            &users.bob,
+            &contracts.art,
+            "2",
+            Some("1")
+        ),
+        Some(ContractError::NotBundleOwner {})
+    );
+    assert_eq!(owner_of(&app, &contracts.art, "2"), users.bob);
+
+    // the bundle trades as a single token
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.bundle.clone(),
+        &ExecuteMsg::TransferNft {
+            recipient: users.bob.to_string(),
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        unwrap(&mut app, &contracts, &users.alice, "1"),
+        Some(ContractError::Base(Cw721ContractError::Ownership(
+            OwnershipError::NotOwner
+        )))
+    );
+
+    // unwrapping releases the contents to the holder and burns the bundle
+    assert_eq!(unwrap(&mut app, &contracts, &users.bob, "1"), None);
+    assert_eq!(owner_of(&app, &contracts.art, "1"), users.bob);
+    assert_eq!(owner_of(&app, &contracts.games, "1"), users.bob);
+    let res: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(contracts.bundle.clone(), &QueryMsg::NumTokens {})
+        .unwrap();
+    assert_eq!(res.count, 0);
+}
+
+#[test]
+fn test_lock_and_unwrap() {
+    let mut app = App::default();
+    let users = setup_users();
+    let contracts = setup_contracts(&mut app, &users);
+    assert_eq!(
+        wrap(
+            &mut app,
+            &contracts,
+            &users.alice,
+            &contracts.art,
+            "1",
+            None
+        ),
+        None
+    );
+
+    // locking keeps the contents, but the bundle can't be unwrapped until unlocked
+    let execute = |app: &mut App, msg: &ExecuteMsg| {
+        app.execute_contract(users.alice.clone(), contracts.bundle.clone(), msg, &[])
+            .unwrap()
+    };
+    execute(
+        &mut app,
+        &ExecuteMsg::LockToken {
+            token_id: "1".to_string(),
+        },
+    );
+    let res: NftInfoResponse<Option<Bundle>> = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.bundle.clone(),
+            &QueryMsg::NftInfo {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.extension,
+        Some(Bundle {
+            tokens: vec![BundledToken {
+                collection: contracts.art.clone(),
+                token_id: "1".to_string(),
+            }],
+        })
+    );
+    assert_eq!(
+        unwrap(&mut app, &contracts, &users.alice, "1"),
+        Some(ContractError::Base(Cw721ContractError::TokenLocked {
+            token_id: "1".to_string()
+        }))
+    );
+
+    execute(
+        &mut app,
+        &ExecuteMsg::UnlockToken {
+            token_id: "1".to_string(),
+        },
+    );
+    app.update_block(|block| block.time = block.time.plus_days(1));
+    assert_eq!(unwrap(&mut app, &contracts, &users.alice, "1"), None);
+    assert_eq!(owner_of(&app, &contracts.art, "1"), users.alice);
+}
+
+#[test]
+fn test_bundles_only_minted_by_wrapping() {
+    let mut app = App::default();
+    let users = setup_users();
+    let contracts = setup_contracts(&mut app, &users);
+
+    let err: ContractError = app
+        .execute_contract(
+            users.creator.clone(),
+            contracts.bundle.clone(),
+            &ExecuteMsg::Mint {
+                token_id: "fake".to_string(),
+                owner: users.alice.to_string(),
+                token_uri: None,
+                extension: Some(Bundle {
+                    tokens: vec![BundledToken {
+                        collection: contracts.art.clone(),
+                        token_id: "2".to_string(),
+                    }],
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MintDisabled {});
+
+    // contents can't be dropped by burning the bundle
+    assert_eq!(
+        wrap(&mut app, &contracts, &users.bob, &contracts.art, "2", None),
+        None
+    );
+    let err: ContractError = app
+        .execute_contract(
+            users.bob.clone(),
+            contracts.bundle.clone(),
+            &ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ContentsImmutable {});
+
+    // bundles can't be moved under another token
+    let err: ContractError = app
+        .execute_contract(
+            users.bob.clone(),
+            contracts.bundle.clone(),
+            &ExecuteMsg::AttachToken {
+                token_id: "1".to_string(),
+                parent_token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AttachmentDisabled {});
+}
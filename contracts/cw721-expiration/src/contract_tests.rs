@@ -1,14 +1,15 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Response, StdError, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, DepsMut, Response, StdError, WasmMsg,
 };
 
 use cw721::error::Cw721ContractError;
+use cw721::events::{ApproveEvent, TransferEvent};
 use cw721::msg::CollectionInfoAndExtensionResponse;
 use cw721::msg::{
-    ApprovalResponse, Cw721ExecuteMsg, NftInfoResponse, OperatorResponse, OperatorsResponse,
-    OwnerOfResponse, TokensResponse,
+    ApprovalResponse, AttachTokenMsg, Cw721ExecuteMsg, NftInfoResponse, OperatorResponse,
+    OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
 use cw721::receiver::Cw721ReceiveMsg;
 use cw721::state::{CREATOR, MINTER};
@@ -239,6 +240,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -271,6 +273,110 @@ fn test_mint() {
     assert_eq!(vec![token_id], tokens.tokens);
 }
 
+#[test]
+fn test_mint_edition() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+
+    let register_msg = Cw721ExecuteMsg::RegisterEditionMaster {
+        master_id: "print".to_string(),
+        token_uri: None,
+        extension: None,
+        max_size: 2,
+    };
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), creator, register_msg)
+        .unwrap();
+
+    // minter can mint editions
+    let mint_msg = Cw721ExecuteMsg::MintEdition {
+        master_id: "print".to_string(),
+        owner: String::from("medusa"),
+    };
+    let allowed = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), allowed.clone(), mint_msg)
+        .unwrap();
+
+    // assert mint timestamp is set, so the edition is valid
+    let mint_timestamp = contract
+        .mint_timestamps
+        .load(deps.as_ref().storage, "print/1")
+        .unwrap();
+    assert_eq!(mint_timestamp, mock_env().block.time);
+    let owner = contract
+        .query_owner_of_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            "print/1".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+    assert_eq!(owner.owner, "medusa");
+
+    // unknown masters are rejected
+    let mint_msg = Cw721ExecuteMsg::MintEdition {
+        master_id: "unknown".to_string(),
+        owner: String::from("medusa"),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), allowed, mint_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Cw721(Cw721ContractError::EditionMasterNotFound {
+            master_id: "unknown".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_mint_from_template() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+
+    let template_msg = Cw721ExecuteMsg::SetTemplate {
+        template_id: "ticket".to_string(),
+        extension: None,
+    };
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), creator, template_msg)
+        .unwrap();
+
+    // minter can mint from templates
+    let mint_msg = Cw721ExecuteMsg::MintFromTemplate {
+        token_id: "atomize".to_string(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        template_id: "ticket".to_string(),
+        overrides: None,
+    };
+    let allowed = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), allowed, mint_msg)
+        .unwrap();
+
+    // assert mint timestamp is set, so the token is valid
+    let mint_timestamp = contract
+        .mint_timestamps
+        .load(deps.as_ref().storage, "atomize")
+        .unwrap();
+    assert_eq!(mint_timestamp, mock_env().block.time);
+    let owner = contract
+        .query_owner_of_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            "atomize".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+    assert_eq!(owner.owner, "medusa");
+}
+
 #[test]
 fn test_update_minter() {
     let mut deps = mock_dependencies();
@@ -507,6 +613,15 @@ fn test_transfer_nft() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from(owner),
+                    previous_owner: String::from(owner),
+                    recipient: String::from(new_owner),
+                }
+                .into()
+            )
             .add_attribute("action", "transfer_nft")
             .add_attribute("sender", owner)
             .add_attribute("recipient", new_owner)
@@ -593,6 +708,15 @@ fn test_send_nft() {
         res,
         Response::new()
             .add_message(expected)
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("venus"),
+                    previous_owner: String::from("venus"),
+                    recipient: target.clone(),
+                }
+                .into()
+            )
             .add_attribute("action", "send_nft")
             .add_attribute("sender", "venus")
             .add_attribute("recipient", "another_contract")
@@ -616,6 +740,135 @@ fn test_send_nft() {
     );
 }
 
+#[test]
+fn test_attach_detach_token() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+
+    // mint some NFTs
+    let mut env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["character", "hat", "gem"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: MINTER_ADDR.to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        contract
+            .execute(deps.as_mut(), env.clone(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    let attach_msg = |token_id: &str| Cw721ExecuteMsg::AttachToken {
+        token_id: token_id.to_string(),
+        parent_token_id: "character".to_string(),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            attach_msg("hat"),
+        )
+        .unwrap();
+    let mint_date = env.block.time;
+    let expiration = env.block.time.plus_days(1);
+
+    // mint another NFT later, so it is still valid when its parent expires
+    env.block.time = mint_date.plus_hours(12);
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "scarf".to_string(),
+        owner: MINTER_ADDR.to_string(),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), minter.clone(), mint_msg)
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            attach_msg("scarf"),
+        )
+        .unwrap();
+
+    // assert invalid nft throws error
+    env.block.time = expiration;
+    let error = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            attach_msg("gem"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: "gem".to_string(),
+            mint_date,
+            expiration
+        }
+    );
+
+    // assert tokens of other collections can't be attached to an invalid parent
+    let receive_msg = Cw721ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: MINTER_ADDR.to_string(),
+        token_id: "sword".to_string(),
+        msg: to_json_binary(&AttachTokenMsg {
+            parent_token_id: "character".to_string(),
+        })
+        .unwrap(),
+    });
+    let error = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other_collection", &[]),
+            receive_msg,
+        )
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: "character".to_string(),
+            mint_date,
+            expiration
+        }
+    );
+
+    // assert valid children can be detached from an invalid parent
+    let detach_msg = |token_id: &str| Cw721ExecuteMsg::DetachToken {
+        parent_token_id: "character".to_string(),
+        collection: None,
+        token_id: token_id.to_string(),
+        recipient: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            detach_msg("scarf"),
+        )
+        .unwrap();
+
+    // assert invalid children can't be detached
+    let error = contract
+        .execute(deps.as_mut(), env, minter, detach_msg("hat"))
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: "hat".to_string(),
+            mint_date,
+            expiration
+        }
+    );
+}
+
 #[test]
 fn test_approve_revoke() {
     let mut deps = mock_dependencies();
@@ -672,6 +925,16 @@ fn test_approve_revoke() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                ApproveEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("demeter"),
+                    owner: String::from("demeter"),
+                    spender: String::from("random"),
+                    expires: Expiration::Never {},
+                }
+                .into()
+            )
             .add_attribute("action", "approve")
             .add_attribute("sender", "demeter")
             .add_attribute("spender", "random")
@@ -726,6 +989,7 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -770,6 +1034,7 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -791,7 +1056,28 @@ fn test_approve_revoke() {
 
     // assert revoke of invalid nft throws error
     let error = contract
-        .execute(deps.as_mut(), env, owner, revoke_msg)
+        .execute(deps.as_mut(), env.clone(), owner, revoke_msg)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: token_id.clone(),
+            mint_date,
+            expiration
+        }
+    );
+
+    // assert permit of invalid nft throws error, before checking the signature
+    let permit_msg = Cw721ExecuteMsg::Permit {
+        token_id: token_id.clone(),
+        spender: String::from("random"),
+        expires: None,
+        nonce: 0,
+        signature: Binary::default(),
+        pubkey: Binary::default(),
+    };
+    let error = contract
+        .execute(deps.as_mut(), env, mock_info("random", &[]), permit_msg)
         .unwrap_err();
     assert_eq!(
         error,
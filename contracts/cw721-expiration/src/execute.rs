@@ -2,9 +2,10 @@ use crate::{
     error::ContractError, msg::InstantiateMsg, state::DefaultCw721ExpirationContract,
     CONTRACT_NAME, CONTRACT_VERSION,
 };
-use cosmwasm_std::{Binary, DepsMut, Empty, Env, MessageInfo, Response};
+use cosmwasm_std::{from_json, Binary, DepsMut, Empty, Env, MessageInfo, Response};
 use cw721::{
-    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg},
+    msg::{AttachTokenMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintVoucher},
+    receiver::Cw721ReceiveMsg,
     traits::Cw721Execute,
     Expiration,
 };
@@ -37,6 +38,7 @@ impl DefaultCw721ExpirationContract<'static> {
                 minter: msg.minter,
                 creator: msg.creator,
                 withdraw_address: msg.withdraw_address,
+                ics721_bridge: None,
             },
             CONTRACT_NAME,
             CONTRACT_VERSION,
@@ -65,6 +67,37 @@ impl DefaultCw721ExpirationContract<'static> {
             } => {
                 contract.mint_with_timestamp(deps, env, info, token_id, owner, token_uri, extension)
             }
+            Cw721ExecuteMsg::SafeMint {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                msg,
+            } => contract.safe_mint_with_timestamp(
+                deps, env, info, token_id, owner, token_uri, extension, msg,
+            ),
+            Cw721ExecuteMsg::MintWithVoucher { voucher, signature } => {
+                contract.mint_with_voucher_and_timestamp(deps, env, info, voucher, signature)
+            }
+            Cw721ExecuteMsg::MintEdition { master_id, owner } => {
+                contract.mint_edition_with_timestamp(deps, env, info, master_id, owner)
+            }
+            Cw721ExecuteMsg::MintFromTemplate {
+                token_id,
+                owner,
+                token_uri,
+                template_id,
+                overrides,
+            } => contract.mint_from_template_with_timestamp(
+                deps,
+                env,
+                info,
+                token_id,
+                owner,
+                token_uri,
+                template_id,
+                overrides,
+            ),
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -73,6 +106,21 @@ impl DefaultCw721ExpirationContract<'static> {
             Cw721ExecuteMsg::Revoke { spender, token_id } => {
                 contract.revoke_include_nft_expired(deps, env, info, spender, token_id)
             }
+            Cw721ExecuteMsg::Permit {
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+                pubkey,
+            } => contract.permit_include_nft_expired(
+                deps, env, info, token_id, spender, expires, nonce, signature, pubkey,
+            ),
+            Cw721ExecuteMsg::SetUser {
+                token_id,
+                user,
+                expires,
+            } => contract.set_user_include_nft_expired(deps, env, info, token_id, user, expires),
             Cw721ExecuteMsg::TransferNft {
                 recipient,
                 token_id,
@@ -82,9 +130,58 @@ impl DefaultCw721ExpirationContract<'static> {
                 token_id,
                 msg,
             } => contract.send_nft_include_nft_expired(deps, env, info, recipient, token_id, msg),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient,
+                token_id,
+                msg,
+            } => contract
+                .safe_transfer_nft_include_nft_expired(deps, env, info, recipient, token_id, msg),
+            Cw721ExecuteMsg::SendNftBatch {
+                contract: recipient,
+                token_ids,
+                msg,
+            } => contract
+                .send_nft_batch_include_nft_expired(deps, env, info, recipient, token_ids, msg),
+            Cw721ExecuteMsg::ProposeTransfer {
+                recipient,
+                token_id,
+                expires,
+            } => contract.propose_transfer_include_nft_expired(
+                deps, env, info, recipient, token_id, expires,
+            ),
+            Cw721ExecuteMsg::AcceptTransfer { token_id } => {
+                contract.accept_transfer_include_nft_expired(deps, env, info, token_id)
+            }
             Cw721ExecuteMsg::Burn { token_id } => {
                 contract.burn_nft_include_nft_expired(deps, env, info, token_id)
             }
+            Cw721ExecuteMsg::AttachToken {
+                token_id,
+                parent_token_id,
+            } => contract.attach_token_include_nft_expired(
+                deps,
+                env,
+                info,
+                token_id,
+                parent_token_id,
+            ),
+            Cw721ExecuteMsg::DetachToken {
+                parent_token_id,
+                collection,
+                token_id,
+                recipient,
+            } => contract.detach_token_include_nft_expired(
+                deps,
+                env,
+                info,
+                parent_token_id,
+                collection,
+                token_id,
+                recipient,
+            ),
+            Cw721ExecuteMsg::ReceiveNft(receive_msg) => {
+                contract.receive_nft_include_nft_expired(deps, env, info, receive_msg)
+            }
             _ => {
                 let response = contract.base_contract.execute(deps, &env, &info, msg)?;
                 Ok(response)
@@ -113,6 +210,106 @@ impl DefaultCw721ExpirationContract<'static> {
         Ok(res)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn safe_mint_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: DefaultOptionalNftExtensionMsg,
+        msg: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &token_id, &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .safe_mint(
+                deps, &env, &info, token_id, owner, token_uri, extension, msg,
+            )?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    pub fn mint_with_voucher_and_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        voucher: MintVoucher<DefaultOptionalNftExtensionMsg>,
+        signature: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &voucher.token_id, &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .mint_with_voucher(deps, &env, &info, voucher, signature)?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    pub fn mint_edition_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        master_id: String,
+        owner: String,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        // unknown masters are rejected by the base contract
+        if let Some(master) = self
+            .base_contract
+            .config
+            .edition_masters
+            .may_load(deps.storage, &master_id)?
+        {
+            let token_id = format!("{}/{}", master_id, master.minted + 1);
+            self.mint_timestamps
+                .save(deps.storage, &token_id, &mint_timstamp)?;
+        }
+        let res = self
+            .base_contract
+            .mint_edition(deps, &env, &info, master_id, owner)?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_from_template_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        template_id: String,
+        overrides: DefaultOptionalNftExtensionMsg,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &token_id, &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .mint_from_template(
+                deps,
+                &env,
+                &info,
+                token_id,
+                owner,
+                token_uri,
+                template_id,
+                overrides,
+            )?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
     pub fn approve_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -142,6 +339,40 @@ impl DefaultCw721ExpirationContract<'static> {
             .revoke(deps, &env, &info, spender, token_id)?)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self.base_contract.permit(
+            deps, &env, &info, token_id, spender, expires, nonce, signature, pubkey,
+        )?)
+    }
+
+    pub fn set_user_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .set_user(deps, &env, &info, token_id, user, expires)?)
+    }
+
     pub fn transfer_nft_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -171,6 +402,66 @@ impl DefaultCw721ExpirationContract<'static> {
             .send_nft(deps, &env, &info, contract, token_id, msg)?)
     }
 
+    pub fn safe_transfer_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+        msg: Option<Binary>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .safe_transfer_nft(deps, &env, &info, recipient, token_id, msg)?)
+    }
+
+    pub fn send_nft_batch_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        }
+        Ok(self
+            .base_contract
+            .send_nft_batch(deps, &env, &info, contract, token_ids, msg)?)
+    }
+
+    pub fn propose_transfer_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .propose_transfer(deps, &env, &info, recipient, token_id, expires)?)
+    }
+
+    pub fn accept_transfer_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .accept_transfer(deps, &env, &info, token_id)?)
+    }
+
     pub fn burn_nft_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -182,6 +473,63 @@ impl DefaultCw721ExpirationContract<'static> {
         Ok(self.base_contract.burn_nft(deps, &env, &info, token_id)?)
     }
 
+    pub fn attach_token_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        parent_token_id: String,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        self.assert_nft_expired(deps.as_ref(), &env, parent_token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .attach_token(deps, &env, &info, token_id, parent_token_id)?)
+    }
+
+    /// Attaches a token of another collection, sent via `SendNft`, to a valid parent.
+    pub fn receive_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receive_msg: Cw721ReceiveMsg,
+    ) -> Result<Response<Empty>, ContractError> {
+        let AttachTokenMsg { parent_token_id } = from_json(&receive_msg.msg)?;
+        self.assert_nft_expired(deps.as_ref(), &env, parent_token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .receive_nft(deps, &env, &info, receive_msg)?)
+    }
+
+    /// Children can be detached from expired parents, so they don't get stuck.
+    #[allow(clippy::too_many_arguments)]
+    pub fn detach_token_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        parent_token_id: String,
+        collection: Option<String>,
+        token_id: String,
+        recipient: Option<String>,
+    ) -> Result<Response<Empty>, ContractError> {
+        // tokens of other collections don't expire here
+        if collection.is_none() || collection.as_deref() == Some(env.contract.address.as_str()) {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        }
+        Ok(self.base_contract.detach_token(
+            deps,
+            &env,
+            &info,
+            parent_token_id,
+            collection,
+            token_id,
+            recipient,
+        )?)
+    }
+
     // -- migrate --
     pub fn migrate(
         &self,
@@ -66,6 +66,7 @@ pub fn instantiate(
                 minter: None,
                 creator: None,
                 withdraw_address: msg.withdraw_address,
+                ics721_bridge: None,
             })?,
             funds: vec![],
             admin: None,
@@ -240,6 +241,7 @@ mod tests {
                         minter: None,
                         creator: None,
                         withdraw_address: None,
+                        ics721_bridge: None,
                     })
                     .unwrap(),
                     funds: vec![],
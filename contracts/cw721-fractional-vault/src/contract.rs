@@ -0,0 +1,411 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{
+    Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse,
+};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+
+use crate::error::ContractError;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ShareToken, VaultsResponse,
+};
+use crate::state::{Bid, Config, Vault, VaultStatus, CONFIG, VAULTS, VAULT_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-fractional-vault";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            cw20_code_id: msg.cw20_code_id,
+        },
+    )?;
+    VAULT_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("cw20_code_id", msg.cw20_code_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, env, info, receive_msg),
+        ExecuteMsg::Receive(receive_msg) => execute_receive(deps, info, receive_msg),
+        ExecuteMsg::Bid { vault_id } => execute_bid(deps, env, info, vault_id),
+        ExecuteMsg::SettleBuyout { vault_id } => execute_settle_buyout(deps, env, vault_id),
+    }
+}
+
+/// Stores the address of a newly instantiated share token. The reply id is the vault id.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let vault_id = msg.id;
+    let mut vault = load_vault(deps.as_ref(), vault_id)?;
+    let res = parse_reply_instantiate_data(msg)?;
+    let share_token = deps.api.addr_validate(&res.contract_address)?;
+    vault.share_token = Some(share_token.clone());
+    VAULTS.save(deps.storage, vault_id, &vault)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "share_token_instantiated")
+        .add_attribute("vault_id", vault_id.to_string())
+        .add_attribute("share_token", share_token.to_string()))
+}
+
+fn load_vault(deps: Deps, vault_id: u64) -> Result<Vault, ContractError> {
+    VAULTS
+        .may_load(deps.storage, vault_id)?
+        .ok_or(ContractError::VaultNotFound { vault_id })
+}
+
+fn transfer_nft_msg(vault: &Vault, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: vault.token_id.clone(),
+    };
+    DefaultCw721Helper::new(vault.collection.clone()).call(transfer_msg)
+}
+
+fn burn_shares_msg(share_token: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: share_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw721HookMsg::Fractionalize {
+        share_token,
+        supply,
+        buyout,
+    } = from_json(&receive_msg.msg)?;
+    if supply.is_zero() {
+        return Err(ContractError::InvalidSupply {});
+    }
+    let depositor = deps.api.addr_validate(&receive_msg.sender)?;
+    let vault_id = VAULT_COUNT.update(deps.storage, |count| StdResult::Ok(count + 1))?;
+    let mut vault = Vault {
+        id: vault_id,
+        collection: info.sender,
+        token_id: receive_msg.token_id,
+        depositor,
+        share_token: None,
+        supply,
+        buyout,
+        status: VaultStatus::Active,
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let response = match share_token {
+        ShareToken::New {
+            name,
+            symbol,
+            decimals,
+        } => {
+            // no minter, so the supply is fixed
+            let instantiate_msg = WasmMsg::Instantiate {
+                admin: None,
+                code_id: config.cw20_code_id,
+                msg: to_json_binary(&Cw20InstantiateMsg {
+                    name,
+                    symbol,
+                    decimals,
+                    initial_balances: vec![Cw20Coin {
+                        address: vault.depositor.to_string(),
+                        amount: supply,
+                    }],
+                    mint: None,
+                    marketing: None,
+                })?,
+                funds: vec![],
+                label: format!("shares of vault {}", vault_id),
+            };
+            Response::new().add_submessage(SubMsg::reply_on_success(instantiate_msg, vault_id))
+        }
+        ShareToken::Existing { address } => {
+            let share_token = deps.api.addr_validate(&address)?;
+            // the token's own answers can't be trusted, unless it runs the configured cw20 code
+            let contract_info = deps.querier.query_wasm_contract_info(&share_token)?;
+            if contract_info.code_id != config.cw20_code_id {
+                return Err(ContractError::InvalidShareToken {});
+            }
+            // shares must not exist before, and nobody else may mint them
+            let minter: Option<MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&share_token, &Cw20QueryMsg::Minter {})?;
+            let token_info: TokenInfoResponse = deps
+                .querier
+                .query_wasm_smart(&share_token, &Cw20QueryMsg::TokenInfo {})?;
+            if minter.map(|m| m.minter) != Some(env.contract.address.to_string())
+                || !token_info.total_supply.is_zero()
+            {
+                return Err(ContractError::InvalidShareToken {});
+            }
+            let mint_msg = WasmMsg::Execute {
+                contract_addr: share_token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: vault.depositor.to_string(),
+                    amount: supply,
+                })?,
+                funds: vec![],
+            };
+            vault.share_token = Some(share_token);
+            Response::new().add_message(mint_msg)
+        }
+    };
+    VAULTS.save(deps.storage, vault_id, &vault)?;
+
+    Ok(response
+        .add_attribute("action", "fractionalize")
+        .add_attribute("vault_id", vault_id.to_string())
+        .add_attribute("collection", vault.collection.to_string())
+        .add_attribute("token_id", vault.token_id)
+        .add_attribute("depositor", vault.depositor.to_string())
+        .add_attribute("supply", supply.to_string()))
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let hook_msg: Cw20HookMsg = from_json(&receive_msg.msg)?;
+    let vault_id = match hook_msg {
+        Cw20HookMsg::Redeem { vault_id } | Cw20HookMsg::Claim { vault_id } => vault_id,
+    };
+    let vault = load_vault(deps.as_ref(), vault_id)?;
+    if vault.share_token.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let sender = deps.api.addr_validate(&receive_msg.sender)?;
+
+    match hook_msg {
+        Cw20HookMsg::Redeem { .. } => execute_redeem(deps, vault, sender, receive_msg.amount),
+        Cw20HookMsg::Claim { .. } => execute_claim(deps, vault, sender, receive_msg.amount),
+    }
+}
+
+pub fn execute_redeem(
+    deps: DepsMut,
+    mut vault: Vault,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if vault.status != VaultStatus::Active {
+        return Err(ContractError::VaultNotActive {});
+    }
+    if amount != vault.supply {
+        return Err(ContractError::NotAllShares {
+            supply: vault.supply.to_string(),
+        });
+    }
+    vault.status = VaultStatus::Redeemed;
+    VAULTS.save(deps.storage, vault.id, &vault)?;
+
+    let share_token = vault.share_token.as_ref().unwrap();
+    Ok(Response::new()
+        .add_message(burn_shares_msg(share_token, amount)?)
+        .add_message(transfer_nft_msg(&vault, &sender)?)
+        .add_attribute("action", "redeem")
+        .add_attribute("vault_id", vault.id.to_string())
+        .add_attribute("recipient", sender.to_string()))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    mut vault: Vault,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let VaultStatus::Sold {
+        remaining_proceeds,
+        remaining_shares,
+    } = vault.status
+    else {
+        return Err(ContractError::NotSold {});
+    };
+    if amount > remaining_shares {
+        return Err(ContractError::TooManyShares {
+            remaining_shares: remaining_shares.to_string(),
+        });
+    }
+    // pro-rata of what's left, so the last claim gets all remaining proceeds
+    let payout = remaining_proceeds.checked_multiply_ratio(amount, remaining_shares)?;
+    vault.status = VaultStatus::Sold {
+        remaining_proceeds: remaining_proceeds.checked_sub(payout)?,
+        remaining_shares: remaining_shares.checked_sub(amount)?,
+    };
+    VAULTS.save(deps.storage, vault.id, &vault)?;
+
+    let denom = &vault.buyout.as_ref().unwrap().denom;
+    let share_token = vault.share_token.as_ref().unwrap();
+    let payout_msg = (!payout.is_zero()).then(|| BankMsg::Send {
+        to_address: sender.to_string(),
+        amount: coins(payout.u128(), denom),
+    });
+    Ok(Response::new()
+        .add_message(burn_shares_msg(share_token, amount)?)
+        .add_messages(payout_msg)
+        .add_attribute("action", "claim")
+        .add_attribute("vault_id", vault.id.to_string())
+        .add_attribute("recipient", sender.to_string())
+        .add_attribute("shares", amount.to_string())
+        .add_attribute("payout", payout.to_string()))
+}
+
+pub fn execute_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: u64,
+) -> Result<Response, ContractError> {
+    let mut vault = load_vault(deps.as_ref(), vault_id)?;
+    let Some(buyout) = vault.buyout.clone() else {
+        return Err(ContractError::NoBuyout {});
+    };
+    let now = env.block.time;
+    let (min_bid, end_time, refund_msg) = match &vault.status {
+        VaultStatus::Active => (
+            buyout.reserve_price,
+            now.plus_seconds(buyout.duration),
+            None,
+        ),
+        VaultStatus::Auction {
+            highest_bid,
+            end_time,
+        } => {
+            if now >= *end_time {
+                return Err(ContractError::AuctionEnded {});
+            }
+            // refund outbid bidder
+            let refund_msg = BankMsg::Send {
+                to_address: highest_bid.bidder.to_string(),
+                amount: coins(highest_bid.amount.u128(), &buyout.denom),
+            };
+            (
+                highest_bid.amount + Uint128::one(),
+                *end_time,
+                Some(refund_msg),
+            )
+        }
+        _ => return Err(ContractError::VaultNotActive {}),
+    };
+    let amount = must_pay(&info, &buyout.denom)?;
+    if amount < min_bid {
+        return Err(ContractError::BidTooLow {
+            min_bid: min_bid.to_string(),
+        });
+    }
+    vault.status = VaultStatus::Auction {
+        highest_bid: Bid {
+            bidder: info.sender.clone(),
+            amount,
+        },
+        end_time,
+    };
+    VAULTS.save(deps.storage, vault_id, &vault)?;
+
+    Ok(Response::new()
+        .add_messages(refund_msg)
+        .add_attribute("action", "bid")
+        .add_attribute("vault_id", vault_id.to_string())
+        .add_attribute("bidder", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("end_time", end_time.to_string()))
+}
+
+pub fn execute_settle_buyout(
+    deps: DepsMut,
+    env: Env,
+    vault_id: u64,
+) -> Result<Response, ContractError> {
+    let mut vault = load_vault(deps.as_ref(), vault_id)?;
+    let VaultStatus::Auction {
+        highest_bid,
+        end_time,
+    } = vault.status.clone()
+    else {
+        return Err(ContractError::VaultNotActive {});
+    };
+    if env.block.time < end_time {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+    vault.status = VaultStatus::Sold {
+        remaining_proceeds: highest_bid.amount,
+        remaining_shares: vault.supply,
+    };
+    VAULTS.save(deps.storage, vault_id, &vault)?;
+
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&vault, &highest_bid.bidder)?)
+        .add_attribute("action", "settle_buyout")
+        .add_attribute("vault_id", vault_id.to_string())
+        .add_attribute("winner", highest_bid.bidder.to_string())
+        .add_attribute("amount", highest_bid.amount.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Vault { vault_id } => to_json_binary(&VAULTS.load(deps.storage, vault_id)?),
+        QueryMsg::Vaults { start_after, limit } => {
+            to_json_binary(&query_vaults(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_vaults(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VaultsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let vaults = VAULTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, vault)| vault))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(VaultsResponse { vaults })
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::{CheckedMultiplyRatioError, OverflowError, StdError};
+use cw_utils::{ParseReplyError, PaymentError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    CheckedMultiplyRatio(#[from] CheckedMultiplyRatioError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Vault {vault_id} not found")]
+    VaultNotFound { vault_id: u64 },
+
+    #[error("Share supply must not be zero")]
+    InvalidSupply {},
+
+    #[error("Share token must be of the configured cw20 code, unused and mintable by this contract only")]
+    InvalidShareToken {},
+
+    #[error("Redeeming requires all {supply} shares")]
+    NotAllShares { supply: String },
+
+    #[error("Vault is not active")]
+    VaultNotActive {},
+
+    #[error("Vault has no buyout")]
+    NoBuyout {},
+
+    #[error("Bid too low, minimum is {min_bid}")]
+    BidTooLow { min_bid: String },
+
+    #[error("Buyout auction has ended")]
+    AuctionEnded {},
+
+    #[error("Buyout auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Vault has not been sold")]
+    NotSold {},
+
+    #[error("Only {remaining_shares} shares are left to claim")]
+    TooManyShares { remaining_shares: String },
+}
@@ -0,0 +1,81 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::{Buyout, Config, Vault};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Code id of cw20-base, used for instantiating new share tokens.
+    pub cw20_code_id: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Fractionalizes the received NFT on behalf of its previous owner. Sent by the cw721
+    /// contract on `SendNft`, with `Cw721HookMsg` as msg.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Redeems the NFT or claims buyout proceeds with the received shares. Sent by the share
+    /// token on `Send`, with `Cw20HookMsg` as msg.
+    Receive(Cw20ReceiveMsg),
+    /// Places a bid in the buyout auction with the sent funds. The first bid must meet the
+    /// reserve price and starts the auction, the previous highest bidder is refunded.
+    Bid { vault_id: u64 },
+    /// Settles an ended buyout auction: the NFT goes to the highest bidder, and shares can be
+    /// exchanged for their part of the proceeds. Can be called by anyone.
+    SettleBuyout { vault_id: u64 },
+}
+
+#[cw_serde]
+pub enum ShareToken {
+    /// Instantiates a new cw20-base token, with the whole supply minted to the depositor and
+    /// no minter.
+    New {
+        name: String,
+        symbol: String,
+        decimals: u8,
+    },
+    /// Uses an existing cw20 token without supply, which is only mintable by this contract.
+    Existing { address: String },
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Locks the NFT in a new vault, and mints `supply` shares to the depositor.
+    Fractionalize {
+        share_token: ShareToken,
+        supply: Uint128,
+        buyout: Option<Buyout>,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Burns all shares and transfers the NFT to the sender.
+    Redeem { vault_id: u64 },
+    /// Burns the shares and sends their pro-rata part of the buyout proceeds to the sender.
+    Claim { vault_id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+
+    #[returns(Vault)]
+    Vault { vault_id: u64 },
+
+    /// Lists all vaults, ordered by id.
+    #[returns(VaultsResponse)]
+    Vaults {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VaultsResponse {
+    pub vaults: Vec<Vault>,
+}
@@ -0,0 +1,62 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    /// Code id of cw20-base, used for instantiating new share tokens.
+    pub cw20_code_id: u64,
+}
+
+#[cw_serde]
+pub struct Buyout {
+    /// Native denom bids are paid in.
+    pub denom: String,
+    /// Minimum amount of the first bid, which starts the buyout auction.
+    pub reserve_price: Uint128,
+    /// Duration of the buyout auction in seconds, starting with the first bid.
+    pub duration: u64,
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub enum VaultStatus {
+    /// Shares are tradeable, and all shares together redeem the NFT.
+    Active,
+    /// A buyout auction is running. Redeeming is no longer possible.
+    Auction {
+        highest_bid: Bid,
+        end_time: Timestamp,
+    },
+    /// The NFT has been bought out. Shares are claims on the remaining proceeds.
+    Sold {
+        remaining_proceeds: Uint128,
+        remaining_shares: Uint128,
+    },
+    /// The NFT has been redeemed with all shares.
+    Redeemed,
+}
+
+#[cw_serde]
+pub struct Vault {
+    pub id: u64,
+    pub collection: Addr,
+    pub token_id: String,
+    pub depositor: Addr,
+    /// The cw20 token representing shares of the NFT. Set once a new share token is
+    /// instantiated.
+    pub share_token: Option<Addr>,
+    /// Fixed total supply of shares.
+    pub supply: Uint128,
+    pub buyout: Option<Buyout>,
+    pub status: VaultStatus,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const VAULT_COUNT: Item<u64> = Item::new("vault_count");
+pub const VAULTS: Map<u64, Vault> = Map::new("vaults");
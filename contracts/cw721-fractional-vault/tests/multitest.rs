@@ -0,0 +1,534 @@
+use cosmwasm_std::testing::{mock_dependencies, MockApi};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Uint128};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse, TokenInfoResponse};
+use cw721::msg::OwnerOfResponse;
+use cw721_fractional_vault::contract::execute_claim;
+use cw721_fractional_vault::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ShareToken,
+};
+use cw721_fractional_vault::state::{Buyout, Vault, VaultStatus};
+use cw721_fractional_vault::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+
+const DENOM: &str = "uatom";
+const SUPPLY: u128 = 100;
+
+struct Contracts {
+    cw20_code_id: u64,
+    nft_contract: Addr,
+    vault: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    alice: Addr,
+    bob: Addr,
+    carol: Addr,
+    dave: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        creator: api.addr_make("creator"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+        carol: api.addr_make("carol"),
+        dave: api.addr_make("dave"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        for bidder in [&users.carol, &users.dave] {
+            router
+                .bank
+                .init_balance(storage, bidder, coins(10_000, DENOM))
+                .unwrap();
+        }
+    });
+    (app, users)
+}
+
+/// Setup a cw721-base collection with token "1" owned by alice, and the vault
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_fractional_vault::contract::*;
+
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+    ));
+    let cw20_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            users.creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        users.creator.clone(),
+        nft_contract.clone(),
+        &base_msg::ExecuteMsg::Mint {
+            token_id: "1".to_string(),
+            owner: users.alice.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vault = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg { cw20_code_id },
+            &[],
+            "vault".to_string(),
+            None,
+        )
+        .unwrap();
+
+    Contracts {
+        cw20_code_id,
+        nft_contract,
+        vault,
+    }
+}
+
+fn fractionalize(
+    app: &mut App,
+    contracts: &Contracts,
+    depositor: &Addr,
+    share_token: ShareToken,
+    buyout: Option<Buyout>,
+) -> Option<ContractError> {
+    app.execute_contract(
+        depositor.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.vault.to_string(),
+            token_id: "1".to_string(),
+            msg: to_json_binary(&Cw721HookMsg::Fractionalize {
+                share_token,
+                supply: Uint128::new(SUPPLY),
+                buyout,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn new_share_token() -> ShareToken {
+    ShareToken::New {
+        name: "shares".to_string(),
+        symbol: "SHARES".to_string(),
+        decimals: 6,
+    }
+}
+
+fn query_vault(app: &App, contracts: &Contracts) -> Vault {
+    app.wrap()
+        .query_wasm_smart(contracts.vault.clone(), &QueryMsg::Vault { vault_id: 1 })
+        .unwrap()
+}
+
+fn owner_of(app: &App, contracts: &Contracts) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+fn share_balance(app: &App, share_token: &Addr, address: &Addr) -> u128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            share_token.clone(),
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance.u128()
+}
+
+fn transfer_shares(
+    app: &mut App,
+    share_token: &Addr,
+    sender: &Addr,
+    recipient: &Addr,
+    amount: u128,
+) {
+    app.execute_contract(
+        sender.clone(),
+        share_token.clone(),
+        &Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn send_shares(
+    app: &mut App,
+    contracts: &Contracts,
+    share_token: &Addr,
+    sender: &Addr,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        share_token.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: contracts.vault.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&msg).unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn bid(app: &mut App, contracts: &Contracts, bidder: &Addr, amount: u128) -> Option<ContractError> {
+    app.execute_contract(
+        bidder.clone(),
+        contracts.vault.clone(),
+        &ExecuteMsg::Bid { vault_id: 1 },
+        &coins(amount, DENOM),
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+#[test]
+fn test_fractionalize_and_redeem() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // whole supply is minted to the depositor
+    assert_eq!(
+        fractionalize(&mut app, &contracts, &users.alice, new_share_token(), None),
+        None
+    );
+    assert_eq!(owner_of(&app, &contracts), contracts.vault);
+    let vault = query_vault(&app, &contracts);
+    assert_eq!(vault.depositor, users.alice);
+    let share_token = vault.share_token.unwrap();
+    assert_eq!(share_balance(&app, &share_token, &users.alice), SUPPLY);
+
+    // redeeming needs all shares
+    transfer_shares(&mut app, &share_token, &users.alice, &users.bob, 40);
+    let redeem = Cw20HookMsg::Redeem { vault_id: 1 };
+    assert_eq!(
+        send_shares(
+            &mut app,
+            &contracts,
+            &share_token,
+            &users.alice,
+            60,
+            redeem.clone()
+        ),
+        Some(ContractError::NotAllShares {
+            supply: SUPPLY.to_string()
+        })
+    );
+    // without buyout, there are no bids
+    assert_eq!(
+        bid(&mut app, &contracts, &users.carol, 1_000),
+        Some(ContractError::NoBuyout {})
+    );
+
+    transfer_shares(&mut app, &share_token, &users.alice, &users.bob, 60);
+    assert_eq!(
+        send_shares(
+            &mut app,
+            &contracts,
+            &share_token,
+            &users.bob,
+            SUPPLY,
+            redeem
+        ),
+        None
+    );
+    assert_eq!(owner_of(&app, &contracts), users.bob);
+    assert_eq!(query_vault(&app, &contracts).status, VaultStatus::Redeemed);
+    let token_info: TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(share_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(token_info.total_supply, Uint128::zero());
+}
+
+#[test]
+fn test_buyout() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    let buyout = Buyout {
+        denom: DENOM.to_string(),
+        reserve_price: Uint128::new(1_000),
+        duration: 100,
+    };
+    assert_eq!(
+        fractionalize(
+            &mut app,
+            &contracts,
+            &users.alice,
+            new_share_token(),
+            Some(buyout)
+        ),
+        None
+    );
+    let share_token = query_vault(&app, &contracts).share_token.unwrap();
+    transfer_shares(&mut app, &share_token, &users.alice, &users.bob, 25);
+
+    // first bid must meet the reserve price, later ones must outbid
+    assert_eq!(
+        bid(&mut app, &contracts, &users.carol, 900),
+        Some(ContractError::BidTooLow {
+            min_bid: "1000".to_string()
+        })
+    );
+    assert_eq!(bid(&mut app, &contracts, &users.carol, 1_000), None);
+    assert_eq!(
+        bid(&mut app, &contracts, &users.dave, 1_000),
+        Some(ContractError::BidTooLow {
+            min_bid: "1001".to_string()
+        })
+    );
+    assert_eq!(bid(&mut app, &contracts, &users.dave, 1_200), None);
+    assert_eq!(
+        app.wrap().query_balance(&users.carol, DENOM).unwrap(),
+        coin(10_000, DENOM)
+    );
+
+    // no redeeming once the auction runs
+    assert_eq!(
+        send_shares(
+            &mut app,
+            &contracts,
+            &share_token,
+            &users.alice,
+            75,
+            Cw20HookMsg::Redeem { vault_id: 1 }
+        ),
+        Some(ContractError::VaultNotActive {})
+    );
+
+    let settle = ExecuteMsg::SettleBuyout { vault_id: 1 };
+    let err: ContractError = app
+        .execute_contract(users.bob.clone(), contracts.vault.clone(), &settle, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AuctionNotEnded {});
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert_eq!(
+        bid(&mut app, &contracts, &users.carol, 2_000),
+        Some(ContractError::AuctionEnded {})
+    );
+    app.execute_contract(users.bob.clone(), contracts.vault.clone(), &settle, &[])
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts), users.dave);
+
+    // shares are claims on the proceeds
+    let claim = Cw20HookMsg::Claim { vault_id: 1 };
+    assert_eq!(
+        send_shares(
+            &mut app,
+            &contracts,
+            &share_token,
+            &users.bob,
+            25,
+            claim.clone()
+        ),
+        None
+    );
+    assert_eq!(
+        send_shares(&mut app, &contracts, &share_token, &users.alice, 75, claim),
+        None
+    );
+    assert_eq!(
+        app.wrap().query_balance(&users.bob, DENOM).unwrap(),
+        coin(300, DENOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&users.alice, DENOM).unwrap(),
+        coin(900, DENOM)
+    );
+}
+
+#[test]
+fn test_existing_share_token() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // same cw20 code, but stored with another code id
+    let other_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )));
+    let mut share_tokens = vec![];
+    for (code_id, minter) in [
+        (contracts.cw20_code_id, &users.creator),
+        (contracts.cw20_code_id, &contracts.vault),
+        (other_code_id, &contracts.vault),
+    ] {
+        let share_token = app
+            .instantiate_contract(
+                code_id,
+                users.creator.clone(),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "shares".to_string(),
+                    symbol: "SHARES".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: minter.to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                },
+                &[],
+                "shares".to_string(),
+                None,
+            )
+            .unwrap();
+        share_tokens.push(share_token);
+    }
+
+    // others could mint shares
+    assert_eq!(
+        fractionalize(
+            &mut app,
+            &contracts,
+            &users.alice,
+            ShareToken::Existing {
+                address: share_tokens[0].to_string()
+            },
+            None
+        ),
+        Some(ContractError::InvalidShareToken {})
+    );
+
+    // other code ids could answer queries arbitrarily
+    assert_eq!(
+        fractionalize(
+            &mut app,
+            &contracts,
+            &users.alice,
+            ShareToken::Existing {
+                address: share_tokens[2].to_string()
+            },
+            None
+        ),
+        Some(ContractError::InvalidShareToken {})
+    );
+
+    assert_eq!(
+        fractionalize(
+            &mut app,
+            &contracts,
+            &users.alice,
+            ShareToken::Existing {
+                address: share_tokens[1].to_string()
+            },
+            None
+        ),
+        None
+    );
+    assert_eq!(
+        query_vault(&app, &contracts).share_token,
+        Some(share_tokens[1].clone())
+    );
+    assert_eq!(share_balance(&app, &share_tokens[1], &users.alice), SUPPLY);
+
+    // only the vault's share token is accepted
+    app.execute_contract(
+        users.creator.clone(),
+        share_tokens[0].clone(),
+        &Cw20ExecuteMsg::Mint {
+            recipient: users.alice.to_string(),
+            amount: Uint128::new(SUPPLY),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        send_shares(
+            &mut app,
+            &contracts,
+            &share_tokens[0],
+            &users.alice,
+            SUPPLY,
+            Cw20HookMsg::Redeem { vault_id: 1 }
+        ),
+        Some(ContractError::Unauthorized {})
+    );
+}
+
+#[test]
+fn test_claim_more_than_remaining_shares() {
+    let mut deps = mock_dependencies();
+    let api = MockApi::default();
+    let vault = Vault {
+        id: 1,
+        collection: api.addr_make("nft"),
+        token_id: "1".to_string(),
+        depositor: api.addr_make("alice"),
+        share_token: Some(api.addr_make("shares")),
+        supply: Uint128::new(SUPPLY),
+        buyout: Some(Buyout {
+            denom: DENOM.to_string(),
+            reserve_price: Uint128::new(1_000),
+            duration: 100,
+        }),
+        status: VaultStatus::Sold {
+            remaining_proceeds: Uint128::new(300),
+            remaining_shares: Uint128::new(25),
+        },
+    };
+    let err =
+        execute_claim(deps.as_mut(), vault, api.addr_make("bob"), Uint128::new(26)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TooManyShares {
+            remaining_shares: "25".to_string()
+        }
+    );
+}
@@ -0,0 +1,320 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+
+use crate::error::ContractError;
+use crate::helpers::send_tokens;
+use crate::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, ListingsResponse, QueryMsg,
+};
+use crate::state::{listings, Listing};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-marketplace";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, info, receive_msg),
+        ExecuteMsg::Receive(receive_msg) => execute_receive_cw20(deps, info, receive_msg),
+        ExecuteMsg::Buy {
+            collection,
+            token_id,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let listing = load_listing(deps.as_ref(), &collection, &token_id)?;
+            let Denom::Native(denom) = &listing.denom else {
+                return Err(wrong_payment(&listing));
+            };
+            if must_pay(&info, denom)? != listing.price {
+                return Err(wrong_payment(&listing));
+            }
+            execute_buy(deps, listing, info.sender)
+        }
+        ExecuteMsg::Cancel {
+            collection,
+            token_id,
+        } => execute_cancel(deps, info, collection, token_id),
+        ExecuteMsg::UpdatePrice {
+            collection,
+            token_id,
+            price,
+        } => execute_update_price(deps, info, collection, token_id, price),
+    }
+}
+
+fn load_listing(deps: Deps, collection: &Addr, token_id: &str) -> Result<Listing, ContractError> {
+    listings()
+        .may_load(deps.storage, (collection, token_id))?
+        .ok_or_else(|| ContractError::ListingNotFound {
+            token_id: token_id.to_string(),
+        })
+}
+
+fn wrong_payment(listing: &Listing) -> ContractError {
+    let denom = match &listing.denom {
+        Denom::Native(denom) => denom.clone(),
+        Denom::Cw20(address) => address.to_string(),
+    };
+    ContractError::WrongPayment {
+        price: listing.price.to_string(),
+        denom,
+    }
+}
+
+fn transfer_nft_msg(listing: &Listing, recipient: &Addr) -> StdResult<cosmwasm_std::CosmosMsg> {
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: listing.token_id.clone(),
+    };
+    DefaultCw721Helper::new(listing.collection.clone()).call(transfer_msg)
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw721HookMsg::List { denom, price } = from_json(&receive_msg.msg)?;
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    let denom = match denom {
+        Denom::Native(denom) => Denom::Native(denom),
+        Denom::Cw20(address) => Denom::Cw20(deps.api.addr_validate(address.as_str())?),
+    };
+    // the NFT contract is the sender, so collection can't be faked
+    let listing = Listing {
+        collection: info.sender,
+        token_id: receive_msg.token_id,
+        seller: deps.api.addr_validate(&receive_msg.sender)?,
+        denom,
+        price,
+    };
+    listings().save(
+        deps.storage,
+        (&listing.collection, &listing.token_id),
+        &listing,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list")
+        .add_attribute("collection", listing.collection.to_string())
+        .add_attribute("token_id", listing.token_id)
+        .add_attribute("seller", listing.seller.to_string())
+        .add_attribute("price", listing.price.to_string()))
+}
+
+pub fn execute_receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw20HookMsg::Buy {
+        collection,
+        token_id,
+    } = from_json(&receive_msg.msg)?;
+    let collection = deps.api.addr_validate(&collection)?;
+    let listing = load_listing(deps.as_ref(), &collection, &token_id)?;
+    // the cw20 contract is the sender, so payment can't be faked
+    if listing.denom != Denom::Cw20(info.sender) || receive_msg.amount != listing.price {
+        return Err(wrong_payment(&listing));
+    }
+    let buyer = deps.api.addr_validate(&receive_msg.sender)?;
+    execute_buy(deps, listing, buyer)
+}
+
+/// Settles a paid sale: royalty is paid to the royalty recipient, the rest to the seller,
+/// and the NFT is transferred to the buyer.
+pub fn execute_buy(
+    deps: DepsMut,
+    listing: Listing,
+    buyer: Addr,
+) -> Result<Response, ContractError> {
+    listings().remove(deps.storage, (&listing.collection, &listing.token_id))?;
+
+    let royalty = DefaultCw721Helper::new(listing.collection.clone()).royalty(
+        &deps.querier,
+        &listing.token_id,
+        listing.price,
+    );
+    let mut response = Response::new().add_message(transfer_nft_msg(&listing, &buyer)?);
+    let mut seller_amount = listing.price;
+    if let Some(royalty) = &royalty {
+        seller_amount -= royalty.amount;
+        response = response.add_message(send_tokens(
+            &royalty.recipient,
+            &listing.denom,
+            royalty.amount,
+        )?);
+    }
+    if !seller_amount.is_zero() {
+        response =
+            response.add_message(send_tokens(&listing.seller, &listing.denom, seller_amount)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "buy")
+        .add_attribute("collection", listing.collection.to_string())
+        .add_attribute("token_id", listing.token_id)
+        .add_attribute("seller", listing.seller.to_string())
+        .add_attribute("buyer", buyer.to_string())
+        .add_attribute("price", listing.price.to_string())
+        .add_attribute(
+            "royalty",
+            royalty.map(|r| r.amount).unwrap_or_default().to_string(),
+        ))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let listing = load_listing(deps.as_ref(), &collection, &token_id)?;
+    if listing.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    listings().remove(deps.storage, (&collection, &token_id))?;
+
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&listing, &listing.seller)?)
+        .add_attribute("action", "cancel")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_update_price(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    let collection = deps.api.addr_validate(&collection)?;
+    let mut listing = load_listing(deps.as_ref(), &collection, &token_id)?;
+    if listing.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    listing.price = price;
+    listings().save(deps.storage, (&collection, &token_id), &listing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_price")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", price.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Listing {
+            collection,
+            token_id,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            to_json_binary(&listings().load(deps.storage, (&collection, &token_id))?)
+        }
+        QueryMsg::ListingsByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_json_binary(&query_listings_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListingsBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_json_binary(&query_listings_by_seller(deps, seller, start_after, limit)?),
+    }
+}
+
+pub fn query_listings_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListingsResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let listings = listings()
+        .prefix(&collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, listing)| listing))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListingsResponse { listings })
+}
+
+pub fn query_listings_by_seller(
+    deps: Deps,
+    seller: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<ListingsResponse> {
+    let seller = deps.api.addr_validate(&seller)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|(collection, token_id)| {
+            deps.api
+                .addr_validate(&collection)
+                .map(|collection| Bound::exclusive((collection, token_id)))
+        })
+        .transpose()?;
+    let listings = listings()
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, listing)| listing))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListingsResponse { listings })
+}
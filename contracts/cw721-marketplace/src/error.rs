@@ -0,0 +1,24 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Price must not be zero")]
+    InvalidPrice {},
+
+    #[error("Listing of token {token_id} not found")]
+    ListingNotFound { token_id: String },
+
+    #[error("Wrong payment, expected {price} {denom}")]
+    WrongPayment { price: String, denom: String },
+}
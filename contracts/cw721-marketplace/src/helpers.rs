@@ -0,0 +1,23 @@
+use cosmwasm_std::{coin, to_json_binary, Addr, BankMsg, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Denom};
+
+/// Returns the message for sending native or cw20 tokens.
+pub fn send_tokens(to: &Addr, denom: &Denom, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![coin(amount.u128(), denom)],
+        }
+        .into(),
+        Denom::Cw20(address) => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+    Ok(msg)
+}
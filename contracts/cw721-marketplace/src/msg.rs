@@ -0,0 +1,81 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::Listing;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Lists the received NFT on behalf of its previous owner. Sent by the cw721 contract on `SendNft`,
+    /// with `Cw721HookMsg` as msg.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Buys a listed NFT with cw20 tokens. Sent by the cw20 contract on `Send`, with `Cw20HookMsg` as msg.
+    Receive(Cw20ReceiveMsg),
+    /// Buys a listed NFT with native tokens. The exact price must be sent.
+    Buy {
+        collection: String,
+        token_id: String,
+    },
+    /// Cancels a listing and sends the NFT back. Only seller can call this.
+    Cancel {
+        collection: String,
+        token_id: String,
+    },
+    /// Updates the price of a listing. Only seller can call this.
+    UpdatePrice {
+        collection: String,
+        token_id: String,
+        price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Lists the NFT for the given price, paid in native denom or cw20 tokens.
+    List { denom: Denom, price: Uint128 },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Buys a listed NFT, paid with the sent cw20 tokens.
+    Buy {
+        collection: String,
+        token_id: String,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Listing)]
+    Listing {
+        collection: String,
+        token_id: String,
+    },
+
+    /// Lists all listings of a collection, ordered by token id.
+    #[returns(ListingsResponse)]
+    ListingsByCollection {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Lists all listings of a seller, ordered by collection and token id.
+    /// `start_after` is a tuple of collection and token id.
+    #[returns(ListingsResponse)]
+    ListingsBySeller {
+        seller: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ListingsResponse {
+    pub listings: Vec<Listing>,
+}
@@ -0,0 +1,37 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Denom;
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+
+#[cw_serde]
+pub struct Listing {
+    pub collection: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    /// Native denom or cw20 contract the price is paid in.
+    pub denom: Denom,
+    pub price: Uint128,
+}
+
+pub struct ListingIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, Listing, (Addr, String)>,
+}
+
+impl<'a> IndexList<Listing> for ListingIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+        let v: Vec<&dyn Index<Listing>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stored as ((collection, token id), listing), indexed by seller.
+pub fn listings<'a>() -> IndexedMap<'a, (&'a Addr, &'a str), Listing, ListingIndexes<'a>> {
+    let indexes = ListingIndexes {
+        seller: MultiIndex::new(
+            |_pk, listing: &Listing| listing.seller.clone(),
+            "listings",
+            "listings__seller",
+        ),
+    };
+    IndexedMap::new("listings", indexes)
+}
@@ -0,0 +1,471 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Uint128};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Denom};
+use cw721::msg::{CollectionExtensionMsg, OwnerOfResponse, RoyaltyInfoResponse};
+use cw721_marketplace::msg::{
+    Cw20HookMsg, Cw721HookMsg, ExecuteMsg, InstantiateMsg, ListingsResponse, QueryMsg,
+};
+use cw721_marketplace::state::Listing;
+use cw721_marketplace::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+
+const DENOM: &str = "uatom";
+const PRICE: u128 = 1_000;
+
+struct Contracts {
+    nft_contract: Addr,
+    marketplace_contract: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    seller: Addr,
+    buyer: Addr,
+    royalty_recipient: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        creator: api.addr_make("creator"),
+        seller: api.addr_make("seller"),
+        buyer: api.addr_make("buyer"),
+        royalty_recipient: api.addr_make("royalty_recipient"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &users.buyer, coins(10 * PRICE, DENOM))
+            .unwrap();
+    });
+    (app, users)
+}
+
+/// Setup a cw721-base collection with 5% royalty and the marketplace, and mint tokens to seller
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_marketplace::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            users.creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: Some(CollectionExtensionMsg {
+                    description: Some("description".to_string()),
+                    image: Some("https://example.com/image.png".to_string()),
+                    external_link: None,
+                    explicit_content: None,
+                    start_trading_time: None,
+                    royalty_info: Some(RoyaltyInfoResponse {
+                        payment_address: users.royalty_recipient.to_string(),
+                        share: Decimal::percent(5),
+                    }),
+                }),
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+
+    let marketplace_contract = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {},
+            &[],
+            "marketplace".to_string(),
+            None,
+        )
+        .unwrap();
+
+    for token_id in ["1", "2", "3"] {
+        app.execute_contract(
+            users.creator.clone(),
+            nft_contract.clone(),
+            &base_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: users.seller.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    Contracts {
+        nft_contract,
+        marketplace_contract,
+    }
+}
+
+fn list(app: &mut App, contracts: &Contracts, seller: &Addr, token_id: &str, denom: Denom) {
+    app.execute_contract(
+        seller.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.marketplace_contract.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Cw721HookMsg::List {
+                denom,
+                price: Uint128::new(PRICE),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn owner_of(app: &App, contracts: &Contracts, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_buy_with_native_tokens() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    list(
+        &mut app,
+        &contracts,
+        &users.seller,
+        "1",
+        Denom::Native(DENOM.to_string()),
+    );
+    assert_eq!(
+        owner_of(&app, &contracts, "1"),
+        contracts.marketplace_contract
+    );
+    let listing: Listing = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.marketplace_contract.clone(),
+            &QueryMsg::Listing {
+                collection: contracts.nft_contract.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(listing.seller, users.seller);
+    assert_eq!(listing.price, Uint128::new(PRICE));
+
+    // exact price must be paid
+    let buy_msg = ExecuteMsg::Buy {
+        collection: contracts.nft_contract.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            users.buyer.clone(),
+            contracts.marketplace_contract.clone(),
+            &buy_msg,
+            &coins(PRICE - 1, DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::WrongPayment {
+            price: PRICE.to_string(),
+            denom: DENOM.to_string(),
+        }
+    );
+
+    app.execute_contract(
+        users.buyer.clone(),
+        contracts.marketplace_contract.clone(),
+        &buy_msg,
+        &coins(PRICE, DENOM),
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "1"), users.buyer);
+    // 5% royalty, rest to seller
+    assert_eq!(
+        app.wrap()
+            .query_balance(&users.royalty_recipient, DENOM)
+            .unwrap(),
+        coin(PRICE * 5 / 100, DENOM)
+    );
+    assert_eq!(
+        app.wrap().query_balance(&users.seller, DENOM).unwrap(),
+        coin(PRICE * 95 / 100, DENOM)
+    );
+
+    // listing is gone
+    let err: ContractError = app
+        .execute_contract(
+            users.buyer.clone(),
+            contracts.marketplace_contract.clone(),
+            &buy_msg,
+            &coins(PRICE, DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ListingNotFound {
+            token_id: "1".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_buy_with_cw20_tokens() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    let cw20_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )));
+    let cw20_contract = app
+        .instantiate_contract(
+            cw20_code_id,
+            users.creator.clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: "token".to_string(),
+                symbol: "TOKEN".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: users.buyer.to_string(),
+                    amount: Uint128::new(10 * PRICE),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "token".to_string(),
+            None,
+        )
+        .unwrap();
+    list(
+        &mut app,
+        &contracts,
+        &users.seller,
+        "1",
+        Denom::Cw20(cw20_contract.clone()),
+    );
+
+    // native tokens are not accepted
+    let err: ContractError = app
+        .execute_contract(
+            users.buyer.clone(),
+            contracts.marketplace_contract.clone(),
+            &ExecuteMsg::Buy {
+                collection: contracts.nft_contract.to_string(),
+                token_id: "1".to_string(),
+            },
+            &coins(PRICE, DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::WrongPayment {
+            price: PRICE.to_string(),
+            denom: cw20_contract.to_string(),
+        }
+    );
+
+    app.execute_contract(
+        users.buyer.clone(),
+        cw20_contract.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: contracts.marketplace_contract.to_string(),
+            amount: Uint128::new(PRICE),
+            msg: to_json_binary(&Cw20HookMsg::Buy {
+                collection: contracts.nft_contract.to_string(),
+                token_id: "1".to_string(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "1"), users.buyer);
+
+    let balance = |address: &Addr| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                cw20_contract.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    assert_eq!(
+        balance(&users.royalty_recipient),
+        Uint128::new(PRICE * 5 / 100)
+    );
+    assert_eq!(balance(&users.seller), Uint128::new(PRICE * 95 / 100));
+    assert_eq!(balance(&users.buyer), Uint128::new(9 * PRICE));
+}
+
+#[test]
+fn test_cancel_and_update_price() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    list(
+        &mut app,
+        &contracts,
+        &users.seller,
+        "1",
+        Denom::Native(DENOM.to_string()),
+    );
+
+    // only seller can update price or cancel
+    let update_msg = ExecuteMsg::UpdatePrice {
+        collection: contracts.nft_contract.to_string(),
+        token_id: "1".to_string(),
+        price: Uint128::new(2 * PRICE),
+    };
+    let cancel_msg = ExecuteMsg::Cancel {
+        collection: contracts.nft_contract.to_string(),
+        token_id: "1".to_string(),
+    };
+    for msg in [&update_msg, &cancel_msg] {
+        let err: ContractError = app
+            .execute_contract(
+                users.buyer.clone(),
+                contracts.marketplace_contract.clone(),
+                msg,
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    let err: ContractError = app
+        .execute_contract(
+            users.seller.clone(),
+            contracts.marketplace_contract.clone(),
+            &ExecuteMsg::UpdatePrice {
+                collection: contracts.nft_contract.to_string(),
+                token_id: "1".to_string(),
+                price: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidPrice {});
+
+    app.execute_contract(
+        users.seller.clone(),
+        contracts.marketplace_contract.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+    let listing: Listing = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.marketplace_contract.clone(),
+            &QueryMsg::Listing {
+                collection: contracts.nft_contract.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(listing.price, Uint128::new(2 * PRICE));
+
+    app.execute_contract(
+        users.seller.clone(),
+        contracts.marketplace_contract.clone(),
+        &cancel_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "1"), users.seller);
+}
+
+#[test]
+fn test_query_listings() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    for token_id in ["1", "2", "3"] {
+        list(
+            &mut app,
+            &contracts,
+            &users.seller,
+            token_id,
+            Denom::Native(DENOM.to_string()),
+        );
+    }
+
+    let res: ListingsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.marketplace_contract.clone(),
+            &QueryMsg::ListingsByCollection {
+                collection: contracts.nft_contract.to_string(),
+                start_after: Some("1".to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.listings.len(), 1);
+    assert_eq!(res.listings[0].token_id, "2");
+
+    let res: ListingsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.marketplace_contract.clone(),
+            &QueryMsg::ListingsBySeller {
+                seller: users.seller.to_string(),
+                start_after: Some((contracts.nft_contract.to_string(), "1".to_string())),
+                limit: None,
+            },
+        )
+        .unwrap();
+    let token_ids: Vec<_> = res.listings.into_iter().map(|l| l.token_id).collect();
+    assert_eq!(token_ids, vec!["2", "3"]);
+
+    let res: ListingsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.marketplace_contract.clone(),
+            &QueryMsg::ListingsBySeller {
+                seller: users.buyer.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(res.listings.is_empty());
+}
@@ -24,6 +24,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     ics721_bridge: None,
 /// };
 /// // ...
 /// // mint:
@@ -129,6 +130,7 @@ mod tests {
                 minter: None,
                 creator: None,
                 withdraw_address: None,
+                ics721_bridge: None,
             },
         )
         .unwrap();
@@ -150,6 +152,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
@@ -50,6 +50,7 @@ pub mod entry {
             minter: msg.minter,
             creator: msg.creator,
             withdraw_address: msg.withdraw_address,
+            ics721_bridge: None,
         };
 
         Cw721NonTransferableContract::default().instantiate_with_version(
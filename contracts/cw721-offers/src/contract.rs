@@ -0,0 +1,233 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, BankMsg, Binary, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::{Cw721ExecuteMsg, NftAttributes};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::state::Trait;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{one_coin, Expiration};
+
+use crate::error::ContractError;
+use crate::msg::{Cw721HookMsg, ExecuteMsg, InstantiateMsg, OffersResponse, QueryMsg};
+use crate::state::{offers, Offer, OFFER_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-offers";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    OFFER_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateOffer {
+            collection,
+            traits,
+            expires,
+        } => execute_create_offer(deps, env, info, collection, traits, expires),
+        ExecuteMsg::CancelOffer { offer_id } => execute_cancel_offer(deps, env, info, offer_id),
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, env, info, receive_msg),
+    }
+}
+
+fn load_offer(deps: Deps, offer_id: u64) -> Result<Offer, ContractError> {
+    offers()
+        .may_load(deps.storage, offer_id)?
+        .ok_or(ContractError::OfferNotFound { offer_id })
+}
+
+pub fn execute_create_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    traits: Option<Vec<Trait>>,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let price = one_coin(&info)?;
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+    let id = OFFER_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    let offer = Offer {
+        id,
+        collection: deps.api.addr_validate(&collection)?,
+        bidder: info.sender,
+        price,
+        traits: traits.unwrap_or_default(),
+        expires,
+    };
+    offers().save(deps.storage, id, &offer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_offer")
+        .add_attribute("offer_id", id.to_string())
+        .add_attribute("collection", offer.collection.to_string())
+        .add_attribute("bidder", offer.bidder.to_string())
+        .add_attribute("price", offer.price.to_string()))
+}
+
+pub fn execute_cancel_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let offer = load_offer(deps.as_ref(), offer_id)?;
+    if offer.bidder != info.sender && !offer.expires.is_expired(&env.block) {
+        return Err(ContractError::Unauthorized {});
+    }
+    offers().remove(deps.storage, offer_id)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: offer.bidder.to_string(),
+            amount: vec![offer.price],
+        })
+        .add_attribute("action", "cancel_offer")
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw721HookMsg::AcceptOffer { offer_id } = from_json(&receive_msg.msg)?;
+    let offer = load_offer(deps.as_ref(), offer_id)?;
+    // the NFT contract is the sender, so collection can't be faked
+    let collection = DefaultCw721Helper::new(info.sender);
+    if offer.collection != collection.addr() {
+        return Err(ContractError::WrongCollection {});
+    }
+    if offer.expires.is_expired(&env.block) {
+        return Err(ContractError::OfferExpired {});
+    }
+    let token_id = receive_msg.token_id;
+    if !offer.traits.is_empty() {
+        // attributes only, so collections with any extension type (e.g. cw2981) are supported
+        let attributes = collection
+            .nft_info::<_, Option<NftAttributes>>(&deps.querier, &token_id)?
+            .extension
+            .and_then(|extension| extension.attributes)
+            .unwrap_or_default();
+        if !offer.matches(&attributes) {
+            return Err(ContractError::TraitMismatch { token_id });
+        }
+    }
+    offers().remove(deps.storage, offer_id)?;
+
+    let seller = deps.api.addr_validate(&receive_msg.sender)?;
+    let mut seller_amount = offer.price.amount;
+    let royalty = collection.royalty(&deps.querier, &token_id, offer.price.amount);
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: offer.bidder.to_string(),
+        token_id: token_id.clone(),
+    };
+    let mut response = Response::new().add_message(collection.call(transfer_msg)?);
+    if let Some(royalty) = &royalty {
+        seller_amount -= royalty.amount;
+        response = response.add_message(BankMsg::Send {
+            to_address: royalty.recipient.to_string(),
+            amount: coins(royalty.amount.u128(), &offer.price.denom),
+        });
+    }
+    if !seller_amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: seller.to_string(),
+            amount: coins(seller_amount.u128(), &offer.price.denom),
+        });
+    }
+
+    Ok(response
+        .add_attribute("action", "accept_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("seller", seller.to_string())
+        .add_attribute("bidder", offer.bidder.to_string())
+        .add_attribute(
+            "royalty",
+            royalty.map(|r| r.amount).unwrap_or_default().to_string(),
+        ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Offer { offer_id } => to_json_binary(&offers().load(deps.storage, offer_id)?),
+        QueryMsg::OffersByCollection {
+            collection,
+            start_after,
+            limit,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let offers = offers()
+                .idx
+                .collection
+                .prefix(collection)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, offer)| offer))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&OffersResponse { offers })
+        }
+        QueryMsg::OffersByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => {
+            let bidder = deps.api.addr_validate(&bidder)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let offers = offers()
+                .idx
+                .bidder
+                .prefix(bidder)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, offer)| offer))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&OffersResponse { offers })
+        }
+    }
+}
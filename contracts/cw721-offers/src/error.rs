@@ -0,0 +1,30 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Offer {offer_id} not found")]
+    OfferNotFound { offer_id: u64 },
+
+    #[error("Offer expired")]
+    OfferExpired {},
+
+    #[error("Offer must not be expired on creation")]
+    InvalidExpiration {},
+
+    #[error("Offer is for another collection")]
+    WrongCollection {},
+
+    #[error("Token {token_id} does not match the traits of the offer")]
+    TraitMismatch { token_id: String },
+}
@@ -0,0 +1,61 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::state::Trait;
+use cw_utils::Expiration;
+
+use crate::state::Offer;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates an offer for any token of the collection, escrowing the sent funds. With traits
+    /// given, only tokens having all of these traits in their `NftExtension.attributes` match.
+    CreateOffer {
+        collection: String,
+        traits: Option<Vec<Trait>>,
+        expires: Expiration,
+    },
+    /// Removes an offer and refunds the bidder. Bidder can cancel at any time, anyone else only
+    /// once the offer is expired.
+    CancelOffer { offer_id: u64 },
+    /// Accepts an offer with the received NFT on behalf of its previous owner. Sent by the cw721
+    /// contract on `SendNft`, with `Cw721HookMsg` as msg.
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Sells the NFT to the bidder of the offer. Escrowed funds are split between token owner and
+    /// royalty recipient.
+    AcceptOffer { offer_id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Offer)]
+    Offer { offer_id: u64 },
+
+    /// Lists all offers for a collection, ordered by id.
+    #[returns(OffersResponse)]
+    OffersByCollection {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Lists all offers of a bidder, ordered by id.
+    #[returns(OffersResponse)]
+    OffersByBidder {
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct OffersResponse {
+    pub offers: Vec<Offer>,
+}
@@ -0,0 +1,60 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin};
+use cw721::state::Trait;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct Offer {
+    pub id: u64,
+    pub collection: Addr,
+    pub bidder: Addr,
+    /// Funds escrowed by the bidder.
+    pub price: Coin,
+    /// Traits the token must have. Empty for a collection-wide offer.
+    pub traits: Vec<Trait>,
+    pub expires: Expiration,
+}
+
+impl Offer {
+    /// Returns true if all traits of the offer are found in the given token attributes.
+    /// Traits match by `trait_type` and `value`, `display_type` is ignored.
+    pub fn matches(&self, attributes: &[Trait]) -> bool {
+        self.traits.iter().all(|wanted| {
+            attributes
+                .iter()
+                .any(|attr| attr.trait_type == wanted.trait_type && attr.value == wanted.value)
+        })
+    }
+}
+
+pub struct OfferIndexes<'a> {
+    pub collection: MultiIndex<'a, Addr, Offer, u64>,
+    pub bidder: MultiIndex<'a, Addr, Offer, u64>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.collection, &self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stored as (offer id, offer), indexed by collection and bidder.
+pub fn offers<'a>() -> IndexedMap<'a, u64, Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        collection: MultiIndex::new(
+            |_pk, offer: &Offer| offer.collection.clone(),
+            "offers",
+            "offers__collection",
+        ),
+        bidder: MultiIndex::new(
+            |_pk, offer: &Offer| offer.bidder.clone(),
+            "offers",
+            "offers__bidder",
+        ),
+    };
+    IndexedMap::new("offers", indexes)
+}
+
+pub const OFFER_COUNT: Item<u64> = Item::new("offer_count");
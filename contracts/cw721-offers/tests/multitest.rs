@@ -0,0 +1,297 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr};
+use cw721::msg::{NftExtensionMsg, OwnerOfResponse};
+use cw721::state::Trait;
+use cw721_offers::msg::{Cw721HookMsg, ExecuteMsg, InstantiateMsg, OffersResponse, QueryMsg};
+use cw721_offers::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cw_utils::Expiration;
+
+const DENOM: &str = "uatom";
+const PRICE: u128 = 1_000;
+
+struct Contracts {
+    nft_contract: Addr,
+    offers_contract: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    owner: Addr,
+    bidder: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        creator: api.addr_make("creator"),
+        owner: api.addr_make("owner"),
+        bidder: api.addr_make("bidder"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &users.bidder, coins(10 * PRICE, DENOM))
+            .unwrap();
+    });
+    (app, users)
+}
+
+fn background(value: &str) -> Trait {
+    Trait {
+        display_type: None,
+        trait_type: "Background".to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Setup the cw721-base and offers contracts, and mint token "gold" and "blue" with matching
+/// background trait to owner
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_offers::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            users.creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+
+    let offers_contract = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {},
+            &[],
+            "offers".to_string(),
+            None,
+        )
+        .unwrap();
+
+    for (token_id, value) in [("gold", "Gold"), ("blue", "Blue")] {
+        app.execute_contract(
+            users.creator.clone(),
+            nft_contract.clone(),
+            &base_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: users.owner.to_string(),
+                token_uri: None,
+                extension: Some(NftExtensionMsg {
+                    attributes: Some(vec![background(value)]),
+                    ..NftExtensionMsg::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    Contracts {
+        nft_contract,
+        offers_contract,
+    }
+}
+
+fn create_offer(
+    app: &mut App,
+    contracts: &Contracts,
+    users: &Users,
+    traits: Option<Vec<Trait>>,
+    expires: Expiration,
+) {
+    app.execute_contract(
+        users.bidder.clone(),
+        contracts.offers_contract.clone(),
+        &ExecuteMsg::CreateOffer {
+            collection: contracts.nft_contract.to_string(),
+            traits,
+            expires,
+        },
+        &coins(PRICE, DENOM),
+    )
+    .unwrap();
+}
+
+fn accept_offer(
+    app: &mut App,
+    contracts: &Contracts,
+    users: &Users,
+    token_id: &str,
+    offer_id: u64,
+) -> Option<ContractError> {
+    app.execute_contract(
+        users.owner.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.offers_contract.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Cw721HookMsg::AcceptOffer { offer_id }).unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, contracts: &Contracts, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_collection_offer() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    create_offer(&mut app, &contracts, &users, None, Expiration::Never {});
+    assert_eq!(
+        app.wrap()
+            .query_balance(&contracts.offers_contract, DENOM)
+            .unwrap(),
+        coin(PRICE, DENOM)
+    );
+
+    // any token of the collection is accepted
+    assert_eq!(accept_offer(&mut app, &contracts, &users, "blue", 1), None);
+    assert_eq!(owner_of(&app, &contracts, "blue"), users.bidder);
+    assert_eq!(
+        app.wrap().query_balance(&users.owner, DENOM).unwrap(),
+        coin(PRICE, DENOM)
+    );
+
+    // offer is consumed
+    assert_eq!(
+        accept_offer(&mut app, &contracts, &users, "gold", 1),
+        Some(ContractError::OfferNotFound { offer_id: 1 })
+    );
+}
+
+#[test]
+fn test_trait_offer() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    create_offer(
+        &mut app,
+        &contracts,
+        &users,
+        Some(vec![background("Gold")]),
+        Expiration::Never {},
+    );
+
+    assert_eq!(
+        accept_offer(&mut app, &contracts, &users, "blue", 1),
+        Some(ContractError::TraitMismatch {
+            token_id: "blue".to_string()
+        })
+    );
+    assert_eq!(owner_of(&app, &contracts, "blue"), users.owner);
+
+    assert_eq!(accept_offer(&mut app, &contracts, &users, "gold", 1), None);
+    assert_eq!(owner_of(&app, &contracts, "gold"), users.bidder);
+}
+
+#[test]
+fn test_expiry_and_cancel() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let expires = Expiration::AtHeight(app.block_info().height + 10);
+    create_offer(&mut app, &contracts, &users, None, expires);
+    create_offer(&mut app, &contracts, &users, None, expires);
+
+    let res: OffersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.offers_contract.clone(),
+            &QueryMsg::OffersByCollection {
+                collection: contracts.nft_contract.to_string(),
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.offers.len(), 1);
+    assert_eq!(res.offers[0].id, 2);
+
+    // only bidder can cancel unexpired offers
+    let err: ContractError = app
+        .execute_contract(
+            users.owner.clone(),
+            contracts.offers_contract.clone(),
+            &ExecuteMsg::CancelOffer { offer_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        users.bidder.clone(),
+        contracts.offers_contract.clone(),
+        &ExecuteMsg::CancelOffer { offer_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&users.bidder, DENOM).unwrap(),
+        coin(9 * PRICE, DENOM)
+    );
+
+    // expired offers can't be accepted, but anyone can refund them
+    app.update_block(|block| block.height += 10);
+    assert_eq!(
+        accept_offer(&mut app, &contracts, &users, "gold", 2),
+        Some(ContractError::OfferExpired {})
+    );
+    app.execute_contract(
+        users.owner.clone(),
+        contracts.offers_contract.clone(),
+        &ExecuteMsg::CancelOffer { offer_id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&users.bidder, DENOM).unwrap(),
+        coin(10 * PRICE, DENOM)
+    );
+
+    let res: OffersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.offers_contract.clone(),
+            &QueryMsg::OffersByBidder {
+                bidder: users.bidder.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(res.offers.is_empty());
+}
@@ -45,6 +45,28 @@ pub fn execute(
                 InnerMsg::Fail => Err(ContractError::Failed {}),
             }
         }
+        ExecuteMsg::ReceiveNftBatch(receive_msg) => {
+            let inner: InnerMsg = from_json(&receive_msg.msg)?;
+            match inner {
+                InnerMsg::Succeed => Ok(Response::new()
+                    .add_attributes([
+                        ("action", "receive_nft_batch"),
+                        ("token_ids", receive_msg.token_ids.join(",").as_str()),
+                        ("sender", receive_msg.sender.as_str()),
+                        ("msg", receive_msg.msg.to_base64().as_str()),
+                    ])
+                    .set_data(
+                        [
+                            receive_msg.token_ids.concat(),
+                            receive_msg.sender,
+                            receive_msg.msg.to_base64(),
+                        ]
+                        .concat()
+                        .as_bytes(),
+                    )),
+                InnerMsg::Fail => Err(ContractError::Failed {}),
+            }
+        }
     }
 }
 
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cw721_base::receiver::Cw721ReceiveMsg;
+use cw721_base::receiver::{Cw721BatchReceiveMsg, Cw721ReceiveMsg};
 
 #[cw_serde]
 pub struct InstantiateMsg {}
@@ -7,6 +7,7 @@ pub struct InstantiateMsg {}
 #[cw_serde]
 pub enum ExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
+    ReceiveNftBatch(Cw721BatchReceiveMsg),
 }
 
 #[cw_serde]
@@ -1,4 +1,5 @@
 use cosmwasm_std::{to_json_binary, Addr, Attribute, Binary};
+use cw721::msg::OwnerOfResponse;
 use cw_multi_test::{App, ContractWrapper, Executor};
 
 #[test]
@@ -98,6 +99,246 @@ fn test_cw721_base_receive_fail() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_cw721_base_safe_transfer() {
+    use cw721_base::msg::*;
+    use cw721_receiver_tester::msg::InnerMsg;
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+
+    let Contracts {
+        nft_contract,
+        receiver_contract,
+    } = setup_contracts(&mut app, admin.clone());
+
+    // contract rejecting the token fails the transfer
+    let result = app.execute_contract(
+        admin.clone(),
+        nft_contract.clone(),
+        &ExecuteMsg::SafeTransferNft {
+            recipient: receiver_contract.to_string(),
+            token_id: "test".to_string(),
+            msg: Some(to_json_binary(&InnerMsg::Fail).unwrap()),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // contract not handling the message fails the transfer
+    let result = app.execute_contract(
+        admin.clone(),
+        nft_contract.clone(),
+        &ExecuteMsg::SafeTransferNft {
+            recipient: receiver_contract.to_string(),
+            token_id: "test".to_string(),
+            msg: None,
+        },
+        &[],
+    );
+    assert!(result.is_err());
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_contract.clone(),
+            &QueryMsg::OwnerOf {
+                token_id: "test".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, admin.to_string());
+
+    // contract accepting the token is notified
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            nft_contract.clone(),
+            &ExecuteMsg::SafeTransferNft {
+                recipient: receiver_contract.to_string(),
+                token_id: "test".to_string(),
+                msg: Some(to_json_binary(&InnerMsg::Succeed).unwrap()),
+            },
+            &[],
+        )
+        .unwrap();
+    let mut wasm_events = response.events.iter().filter(|e| e.ty == "wasm");
+    let transfer_event = wasm_events.next().unwrap();
+    assert_eq!(
+        get_attribute(&transfer_event.attributes, "action"),
+        Some("safe_transfer_nft")
+    );
+    let receive_event = wasm_events.next().unwrap();
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "action"),
+        Some("receive_nft")
+    );
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "sender"),
+        Some(admin.as_str())
+    );
+
+    // wallets receive the token without any callback
+    let wallet = app.api().addr_make("wallet");
+    app.execute_contract(
+        admin.clone(),
+        nft_contract.clone(),
+        &ExecuteMsg::Mint {
+            token_id: "wallet_token".to_string(),
+            owner: admin.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            nft_contract.clone(),
+            &ExecuteMsg::SafeTransferNft {
+                recipient: wallet.to_string(),
+                token_id: "wallet_token".to_string(),
+                msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(response.events.iter().filter(|e| e.ty == "wasm").count(), 1);
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_contract,
+            &QueryMsg::OwnerOf {
+                token_id: "wallet_token".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, wallet.to_string());
+}
+
+#[test]
+fn test_cw721_base_receive_batch_succeed() {
+    use cw721_base::msg::*;
+    use cw721_receiver_tester::msg::InnerMsg;
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+
+    let Contracts {
+        nft_contract,
+        receiver_contract,
+    } = setup_contracts(&mut app, admin.clone());
+
+    // mint another token
+    app.execute_contract(
+        admin.clone(),
+        nft_contract.clone(),
+        &ExecuteMsg::Mint {
+            token_id: "test2".to_string(),
+            owner: admin.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // send tokens to receiver contract
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            nft_contract.clone(),
+            &ExecuteMsg::SendNftBatch {
+                contract: receiver_contract.to_string(),
+                token_ids: vec!["test".to_string(), "test2".to_string()],
+                msg: to_json_binary(&InnerMsg::Succeed).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    let mut wasm_events = response.events.iter().filter(|e| e.ty == "wasm");
+
+    let send_event = wasm_events.next().unwrap();
+    assert_eq!(
+        get_attribute(&send_event.attributes, "action"),
+        Some("send_nft_batch")
+    );
+    assert_eq!(
+        get_attribute(&send_event.attributes, "token_ids"),
+        Some("test,test2")
+    );
+
+    // receiver is called once for all tokens
+    let receive_event = wasm_events.next().unwrap();
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "action"),
+        Some("receive_nft_batch")
+    );
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "token_ids"),
+        Some("test,test2")
+    );
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "sender"),
+        Some(admin.as_str())
+    );
+    assert!(wasm_events.next().is_none());
+
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_contract,
+            &QueryMsg::OwnerOf {
+                token_id: "test2".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, receiver_contract.to_string());
+}
+
+#[test]
+fn test_cw721_base_receive_batch_fail() {
+    use cw721_base::msg::*;
+    use cw721_receiver_tester::msg::InnerMsg;
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+
+    let Contracts {
+        nft_contract,
+        receiver_contract,
+    } = setup_contracts(&mut app, admin.clone());
+
+    // send fail message
+    let result = app.execute_contract(
+        admin.clone(),
+        nft_contract.clone(),
+        &ExecuteMsg::SendNftBatch {
+            contract: receiver_contract.to_string(),
+            token_ids: vec!["test".to_string()],
+            msg: to_json_binary(&InnerMsg::Fail).unwrap(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // transfer is reverted
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_contract,
+            &QueryMsg::OwnerOf {
+                token_id: "test".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, admin.to_string());
+}
+
 struct Contracts {
     nft_contract: Addr,
     receiver_contract: Addr,
@@ -129,6 +370,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
                 minter: Some(admin.to_string()),
                 creator: Some(admin.to_string()),
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             &[],
             "nft".to_string(),
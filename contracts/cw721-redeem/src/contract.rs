@@ -0,0 +1,296 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::{Cw721ExecuteMsg, NftAttributes};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{
+    Cw721HookMsg, DepositsResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RecipesResponse,
+};
+use crate::state::{
+    Config, Recipe, RecipeInput, RecipeOutput, TraitFilter, CONFIG, DEPOSITS, RECIPES, RECIPE_COUNT,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-redeem";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+type NftExecuteMsg =
+    Cw721ExecuteMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg, Empty>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: admin.clone(),
+        },
+    )?;
+    RECIPE_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("admin", admin.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, env, info, receive_msg),
+        ExecuteMsg::AddRecipe {
+            input_collection,
+            input_count,
+            trait_filter,
+            output_collection,
+            token_uri,
+            extension,
+        } => {
+            let input = RecipeInput {
+                collection: deps.api.addr_validate(&input_collection)?,
+                count: input_count,
+                trait_filter,
+            };
+            let output = RecipeOutput {
+                collection: deps.api.addr_validate(&output_collection)?,
+                token_uri,
+                extension,
+            };
+            execute_add_recipe(deps, info, input, output)
+        }
+        ExecuteMsg::SetRecipeEnabled { recipe_id, enabled } => {
+            execute_set_recipe_enabled(deps, info, recipe_id, enabled)
+        }
+        ExecuteMsg::Withdraw { recipe_id } => execute_withdraw(deps, info, recipe_id),
+    }
+}
+
+fn assert_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.admin != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+fn load_recipe(deps: Deps, recipe_id: u64) -> Result<Recipe, ContractError> {
+    RECIPES
+        .may_load(deps.storage, recipe_id)?
+        .ok_or(ContractError::RecipeNotFound { recipe_id })
+}
+
+fn has_trait(extension: &Option<NftAttributes>, filter: &TraitFilter) -> bool {
+    extension
+        .as_ref()
+        .and_then(|extension| extension.attributes.as_ref())
+        .is_some_and(|attributes| {
+            attributes
+                .iter()
+                .any(|a| a.trait_type == filter.trait_type && a.value == filter.value)
+        })
+}
+
+pub fn execute_add_recipe(
+    deps: DepsMut,
+    info: MessageInfo,
+    input: RecipeInput,
+    output: RecipeOutput,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+    if input.count == 0 {
+        return Err(ContractError::InvalidCount {});
+    }
+    let recipe_id = RECIPE_COUNT.update(deps.storage, |count| StdResult::Ok(count + 1))?;
+    let recipe = Recipe {
+        id: recipe_id,
+        input,
+        output,
+        enabled: true,
+        redeemed: 0,
+    };
+    RECIPES.save(deps.storage, recipe_id, &recipe)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_recipe")
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("input_collection", recipe.input.collection.to_string())
+        .add_attribute("input_count", recipe.input.count.to_string())
+        .add_attribute("output_collection", recipe.output.collection.to_string()))
+}
+
+pub fn execute_set_recipe_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipe_id: u64,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+    let mut recipe = load_recipe(deps.as_ref(), recipe_id)?;
+    recipe.enabled = enabled;
+    RECIPES.save(deps.storage, recipe_id, &recipe)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_recipe_enabled")
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Deposits the received token, and redeems the deposits once the recipe's count is reached.
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Cw721HookMsg::Redeem { recipe_id } = from_json(&receive_msg.msg)?;
+    let mut recipe = load_recipe(deps.as_ref(), recipe_id)?;
+    if !recipe.enabled {
+        return Err(ContractError::RecipeDisabled { recipe_id });
+    }
+    if info.sender != recipe.input.collection {
+        return Err(ContractError::WrongCollection {});
+    }
+    let input_helper = DefaultCw721Helper::new(recipe.input.collection.clone());
+    if let Some(filter) = &recipe.input.trait_filter {
+        // attributes only, so collections with any extension type (e.g. cw2981) are supported
+        let nft_info = input_helper
+            .nft_info::<_, Option<NftAttributes>>(&deps.querier, &receive_msg.token_id)?;
+        if !has_trait(&nft_info.extension, filter) {
+            return Err(ContractError::TraitMismatch {
+                trait_type: filter.trait_type.clone(),
+                value: filter.value.clone(),
+            });
+        }
+    }
+    let owner = deps.api.addr_validate(&receive_msg.sender)?;
+    let mut deposits = DEPOSITS
+        .may_load(deps.storage, (recipe_id, &owner))?
+        .unwrap_or_default();
+    deposits.push(receive_msg.token_id.clone());
+
+    let res = Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("token_id", receive_msg.token_id);
+    if deposits.len() < recipe.input.count as usize {
+        DEPOSITS.save(deps.storage, (recipe_id, &owner), &deposits)?;
+        return Ok(res);
+    }
+
+    // enough tokens deposited: burn them, and mint the output token
+    DEPOSITS.remove(deps.storage, (recipe_id, &owner));
+    recipe.redeemed += 1;
+    RECIPES.save(deps.storage, recipe_id, &recipe)?;
+    let burn_msgs = deposits
+        .into_iter()
+        .map(|token_id| input_helper.call(NftExecuteMsg::Burn { token_id }))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+    // namespaced by this contract, so ids don't collide with other tokens of the output collection
+    let output_token_id = format!(
+        "redeem/{}/{}/{}",
+        env.contract.address, recipe_id, recipe.redeemed
+    );
+    let mint_msg =
+        DefaultCw721Helper::new(recipe.output.collection.clone()).call(NftExecuteMsg::Mint {
+            token_id: output_token_id.clone(),
+            owner: owner.to_string(),
+            token_uri: recipe.output.token_uri,
+            extension: recipe.output.extension,
+        })?;
+
+    Ok(res
+        .add_messages(burn_msgs)
+        .add_message(mint_msg)
+        .add_attribute("redeemed_token_id", output_token_id))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipe_id: u64,
+) -> Result<Response, ContractError> {
+    let recipe = load_recipe(deps.as_ref(), recipe_id)?;
+    let deposits = DEPOSITS
+        .may_load(deps.storage, (recipe_id, &info.sender))?
+        .ok_or(ContractError::NoDeposits { recipe_id })?;
+    DEPOSITS.remove(deps.storage, (recipe_id, &info.sender));
+
+    let helper = DefaultCw721Helper::new(recipe.input.collection);
+    let transfer_msgs = deposits
+        .into_iter()
+        .map(|token_id| {
+            helper.call(NftExecuteMsg::TransferNft {
+                recipient: info.sender.to_string(),
+                token_id,
+            })
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new()
+        .add_messages(transfer_msgs)
+        .add_attribute("action", "withdraw")
+        .add_attribute("recipe_id", recipe_id.to_string())
+        .add_attribute("recipient", info.sender.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Recipe { recipe_id } => to_json_binary(&RECIPES.load(deps.storage, recipe_id)?),
+        QueryMsg::Recipes { start_after, limit } => {
+            to_json_binary(&query_recipes(deps, start_after, limit)?)
+        }
+        QueryMsg::Deposits { recipe_id, owner } => {
+            to_json_binary(&query_deposits(deps, recipe_id, owner)?)
+        }
+    }
+}
+
+pub fn query_recipes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RecipesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let recipes = RECIPES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, recipe)| recipe))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(RecipesResponse { recipes })
+}
+
+pub fn query_deposits(deps: Deps, recipe_id: u64, owner: String) -> StdResult<DepositsResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let token_ids = DEPOSITS
+        .may_load(deps.storage, (recipe_id, &owner))?
+        .unwrap_or_default();
+    Ok(DepositsResponse { token_ids })
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Recipe {recipe_id} not found")]
+    RecipeNotFound { recipe_id: u64 },
+
+    #[error("Recipe {recipe_id} is disabled")]
+    RecipeDisabled { recipe_id: u64 },
+
+    #[error("Recipe input count must not be zero")]
+    InvalidCount {},
+
+    #[error("Token is not from the input collection of the recipe")]
+    WrongCollection {},
+
+    #[error("Token does not have trait {trait_type}: {value}")]
+    TraitMismatch { trait_type: String, value: String },
+
+    #[error("No tokens deposited for recipe {recipe_id}")]
+    NoDeposits { recipe_id: u64 },
+}
@@ -0,0 +1,69 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::DefaultOptionalNftExtensionMsg;
+
+use crate::state::{Config, Recipe, TraitFilter};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Can add recipes and enable or disable them. Defaults to the sender.
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Deposits the received token for a redemption on behalf of its previous owner. Sent by the
+    /// cw721 contract on `SendNft`, with `Cw721HookMsg` as msg. Once enough tokens are deposited,
+    /// they are burned and the output token is minted to the owner.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Adds a recipe, enabled right away. Only admin can call this.
+    AddRecipe {
+        input_collection: String,
+        input_count: u32,
+        trait_filter: Option<TraitFilter>,
+        output_collection: String,
+        token_uri: Option<String>,
+        extension: DefaultOptionalNftExtensionMsg,
+    },
+    /// Enables or disables a recipe, e.g. at the end of an event. Only admin can call this.
+    SetRecipeEnabled { recipe_id: u64, enabled: bool },
+    /// Returns the tokens deposited by the sender for an incomplete redemption.
+    Withdraw { recipe_id: u64 },
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Deposits the token for the recipe.
+    Redeem { recipe_id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+
+    #[returns(Recipe)]
+    Recipe { recipe_id: u64 },
+
+    /// Lists all recipes, ordered by id.
+    #[returns(RecipesResponse)]
+    Recipes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the token ids deposited by the owner for an incomplete redemption.
+    #[returns(DepositsResponse)]
+    Deposits { recipe_id: u64, owner: String },
+}
+
+#[cw_serde]
+pub struct RecipesResponse {
+    pub recipes: Vec<Recipe>,
+}
+
+#[cw_serde]
+pub struct DepositsResponse {
+    pub token_ids: Vec<String>,
+}
@@ -0,0 +1,51 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw721::DefaultOptionalNftExtensionMsg;
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    /// Can add recipes and enable or disable them.
+    pub admin: Addr,
+}
+
+/// Only input tokens having this trait in their onchain metadata are accepted.
+#[cw_serde]
+pub struct TraitFilter {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct RecipeInput {
+    /// Collection of the tokens to burn.
+    pub collection: Addr,
+    /// Number of tokens to burn per redemption.
+    pub count: u32,
+    pub trait_filter: Option<TraitFilter>,
+}
+
+#[cw_serde]
+pub struct RecipeOutput {
+    /// Collection of the token to mint, for which this contract must be the minter.
+    pub collection: Addr,
+    pub token_uri: Option<String>,
+    pub extension: DefaultOptionalNftExtensionMsg,
+}
+
+#[cw_serde]
+pub struct Recipe {
+    pub id: u64,
+    pub input: RecipeInput,
+    pub output: RecipeOutput,
+    /// Disabled recipes no longer accept tokens, but deposits can still be withdrawn.
+    pub enabled: bool,
+    /// Number of redemptions so far, used to assign output token ids.
+    pub redeemed: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const RECIPE_COUNT: Item<u64> = Item::new("recipe_count");
+pub const RECIPES: Map<u64, Recipe> = Map::new("recipes");
+/// Stored as ((recipe id, owner), token ids), tokens received for a redemption which is not complete yet.
+pub const DEPOSITS: Map<(u64, &Addr), Vec<String>> = Map::new("deposits");
@@ -0,0 +1,282 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{to_json_binary, Addr};
+use cw721::msg::{NftExtensionMsg, NftInfoResponse, OwnerOfResponse};
+use cw721::state::Trait;
+use cw721::DefaultOptionalNftExtension;
+use cw721_metadata_onchain::msg as onchain_msg;
+use cw721_redeem::msg::{Cw721HookMsg, DepositsResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw721_redeem::state::TraitFilter;
+use cw721_redeem::ContractError;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+struct Contracts {
+    season1: Addr,
+    season2: Addr,
+    redeem: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    alice: Addr,
+    bob: Addr,
+}
+
+fn setup_users() -> Users {
+    let api = MockApi::default();
+    Users {
+        creator: api.addr_make("creator"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+    }
+}
+
+fn rarity(value: &str) -> Trait {
+    Trait {
+        display_type: None,
+        trait_type: "rarity".to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Setup the redeem contract, a "season1" collection with "common" tokens "1" and "2" and "rare"
+/// token "3" owned by alice, and a "season2" collection minted by the redeem contract
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_redeem::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_metadata_onchain::entry::execute,
+        cw721_metadata_onchain::entry::instantiate,
+        cw721_metadata_onchain::entry::query,
+    )));
+
+    let redeem = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg { admin: None },
+            &[],
+            "redeem".to_string(),
+            None,
+        )
+        .unwrap();
+
+    let mut collections = vec![];
+    for (name, minter) in [("season1", None), ("season2", Some(redeem.to_string()))] {
+        let nft_contract = app
+            .instantiate_contract(
+                nft_code_id,
+                users.creator.clone(),
+                &onchain_msg::InstantiateMsg {
+                    name: name.to_string(),
+                    symbol: name.to_uppercase(),
+                    collection_info_extension: None,
+                    minter,
+                    creator: None,
+                    withdraw_address: None,
+                    ics721_bridge: None,
+                },
+                &[],
+                name.to_string(),
+                None,
+            )
+            .unwrap();
+        collections.push(nft_contract);
+    }
+    for (token_id, value) in [("1", "common"), ("2", "common"), ("3", "rare")] {
+        app.execute_contract(
+            users.creator.clone(),
+            collections[0].clone(),
+            &onchain_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: users.alice.to_string(),
+                token_uri: None,
+                extension: Some(NftExtensionMsg {
+                    attributes: Some(vec![rarity(value)]),
+                    ..NftExtensionMsg::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    Contracts {
+        season1: collections[0].clone(),
+        season2: collections[1].clone(),
+        redeem,
+    }
+}
+
+/// Adds a recipe burning 2 "common" season1 tokens for a season2 token
+fn add_recipe(app: &mut App, contracts: &Contracts, sender: &Addr) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.redeem.clone(),
+        &ExecuteMsg::AddRecipe {
+            input_collection: contracts.season1.to_string(),
+            input_count: 2,
+            trait_filter: Some(TraitFilter {
+                trait_type: "rarity".to_string(),
+                value: "common".to_string(),
+            }),
+            output_collection: contracts.season2.to_string(),
+            token_uri: None,
+            extension: Some(NftExtensionMsg {
+                name: Some("Season 2 pass".to_string()),
+                ..NftExtensionMsg::default()
+            }),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn redeem(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    token_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.season1.clone(),
+        &onchain_msg::ExecuteMsg::SendNft {
+            contract: contracts.redeem.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Cw721HookMsg::Redeem { recipe_id: 1 }).unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, collection: &Addr, token_id: &str) -> Option<String> {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection.clone(),
+            &onchain_msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .ok()
+        .map(|res| res.owner)
+}
+
+fn deposits(app: &App, contracts: &Contracts, owner: &Addr) -> Vec<String> {
+    let res: DepositsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.redeem.clone(),
+            &QueryMsg::Deposits {
+                recipe_id: 1,
+                owner: owner.to_string(),
+            },
+        )
+        .unwrap();
+    res.token_ids
+}
+
+#[test]
+fn test_redeem() {
+    let mut app = App::default();
+    let users = setup_users();
+    let contracts = setup_contracts(&mut app, &users);
+    assert_eq!(add_recipe(&mut app, &contracts, &users.creator), None);
+
+    // tokens are deposited until the recipe's count is reached
+    assert_eq!(redeem(&mut app, &contracts, &users.alice, "1"), None);
+    assert_eq!(deposits(&app, &contracts, &users.alice), vec!["1"]);
+    assert_eq!(
+        owner_of(&app, &contracts.season1, "1"),
+        Some(contracts.redeem.to_string())
+    );
+
+    // tokens must match the trait filter
+    assert_eq!(
+        redeem(&mut app, &contracts, &users.alice, "3"),
+        Some(ContractError::TraitMismatch {
+            trait_type: "rarity".to_string(),
+            value: "common".to_string(),
+        })
+    );
+
+    // inputs are burned, and the output is minted to the holder
+    assert_eq!(redeem(&mut app, &contracts, &users.alice, "2"), None);
+    assert_eq!(owner_of(&app, &contracts.season1, "1"), None);
+    assert_eq!(owner_of(&app, &contracts.season1, "2"), None);
+    let output_token_id = format!("redeem/{}/1/1", contracts.redeem);
+    assert_eq!(
+        owner_of(&app, &contracts.season2, &output_token_id),
+        Some(users.alice.to_string())
+    );
+    let res: NftInfoResponse<DefaultOptionalNftExtension> = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.season2.clone(),
+            &onchain_msg::QueryMsg::NftInfo {
+                token_id: output_token_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.extension.unwrap().name,
+        Some("Season 2 pass".to_string())
+    );
+    assert!(deposits(&app, &contracts, &users.alice).is_empty());
+}
+
+#[test]
+fn test_withdraw_and_disable() {
+    let mut app = App::default();
+    let users = setup_users();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // only admin manages recipes
+    assert_eq!(
+        add_recipe(&mut app, &contracts, &users.bob),
+        Some(ContractError::Unauthorized {})
+    );
+    assert_eq!(add_recipe(&mut app, &contracts, &users.creator), None);
+    assert_eq!(redeem(&mut app, &contracts, &users.alice, "1"), None);
+
+    // disabled recipes don't accept tokens
+    app.execute_contract(
+        users.creator.clone(),
+        contracts.redeem.clone(),
+        &ExecuteMsg::SetRecipeEnabled {
+            recipe_id: 1,
+            enabled: false,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        redeem(&mut app, &contracts, &users.alice, "2"),
+        Some(ContractError::RecipeDisabled { recipe_id: 1 })
+    );
+
+    // deposits of incomplete redemptions can be withdrawn
+    let withdraw = |app: &mut App| {
+        app.execute_contract(
+            users.alice.clone(),
+            contracts.redeem.clone(),
+            &ExecuteMsg::Withdraw { recipe_id: 1 },
+            &[],
+        )
+        .err()
+        .map(|err| err.downcast::<ContractError>().unwrap())
+    };
+    assert_eq!(withdraw(&mut app), None);
+    assert_eq!(
+        owner_of(&app, &contracts.season1, "1"),
+        Some(users.alice.to_string())
+    );
+    assert_eq!(
+        withdraw(&mut app),
+        Some(ContractError::NoDeposits { recipe_id: 1 })
+    );
+}
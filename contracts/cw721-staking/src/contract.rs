@@ -0,0 +1,346 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, Duration};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, StakedToken,
+    StakedTokensResponse, UnbondingToken, UnbondingTokensResponse,
+};
+use crate::state::{
+    Config, RewardIndex, Staker, CONFIG, REWARD_INDEX, STAKED_TOKENS, STAKERS, UNBONDING_TOKENS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-staking";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+    let config = Config {
+        admin,
+        cw721_address: deps.api.addr_validate(&msg.cw721_address)?,
+        reward_denom: msg.reward_denom,
+        reward_per_second: msg.reward_per_second,
+        unbonding_period: msg.unbonding_period,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    REWARD_INDEX.save(
+        deps.storage,
+        &RewardIndex {
+            index: Uint128::zero(),
+            last_updated: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("admin", config.admin.to_string())
+        .add_attribute("cw721_address", config.cw721_address.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_stake(deps, env, info, receive_msg),
+        ExecuteMsg::Unstake { token_ids } => execute_unstake(deps, env, info, token_ids),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Fund {} => execute_fund(deps, info),
+        ExecuteMsg::UpdateConfig {
+            reward_per_second,
+            unbonding_period,
+        } => execute_update_config(deps, env, info, reward_per_second, unbonding_period),
+    }
+}
+
+/// Accrues rewards emitted until now and returns the updated staker.
+fn update_rewards(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    owner: &Addr,
+) -> StdResult<Staker> {
+    let mut reward_index = REWARD_INDEX.load(storage)?;
+    reward_index.update(config.reward_per_second, env.block.time);
+    REWARD_INDEX.save(storage, &reward_index)?;
+    let mut staker = STAKERS.may_load(storage, owner)?.unwrap_or_default();
+    staker.accrue(reward_index.index);
+    Ok(staker)
+}
+
+pub fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.cw721_address {
+        return Err(ContractError::UnauthorizedNftContract {});
+    }
+    let owner = deps.api.addr_validate(&receive_msg.sender)?;
+
+    let mut staker = update_rewards(deps.storage, &env, &config, &owner)?;
+    staker.staked += 1;
+    STAKERS.save(deps.storage, &owner, &staker)?;
+    STAKED_TOKENS.save(
+        deps.storage,
+        (&owner, &receive_msg.token_id),
+        &env.block.time,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake")
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("token_id", receive_msg.token_id))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    if token_ids.is_empty() {
+        return Err(ContractError::NoTokenIds {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let mut staker = update_rewards(deps.storage, &env, &config, &info.sender)?;
+
+    let claimable_at = config.unbonding_period.after(&env.block);
+    for token_id in &token_ids {
+        let key = (&info.sender, token_id.as_str());
+        if !STAKED_TOKENS.has(deps.storage, key) {
+            return Err(ContractError::NotStaked {
+                token_id: token_id.clone(),
+            });
+        }
+        STAKED_TOKENS.remove(deps.storage, key);
+        UNBONDING_TOKENS.save(deps.storage, key, &claimable_at)?;
+        staker.staked -= 1;
+    }
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unstake")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("token_ids", token_ids.join(","))
+        .add_attribute("claimable_at", claimable_at.to_string()))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut staker = update_rewards(deps.storage, &env, &config, &info.sender)?;
+
+    // pay out as much as funded, the rest remains pending
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.reward_denom)?
+        .amount;
+    let rewards = staker.pending_rewards.min(balance);
+    staker.pending_rewards -= rewards;
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+
+    // send back NFTs, whose unbonding period has passed
+    let unbonded = UNBONDING_TOKENS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, claimable_at)) => claimable_at.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .map(|item| item.map(|(token_id, _)| token_id))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    if rewards.is_zero() && unbonded.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let cw721 = DefaultCw721Helper::new(config.cw721_address);
+    let mut response = Response::new();
+    for token_id in &unbonded {
+        UNBONDING_TOKENS.remove(deps.storage, (&info.sender, token_id));
+        let transfer_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::TransferNft {
+            recipient: info.sender.to_string(),
+            token_id: token_id.clone(),
+        };
+        response = response.add_message(cw721.call(transfer_msg)?);
+    }
+    if !rewards.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![coin(rewards.u128(), &config.reward_denom)],
+        });
+    }
+
+    Ok(response
+        .add_attribute("action", "claim")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("rewards", rewards.to_string())
+        .add_attribute("token_ids", unbonded.join(",")))
+}
+
+pub fn execute_fund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let amount = must_pay(&info, &config.reward_denom)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_per_second: Option<Uint128>,
+    unbonding_period: Option<Duration>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    // accrue rewards at previous emission, before changing it
+    let mut reward_index = REWARD_INDEX.load(deps.storage)?;
+    reward_index.update(config.reward_per_second, env.block.time);
+    REWARD_INDEX.save(deps.storage, &reward_index)?;
+
+    if let Some(reward_per_second) = reward_per_second {
+        config.reward_per_second = reward_per_second;
+    }
+    if let Some(unbonding_period) = unbonding_period {
+        config.unbonding_period = unbonding_period;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("reward_per_second", config.reward_per_second.to_string())
+        .add_attribute("unbonding_period", config.unbonding_period.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::StakedTokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_staked_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::UnbondingTokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_unbonding_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::PendingRewards { owner } => {
+            to_json_binary(&query_pending_rewards(deps, env, owner)?)
+        }
+    }
+}
+
+pub fn query_staked_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<StakedTokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let tokens = STAKED_TOKENS
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(token_id, staked_at)| StakedToken {
+                token_id,
+                staked_at,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(StakedTokensResponse { owner, tokens })
+}
+
+pub fn query_unbonding_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<UnbondingTokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let tokens = UNBONDING_TOKENS
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(token_id, claimable_at)| UnbondingToken {
+                token_id,
+                claimable_at,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(UnbondingTokensResponse { owner, tokens })
+}
+
+pub fn query_pending_rewards(
+    deps: Deps,
+    env: Env,
+    owner: String,
+) -> StdResult<PendingRewardsResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut reward_index = REWARD_INDEX.load(deps.storage)?;
+    reward_index.update(config.reward_per_second, env.block.time);
+    let mut staker = STAKERS.may_load(deps.storage, &owner)?.unwrap_or_default();
+    staker.accrue(reward_index.index);
+    Ok(PendingRewardsResponse {
+        owner,
+        rewards: coin(staker.pending_rewards.u128(), config.reward_denom),
+    })
+}
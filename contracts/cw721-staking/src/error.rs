@@ -0,0 +1,27 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Only NFTs of the staking collection can be staked")]
+    UnauthorizedNftContract {},
+
+    #[error("Token {token_id} is not staked by sender")]
+    NotStaked { token_id: String },
+
+    #[error("No token ids given")]
+    NoTokenIds {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+}
@@ -0,0 +1,95 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw_utils::{Duration, Expiration};
+
+use crate::state::Config;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Admin funding rewards and updating the config. Defaults to sender.
+    pub admin: Option<String>,
+    /// The only collection whose NFTs can be staked.
+    pub cw721_address: String,
+    /// Native denom rewards are paid in.
+    pub reward_denom: String,
+    /// Reward emitted per staked token per second.
+    pub reward_per_second: Uint128,
+    /// Period between unstaking and claiming back an NFT.
+    pub unbonding_period: Duration,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Stakes the received NFT on behalf of its previous owner. Sent by the cw721 contract on `SendNft`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Stops staking the given tokens, which can be claimed back after the unbonding period.
+    Unstake { token_ids: Vec<String> },
+    /// Pays out pending rewards, and sends back all NFTs whose unbonding period has passed.
+    Claim {},
+    /// Funds rewards with the sent reward denom. Only admin can call this.
+    Fund {},
+    /// Updates reward emission and unbonding period. Rewards accrued so far are kept.
+    /// Only admin can call this.
+    UpdateConfig {
+        reward_per_second: Option<Uint128>,
+        unbonding_period: Option<Duration>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+
+    /// Lists tokens staked by owner, along with their stake start time.
+    #[returns(StakedTokensResponse)]
+    StakedTokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Lists tokens unstaked by owner and not yet claimed, along with the end of their unbonding period.
+    #[returns(UnbondingTokensResponse)]
+    UnbondingTokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns rewards accrued by owner and not yet claimed.
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { owner: String },
+}
+
+#[cw_serde]
+pub struct StakedToken {
+    pub token_id: String,
+    pub staked_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct StakedTokensResponse {
+    pub owner: Addr,
+    pub tokens: Vec<StakedToken>,
+}
+
+#[cw_serde]
+pub struct UnbondingToken {
+    pub token_id: String,
+    pub claimable_at: Expiration,
+}
+
+#[cw_serde]
+pub struct UnbondingTokensResponse {
+    pub owner: Addr,
+    pub tokens: Vec<UnbondingToken>,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub owner: Addr,
+    pub rewards: Coin,
+}
@@ -0,0 +1,58 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr,
+    pub cw721_address: Addr,
+    pub reward_denom: String,
+    pub reward_per_second: Uint128,
+    pub unbonding_period: Duration,
+}
+
+/// Rewards emitted per staked token since instantiation. Each staker keeps a snapshot of it,
+/// so rewards are accounted correctly when the reward emission changes.
+#[cw_serde]
+pub struct RewardIndex {
+    pub index: Uint128,
+    pub last_updated: Timestamp,
+}
+
+impl RewardIndex {
+    /// Accrues rewards emitted since last update.
+    pub fn update(&mut self, reward_per_second: Uint128, now: Timestamp) {
+        let seconds = now.seconds().saturating_sub(self.last_updated.seconds());
+        self.index += reward_per_second * Uint128::from(seconds);
+        self.last_updated = now;
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Staker {
+    /// Number of staked tokens, earning rewards.
+    pub staked: u64,
+    /// Reward index, when rewards were last accrued.
+    pub reward_index: Uint128,
+    /// Accrued rewards, not yet claimed.
+    pub pending_rewards: Uint128,
+}
+
+impl Staker {
+    /// Accrues rewards of staked tokens until the given reward index.
+    pub fn accrue(&mut self, reward_index: Uint128) {
+        let delta = reward_index - self.reward_index;
+        self.pending_rewards += delta * Uint128::from(self.staked);
+        self.reward_index = reward_index;
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const REWARD_INDEX: Item<RewardIndex> = Item::new("reward_index");
+pub const STAKERS: Map<&Addr, Staker> = Map::new("stakers");
+/// Stored as ((owner, token id), stake start time).
+pub const STAKED_TOKENS: Map<(&Addr, &str), Timestamp> = Map::new("staked_tokens");
+/// Stored as ((owner, token id), end of unbonding period).
+pub const UNBONDING_TOKENS: Map<(&Addr, &str), Expiration> = Map::new("unbonding_tokens");
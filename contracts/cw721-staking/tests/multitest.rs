@@ -0,0 +1,346 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Empty, Uint128};
+use cw721::msg::OwnerOfResponse;
+use cw721_staking::msg::{
+    ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, StakedTokensResponse,
+    UnbondingTokensResponse,
+};
+use cw721_staking::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cw_utils::Duration;
+
+const DENOM: &str = "ustake";
+const REWARD_PER_SECOND: u128 = 10;
+const UNBONDING_PERIOD: u64 = 100;
+
+struct Contracts {
+    nft_contract: Addr,
+    staking_contract: Addr,
+}
+
+/// Setup the cw721-base and cw721-staking contracts, mint tokens to owner and fund rewards
+fn setup_contracts(app: &mut App, admin: Addr, owner: Addr) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_staking::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            admin.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: None,
+                minter: Some(admin.to_string()),
+                creator: Some(admin.to_string()),
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+
+    let staking_contract = app
+        .instantiate_contract(
+            code_id,
+            admin.clone(),
+            &InstantiateMsg {
+                admin: None,
+                cw721_address: nft_contract.to_string(),
+                reward_denom: DENOM.to_string(),
+                reward_per_second: Uint128::new(REWARD_PER_SECOND),
+                unbonding_period: Duration::Time(UNBONDING_PERIOD),
+            },
+            &[],
+            "staking".to_string(),
+            None,
+        )
+        .unwrap();
+
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            admin.clone(),
+            nft_contract.clone(),
+            &base_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.execute_contract(
+        admin,
+        staking_contract.clone(),
+        &ExecuteMsg::Fund {},
+        &coins(1_000_000, DENOM),
+    )
+    .unwrap();
+
+    Contracts {
+        nft_contract,
+        staking_contract,
+    }
+}
+
+fn setup_app() -> (App, Addr, Addr) {
+    let api = MockApi::default();
+    let admin = api.addr_make("admin");
+    let owner = api.addr_make("owner");
+    let app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &admin, coins(1_000_000, DENOM))
+            .unwrap();
+    });
+    (app, admin, owner)
+}
+
+fn stake(app: &mut App, contracts: &Contracts, owner: &Addr, token_id: &str) {
+    app.execute_contract(
+        owner.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.staking_contract.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Empty {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn pending_rewards(app: &App, contracts: &Contracts, owner: &Addr) -> u128 {
+    let res: PendingRewardsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.staking_contract.clone(),
+            &QueryMsg::PendingRewards {
+                owner: owner.to_string(),
+            },
+        )
+        .unwrap();
+    res.rewards.amount.u128()
+}
+
+fn owner_of(app: &App, contracts: &Contracts, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_stake_and_earn_rewards() {
+    let (mut app, admin, owner) = setup_app();
+    let contracts = setup_contracts(&mut app, admin, owner.clone());
+
+    stake(&mut app, &contracts, &owner, "1");
+    let res: StakedTokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.staking_contract.clone(),
+            &QueryMsg::StakedTokens {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.tokens.len(), 1);
+    assert_eq!(res.tokens[0].token_id, "1");
+    assert_eq!(res.tokens[0].staked_at, app.block_info().time);
+    assert_eq!(owner_of(&app, &contracts, "1"), contracts.staking_contract);
+
+    // 1 token for 10 seconds, then 2 tokens for 10 seconds
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    assert_eq!(
+        pending_rewards(&app, &contracts, &owner),
+        10 * REWARD_PER_SECOND
+    );
+    stake(&mut app, &contracts, &owner, "2");
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    assert_eq!(
+        pending_rewards(&app, &contracts, &owner),
+        30 * REWARD_PER_SECOND
+    );
+
+    // claim rewards
+    app.execute_contract(
+        owner.clone(),
+        contracts.staking_contract.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&owner, DENOM).unwrap(),
+        coin(30 * REWARD_PER_SECOND, DENOM)
+    );
+    assert_eq!(pending_rewards(&app, &contracts, &owner), 0);
+
+    // nothing left to claim in same block
+    let err: ContractError = app
+        .execute_contract(
+            owner.clone(),
+            contracts.staking_contract.clone(),
+            &ExecuteMsg::Claim {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+}
+
+#[test]
+fn test_unstake_and_claim() {
+    let (mut app, admin, owner) = setup_app();
+    let contracts = setup_contracts(&mut app, admin.clone(), owner.clone());
+    stake(&mut app, &contracts, &owner, "1");
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+
+    // only staker can unstake
+    let err: ContractError = app
+        .execute_contract(
+            admin,
+            contracts.staking_contract.clone(),
+            &ExecuteMsg::Unstake {
+                token_ids: vec!["1".to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotStaked {
+            token_id: "1".to_string()
+        }
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        contracts.staking_contract.clone(),
+        &ExecuteMsg::Unstake {
+            token_ids: vec!["1".to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    let res: UnbondingTokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.staking_contract.clone(),
+            &QueryMsg::UnbondingTokens {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.tokens.len(), 1);
+
+    // unbonding tokens don't earn rewards
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD / 2));
+    assert_eq!(
+        pending_rewards(&app, &contracts, &owner),
+        10 * REWARD_PER_SECOND
+    );
+
+    // rewards are claimed, but NFT is still unbonding
+    app.execute_contract(
+        owner.clone(),
+        contracts.staking_contract.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "1"), contracts.staking_contract);
+
+    // NFT is sent back after unbonding period
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD / 2));
+    app.execute_contract(
+        owner.clone(),
+        contracts.staking_contract.clone(),
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "1"), owner);
+    assert_eq!(
+        app.wrap().query_balance(&owner, DENOM).unwrap(),
+        coin(10 * REWARD_PER_SECOND, DENOM)
+    );
+}
+
+#[test]
+fn test_only_collection_and_admin() {
+    let (mut app, admin, owner) = setup_app();
+    let contracts = setup_contracts(&mut app, admin.clone(), owner.clone());
+
+    // NFTs of other collections are rejected
+    let err: ContractError = app
+        .execute_contract(
+            owner.clone(),
+            contracts.staking_contract.clone(),
+            &ExecuteMsg::ReceiveNft(cw721::receiver::Cw721ReceiveMsg {
+                sender: owner.to_string(),
+                token_id: "1".to_string(),
+                msg: to_json_binary(&Empty {}).unwrap(),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnauthorizedNftContract {});
+
+    // only admin can update config
+    let update_msg = ExecuteMsg::UpdateConfig {
+        reward_per_second: Some(Uint128::new(2 * REWARD_PER_SECOND)),
+        unbonding_period: None,
+    };
+    let err: ContractError = app
+        .execute_contract(
+            owner.clone(),
+            contracts.staking_contract.clone(),
+            &update_msg,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // rewards accrued before an update are kept
+    stake(&mut app, &contracts, &owner, "1");
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    app.execute_contract(admin, contracts.staking_contract.clone(), &update_msg, &[])
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    assert_eq!(
+        pending_rewards(&app, &contracts, &owner),
+        30 * REWARD_PER_SECOND
+    );
+}
@@ -0,0 +1,401 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::traits::Cw721Calls;
+use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+use crate::error::ContractError;
+use crate::msg::{Cw721HookMsg, ExecuteMsg, InstantiateMsg, NftMsg, QueryMsg, SwapsResponse};
+use crate::state::{Nft, Swap, SWAPS, SWAP_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-swap";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    SWAP_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateSwap {
+            counterparty,
+            offered_nfts,
+            wanted_nfts,
+            wanted_funds,
+            expires,
+        } => execute_create_swap(
+            deps,
+            env,
+            info,
+            counterparty,
+            offered_nfts,
+            wanted_nfts,
+            wanted_funds,
+            expires,
+        ),
+        ExecuteMsg::DepositFunds { swap_id } => execute_deposit_funds(deps, env, info, swap_id),
+        ExecuteMsg::Cancel { swap_id } => execute_cancel(deps, env, info, swap_id),
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nfts(
+            deps,
+            env,
+            info,
+            receive_msg.sender,
+            vec![receive_msg.token_id],
+            receive_msg.msg,
+        ),
+        ExecuteMsg::ReceiveNftBatch(receive_msg) => execute_receive_nfts(
+            deps,
+            env,
+            info,
+            receive_msg.sender,
+            receive_msg.token_ids,
+            receive_msg.msg,
+        ),
+    }
+}
+
+fn load_swap(deps: Deps, swap_id: u64) -> Result<Swap, ContractError> {
+    SWAPS
+        .may_load(deps.storage, swap_id)?
+        .ok_or(ContractError::SwapNotFound { swap_id })
+}
+
+/// Sorts coins by denom and removes zero amounts, so bundles of funds can be compared.
+fn normalize_funds(funds: &[Coin]) -> Vec<Coin> {
+    let mut funds: Vec<Coin> = funds
+        .iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .cloned()
+        .collect();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    funds
+}
+
+fn validate_nfts(deps: Deps, nfts: Vec<NftMsg>) -> StdResult<Vec<Nft>> {
+    nfts.into_iter()
+        .map(|nft| {
+            Ok(Nft {
+                collection: deps.api.addr_validate(&nft.collection)?,
+                token_id: nft.token_id,
+            })
+        })
+        .collect()
+}
+
+fn transfer_nft_msg(nft: &Nft, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let transfer_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: nft.token_id.clone(),
+    };
+    DefaultCw721Helper::new(nft.collection.clone()).call(transfer_msg)
+}
+
+/// Returns the messages sending the given NFTs and funds to recipient.
+fn send_bundle_msgs<'a>(
+    nfts: impl Iterator<Item = &'a Nft>,
+    funds: &[Coin],
+    recipient: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = nfts
+        .map(|nft| transfer_nft_msg(nft, recipient))
+        .collect::<StdResult<Vec<_>>>()?;
+    if !funds.is_empty() {
+        msgs.push(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: funds.to_vec(),
+            }
+            .into(),
+        );
+    }
+    Ok(msgs)
+}
+
+/// Assigns the counterparty on first deposit, or checks it is the given depositor.
+fn assert_counterparty(swap: &mut Swap, depositor: &Addr) -> Result<(), ContractError> {
+    match &swap.counterparty {
+        Some(counterparty) if counterparty != depositor => Err(ContractError::Unauthorized {}),
+        Some(_) => Ok(()),
+        None => {
+            swap.counterparty = Some(depositor.clone());
+            Ok(())
+        }
+    }
+}
+
+/// Validates and saves a new swap, assigning its id.
+fn save_new_swap(deps: DepsMut, env: &Env, mut swap: Swap) -> Result<Response, ContractError> {
+    if swap.expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+    if (swap.offered_nfts.is_empty() && swap.offered_funds.is_empty())
+        || (swap.wanted_nfts.is_empty() && swap.wanted_funds.is_empty())
+    {
+        return Err(ContractError::EmptyBundle {});
+    }
+    swap.id = SWAP_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    SWAPS.save(deps.storage, swap.id, &swap)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_swap")
+        .add_attribute("swap_id", swap.id.to_string())
+        .add_attribute("creator", swap.creator.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    counterparty: Option<String>,
+    offered_nfts: Vec<NftMsg>,
+    wanted_nfts: Vec<NftMsg>,
+    wanted_funds: Vec<Coin>,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let swap = Swap {
+        id: 0,
+        creator: info.sender,
+        counterparty: counterparty
+            .map(|counterparty| deps.api.addr_validate(&counterparty))
+            .transpose()?,
+        offered_nfts: validate_nfts(deps.as_ref(), offered_nfts)?,
+        offered_funds: normalize_funds(&info.funds),
+        wanted_nfts: validate_nfts(deps.as_ref(), wanted_nfts)?,
+        wanted_funds: normalize_funds(&wanted_funds),
+        expires,
+        deposited_nfts: vec![],
+        wanted_funds_deposited: false,
+    };
+    save_new_swap(deps, &env, swap)
+}
+
+/// Handles NFTs received via `SendNft` or `SendNftBatch`: either opens a swap offering them, or
+/// deposits them for an existing swap.
+pub fn execute_receive_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    token_ids: Vec<String>,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let depositor = deps.api.addr_validate(&sender)?;
+    // the NFT contract is the sender, so collection can't be faked
+    let received: Vec<Nft> = token_ids
+        .into_iter()
+        .map(|token_id| Nft {
+            collection: info.sender.clone(),
+            token_id,
+        })
+        .collect();
+    match from_json(&msg)? {
+        Cw721HookMsg::Create {
+            counterparty,
+            offered_nfts,
+            wanted_nfts,
+            wanted_funds,
+            expires,
+        } => {
+            // received NFTs are offered and deposited right away
+            let mut all_offered_nfts = received.clone();
+            for nft in validate_nfts(deps.as_ref(), offered_nfts)? {
+                if !all_offered_nfts.contains(&nft) {
+                    all_offered_nfts.push(nft);
+                }
+            }
+            let swap = Swap {
+                id: 0,
+                creator: depositor,
+                counterparty: counterparty
+                    .map(|counterparty| deps.api.addr_validate(&counterparty))
+                    .transpose()?,
+                offered_nfts: all_offered_nfts,
+                offered_funds: vec![],
+                wanted_nfts: validate_nfts(deps.as_ref(), wanted_nfts)?,
+                wanted_funds: normalize_funds(&wanted_funds),
+                expires,
+                deposited_nfts: received,
+                wanted_funds_deposited: false,
+            };
+            save_new_swap(deps, &env, swap)
+        }
+        Cw721HookMsg::Deposit { swap_id } => {
+            execute_deposit_nfts(deps, env, depositor, received, swap_id)
+        }
+    }
+}
+
+pub fn execute_deposit_nfts(
+    deps: DepsMut,
+    env: Env,
+    depositor: Addr,
+    nfts: Vec<Nft>,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let mut swap = load_swap(deps.as_ref(), swap_id)?;
+    if swap.expires.is_expired(&env.block) {
+        return Err(ContractError::SwapExpired {});
+    }
+    for nft in nfts {
+        if swap.offered_nfts.contains(&nft) {
+            if depositor != swap.creator {
+                return Err(ContractError::Unauthorized {});
+            }
+        } else if swap.wanted_nfts.contains(&nft) {
+            assert_counterparty(&mut swap, &depositor)?;
+        } else {
+            return Err(ContractError::NotInSwap {
+                token_id: nft.token_id,
+            });
+        }
+        swap.deposited_nfts.push(nft);
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "deposit_nfts")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("depositor", depositor.to_string());
+    execute_swap_if_complete(deps, swap, response)
+}
+
+pub fn execute_deposit_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let mut swap = load_swap(deps.as_ref(), swap_id)?;
+    if swap.expires.is_expired(&env.block) {
+        return Err(ContractError::SwapExpired {});
+    }
+    if swap.wanted_funds_deposited || normalize_funds(&info.funds) != swap.wanted_funds {
+        return Err(ContractError::WrongFunds {});
+    }
+    assert_counterparty(&mut swap, &info.sender)?;
+    swap.wanted_funds_deposited = true;
+
+    let response = Response::new()
+        .add_attribute("action", "deposit_funds")
+        .add_attribute("swap_id", swap_id.to_string())
+        .add_attribute("depositor", info.sender.to_string());
+    execute_swap_if_complete(deps, swap, response)
+}
+
+/// Executes the swap once both sides are deposited, otherwise saves the deposit.
+fn execute_swap_if_complete(
+    deps: DepsMut,
+    swap: Swap,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let counterparty = match &swap.counterparty {
+        Some(counterparty) if swap.is_complete() => counterparty,
+        _ => {
+            SWAPS.save(deps.storage, swap.id, &swap)?;
+            return Ok(response);
+        }
+    };
+    SWAPS.remove(deps.storage, swap.id);
+
+    let to_counterparty =
+        send_bundle_msgs(swap.offered_nfts.iter(), &swap.offered_funds, counterparty)?;
+    let to_creator = send_bundle_msgs(swap.wanted_nfts.iter(), &swap.wanted_funds, &swap.creator)?;
+    Ok(response
+        .add_messages(to_counterparty)
+        .add_messages(to_creator)
+        .add_attribute("swapped", "true")
+        .add_attribute("counterparty", counterparty.to_string()))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    swap_id: u64,
+) -> Result<Response, ContractError> {
+    let swap = load_swap(deps.as_ref(), swap_id)?;
+    let is_party = info.sender == swap.creator || swap.counterparty.as_ref() == Some(&info.sender);
+    if !is_party && !swap.expires.is_expired(&env.block) {
+        return Err(ContractError::Unauthorized {});
+    }
+    SWAPS.remove(deps.storage, swap_id);
+
+    // refund deposits to the party that made them
+    let offered_deposits = swap
+        .deposited_nfts
+        .iter()
+        .filter(|nft| swap.offered_nfts.contains(nft));
+    let mut msgs = send_bundle_msgs(offered_deposits, &swap.offered_funds, &swap.creator)?;
+    if let Some(counterparty) = &swap.counterparty {
+        let wanted_deposits = swap
+            .deposited_nfts
+            .iter()
+            .filter(|nft| swap.wanted_nfts.contains(nft));
+        let wanted_funds = if swap.wanted_funds_deposited {
+            swap.wanted_funds.as_slice()
+        } else {
+            &[]
+        };
+        msgs.extend(send_bundle_msgs(
+            wanted_deposits,
+            wanted_funds,
+            counterparty,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "cancel")
+        .add_attribute("swap_id", swap_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Swap { swap_id } => to_json_binary(&SWAPS.load(deps.storage, swap_id)?),
+        QueryMsg::Swaps { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let swaps = SWAPS
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, swap)| swap))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&SwapsResponse { swaps })
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Swap {swap_id} not found")]
+    SwapNotFound { swap_id: u64 },
+
+    #[error("Swap expired")]
+    SwapExpired {},
+
+    #[error("Swap must not be expired on creation")]
+    InvalidExpiration {},
+
+    #[error("Both sides of a swap must contain NFTs or funds")]
+    EmptyBundle {},
+
+    #[error("Token {token_id} is not part of the swap")]
+    NotInSwap { token_id: String },
+
+    #[error("Sent funds don't match the wanted funds")]
+    WrongFunds {},
+}
@@ -0,0 +1,78 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Coin;
+use cw721::receiver::{Cw721BatchReceiveMsg, Cw721ReceiveMsg};
+use cw_utils::Expiration;
+
+use crate::state::Swap;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub struct NftMsg {
+    pub collection: String,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a swap of the offered bundle against the wanted bundle. Sent funds are offered along
+    /// with the offered NFTs, which are deposited afterwards via `SendNft` or `SendNftBatch`.
+    /// Swaps offering NFTs only can also be opened on `SendNft`, see `Cw721HookMsg::Create`.
+    CreateSwap {
+        /// Only this party can fulfil the swap. Anyone, if not set.
+        counterparty: Option<String>,
+        offered_nfts: Vec<NftMsg>,
+        wanted_nfts: Vec<NftMsg>,
+        wanted_funds: Vec<Coin>,
+        expires: Expiration,
+    },
+    /// Deposits the wanted funds. The exact wanted funds must be sent.
+    DepositFunds { swap_id: u64 },
+    /// Cancels a swap, and refunds all deposited NFTs and funds. Creator and counterparty can cancel
+    /// at any time, anyone else only once the swap is expired.
+    Cancel { swap_id: u64 },
+    /// Opens a swap offering the received NFT, or deposits it for an existing swap, with
+    /// `Cw721HookMsg` as msg. Sent by the cw721 contract on `SendNft`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Opens a swap offering the received NFTs, or deposits them for an existing swap, with
+    /// `Cw721HookMsg` as msg. Sent by the cw721 contract on `SendNftBatch`.
+    ReceiveNftBatch(Cw721BatchReceiveMsg),
+}
+
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Opens a swap offering the received NFTs, which are deposited right away. Further offered
+    /// NFTs, e.g. of other collections, are deposited afterwards via `Deposit`.
+    Create {
+        /// Only this party can fulfil the swap. Anyone, if not set.
+        counterparty: Option<String>,
+        /// Offered NFTs in addition to the received ones.
+        offered_nfts: Vec<NftMsg>,
+        wanted_nfts: Vec<NftMsg>,
+        wanted_funds: Vec<Coin>,
+        expires: Expiration,
+    },
+    /// Deposits NFTs of the offered bundle (by creator) or the wanted bundle (by counterparty).
+    /// Once both bundles are deposited, the swap is executed.
+    Deposit { swap_id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Swap)]
+    Swap { swap_id: u64 },
+
+    /// Lists all open swaps, ordered by id.
+    #[returns(SwapsResponse)]
+    Swaps {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct SwapsResponse {
+    pub swaps: Vec<Swap>,
+}
@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct Nft {
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct Swap {
+    pub id: u64,
+    pub creator: Addr,
+    /// Party fulfilling the swap. If not set on creation, the first party depositing wanted NFTs or
+    /// funds becomes the counterparty.
+    pub counterparty: Option<Addr>,
+    /// NFTs deposited by creator.
+    pub offered_nfts: Vec<Nft>,
+    /// Funds deposited by creator on creation.
+    pub offered_funds: Vec<Coin>,
+    /// NFTs deposited by counterparty.
+    pub wanted_nfts: Vec<Nft>,
+    /// Funds deposited by counterparty.
+    pub wanted_funds: Vec<Coin>,
+    pub expires: Expiration,
+    /// NFTs of both sides deposited so far.
+    pub deposited_nfts: Vec<Nft>,
+    pub wanted_funds_deposited: bool,
+}
+
+impl Swap {
+    /// Returns true once both sides have deposited all NFTs and funds.
+    pub fn is_complete(&self) -> bool {
+        self.offered_nfts
+            .iter()
+            .chain(self.wanted_nfts.iter())
+            .all(|nft| self.deposited_nfts.contains(nft))
+            && (self.wanted_funds.is_empty() || self.wanted_funds_deposited)
+    }
+}
+
+pub const SWAP_COUNT: Item<u64> = Item::new("swap_count");
+pub const SWAPS: Map<u64, Swap> = Map::new("swaps");
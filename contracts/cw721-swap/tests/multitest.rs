@@ -0,0 +1,397 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Coin};
+use cw721::msg::OwnerOfResponse;
+use cw721_swap::msg::{Cw721HookMsg, ExecuteMsg, InstantiateMsg, NftMsg, QueryMsg};
+use cw721_swap::state::Swap;
+use cw721_swap::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cw_utils::Expiration;
+
+const DENOM: &str = "uatom";
+
+struct Contracts {
+    collection_a: Addr,
+    collection_b: Addr,
+    swap_contract: Addr,
+}
+
+struct Users {
+    alice: Addr,
+    bob: Addr,
+    stranger: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+        stranger: api.addr_make("stranger"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        for user in [&users.alice, &users.bob] {
+            router
+                .bank
+                .init_balance(storage, user, coins(1_000, DENOM))
+                .unwrap();
+        }
+    });
+    (app, users)
+}
+
+/// Setup the swap contract and two collections: alice owns tokens "1" and "2" of collection A,
+/// bob owns tokens "1" and "2" of collection B
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_swap::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let mut collections = vec![];
+    for (name, owner) in [("a", &users.alice), ("b", &users.bob)] {
+        let collection = app
+            .instantiate_contract(
+                nft_code_id,
+                owner.clone(),
+                &base_msg::InstantiateMsg {
+                    name: name.to_string(),
+                    symbol: name.to_uppercase(),
+                    collection_info_extension: None,
+                    minter: None,
+                    creator: None,
+                    withdraw_address: None,
+                    ics721_bridge: None,
+                },
+                &[],
+                name.to_string(),
+                None,
+            )
+            .unwrap();
+        for token_id in ["1", "2"] {
+            app.execute_contract(
+                owner.clone(),
+                collection.clone(),
+                &base_msg::ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: None,
+                    extension: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        collections.push(collection);
+    }
+
+    let swap_contract = app
+        .instantiate_contract(
+            code_id,
+            users.alice.clone(),
+            &InstantiateMsg {},
+            &[],
+            "swap".to_string(),
+            None,
+        )
+        .unwrap();
+
+    Contracts {
+        collection_b: collections.pop().unwrap(),
+        collection_a: collections.pop().unwrap(),
+        swap_contract,
+    }
+}
+
+fn nft(collection: &Addr, token_id: &str) -> NftMsg {
+    NftMsg {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+    }
+}
+
+fn deposit(
+    app: &mut App,
+    contracts: &Contracts,
+    depositor: &Addr,
+    collection: &Addr,
+    token_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        depositor.clone(),
+        collection.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.swap_contract.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Cw721HookMsg::Deposit { swap_id: 1 }).unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, collection: &Addr, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            collection,
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+fn balance(app: &App, address: &Addr) -> Coin {
+    app.wrap().query_balance(address, DENOM).unwrap()
+}
+
+#[test]
+fn test_swap() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // alice offers two tokens of collection A for a token of collection B plus funds
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.swap_contract.clone(),
+        &ExecuteMsg::CreateSwap {
+            counterparty: None,
+            offered_nfts: vec![
+                nft(&contracts.collection_a, "1"),
+                nft(&contracts.collection_a, "2"),
+            ],
+            wanted_nfts: vec![nft(&contracts.collection_b, "1")],
+            wanted_funds: coins(500, DENOM),
+            expires: Expiration::Never {},
+        },
+        &[],
+    )
+    .unwrap();
+
+    // offered bundle is deposited in a single batch
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.collection_a.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNftBatch {
+            contract: contracts.swap_contract.to_string(),
+            token_ids: vec!["1".to_string(), "2".to_string()],
+            msg: to_json_binary(&Cw721HookMsg::Deposit { swap_id: 1 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // tokens not part of the swap are rejected
+    assert_eq!(
+        deposit(
+            &mut app,
+            &contracts,
+            &users.bob,
+            &contracts.collection_b,
+            "2"
+        ),
+        Some(ContractError::NotInSwap {
+            token_id: "2".to_string()
+        })
+    );
+
+    // bob deposits the wanted token and becomes counterparty
+    assert_eq!(
+        deposit(
+            &mut app,
+            &contracts,
+            &users.bob,
+            &contracts.collection_b,
+            "1"
+        ),
+        None
+    );
+    let swap: Swap = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.swap_contract.clone(),
+            &QueryMsg::Swap { swap_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(swap.counterparty, Some(users.bob.clone()));
+    assert_eq!(swap.deposited_nfts.len(), 3);
+
+    // wanted funds must match exactly
+    let err: ContractError = app
+        .execute_contract(
+            users.bob.clone(),
+            contracts.swap_contract.clone(),
+            &ExecuteMsg::DepositFunds { swap_id: 1 },
+            &coins(400, DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::WrongFunds {});
+
+    // last deposit executes the swap
+    app.execute_contract(
+        users.bob.clone(),
+        contracts.swap_contract.clone(),
+        &ExecuteMsg::DepositFunds { swap_id: 1 },
+        &coins(500, DENOM),
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts.collection_a, "1"), users.bob);
+    assert_eq!(owner_of(&app, &contracts.collection_a, "2"), users.bob);
+    assert_eq!(owner_of(&app, &contracts.collection_b, "1"), users.alice);
+    assert_eq!(balance(&app, &users.alice), coin(1_500, DENOM));
+    assert_eq!(balance(&app, &users.bob), coin(500, DENOM));
+}
+
+#[test]
+fn test_create_swap_on_receive() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // alice opens the swap by sending the offered tokens, naming the wanted token
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.collection_a.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNftBatch {
+            contract: contracts.swap_contract.to_string(),
+            token_ids: vec!["1".to_string(), "2".to_string()],
+            msg: to_json_binary(&Cw721HookMsg::Create {
+                counterparty: Some(users.bob.to_string()),
+                offered_nfts: vec![],
+                wanted_nfts: vec![nft(&contracts.collection_b, "1")],
+                wanted_funds: vec![],
+                expires: Expiration::Never {},
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let swap: Swap = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.swap_contract.clone(),
+            &QueryMsg::Swap { swap_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(swap.creator, users.alice);
+    assert_eq!(swap.offered_nfts.len(), 2);
+    assert_eq!(swap.deposited_nfts, swap.offered_nfts);
+
+    // bob's deposit executes the swap
+    assert_eq!(
+        deposit(
+            &mut app,
+            &contracts,
+            &users.bob,
+            &contracts.collection_b,
+            "1"
+        ),
+        None
+    );
+    assert_eq!(owner_of(&app, &contracts.collection_a, "1"), users.bob);
+    assert_eq!(owner_of(&app, &contracts.collection_a, "2"), users.bob);
+    assert_eq!(owner_of(&app, &contracts.collection_b, "1"), users.alice);
+}
+
+#[test]
+fn test_cancel_and_expiry() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+    let expires = Expiration::AtHeight(app.block_info().height + 10);
+
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.swap_contract.clone(),
+        &ExecuteMsg::CreateSwap {
+            counterparty: Some(users.bob.to_string()),
+            offered_nfts: vec![nft(&contracts.collection_a, "1")],
+            wanted_nfts: vec![nft(&contracts.collection_b, "1")],
+            wanted_funds: vec![],
+            expires,
+        },
+        &coins(100, DENOM),
+    )
+    .unwrap();
+    assert_eq!(
+        deposit(
+            &mut app,
+            &contracts,
+            &users.alice,
+            &contracts.collection_a,
+            "1"
+        ),
+        None
+    );
+    assert_eq!(balance(&app, &users.alice), coin(900, DENOM));
+
+    // only parties can cancel before expiry
+    let err: ContractError = app
+        .execute_contract(
+            users.stranger.clone(),
+            contracts.swap_contract.clone(),
+            &ExecuteMsg::Cancel { swap_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // no deposits after expiry
+    app.update_block(|block| block.height += 10);
+    assert_eq!(
+        deposit(
+            &mut app,
+            &contracts,
+            &users.bob,
+            &contracts.collection_b,
+            "1"
+        ),
+        Some(ContractError::SwapExpired {})
+    );
+
+    // anyone can refund an expired swap
+    app.execute_contract(
+        users.stranger.clone(),
+        contracts.swap_contract.clone(),
+        &ExecuteMsg::Cancel { swap_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts.collection_a, "1"), users.alice);
+    assert_eq!(balance(&app, &users.alice), coin(1_000, DENOM));
+}
+
+#[test]
+fn test_empty_bundle() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    let err: ContractError = app
+        .execute_contract(
+            users.alice.clone(),
+            contracts.swap_contract.clone(),
+            &ExecuteMsg::CreateSwap {
+                counterparty: None,
+                offered_nfts: vec![nft(&contracts.collection_a, "1")],
+                wanted_nfts: vec![],
+                wanted_funds: vec![],
+                expires: Expiration::Never {},
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmptyBundle {});
+}
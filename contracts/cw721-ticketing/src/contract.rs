@@ -0,0 +1,256 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
+};
+use cw721::execute::assert_creator;
+use cw721::extension::Cw721Extensions;
+use cw721::msg::TokensResponse;
+use cw721::traits::{Cw721Execute, Cw721Query};
+use cw721::{
+    DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
+    DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg,
+};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RedemptionStatusResponse, TicketExecuteMsg,
+    TicketQueryMsg,
+};
+use crate::state::{Redemption, TicketConfig, REDEMPTIONS, TICKET_CONFIG};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-ticketing";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+pub type Cw721TicketingContract<'a> = Cw721Extensions<
+    'a,
+    DefaultOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtension,
+    DefaultOptionalCollectionExtensionMsg,
+    TicketExecuteMsg,
+    TicketQueryMsg,
+    Empty,
+>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // redeemed tickets stay transferable, until the creator decides otherwise
+    TICKET_CONFIG.save(
+        deps.storage,
+        &TicketConfig {
+            checkers: vec![],
+            transferable_after_redeem: true,
+        },
+    )?;
+    Ok(Cw721TicketingContract::default().instantiate_with_version(
+        deps,
+        &env,
+        &info,
+        msg,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+    )?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    assert_transferable(deps.as_ref(), moved_tokens(&env, &msg))?;
+    let contract = Cw721TicketingContract::default();
+    match msg {
+        ExecuteMsg::UpdateExtension { msg } => execute_ticket(deps, env, info, msg),
+        ExecuteMsg::Burn { token_id } => {
+            let res = contract.burn_nft(deps.branch(), &env, &info, token_id.clone())?;
+            REDEMPTIONS.remove(deps.storage, &token_id);
+            Ok(res)
+        }
+        _ => Ok(contract.execute(deps, &env, &info, msg)?),
+    }
+}
+
+/// Returns the tokens moved to another owner by the message.
+fn moved_tokens<'a>(env: &Env, msg: &'a ExecuteMsg) -> Vec<&'a String> {
+    match msg {
+        ExecuteMsg::TransferNft { token_id, .. }
+        | ExecuteMsg::SendNft { token_id, .. }
+        | ExecuteMsg::SafeTransferNft { token_id, .. }
+        | ExecuteMsg::ProposeTransfer { token_id, .. }
+        | ExecuteMsg::AcceptTransfer { token_id }
+        | ExecuteMsg::AttachToken { token_id, .. } => vec![token_id],
+        ExecuteMsg::SendNftBatch { token_ids, .. } => token_ids.iter().collect(),
+        // detaching to the sender returns the ticket, tickets of other collections aren't tracked
+        ExecuteMsg::DetachToken {
+            collection,
+            token_id,
+            recipient: Some(_),
+            ..
+        } if collection.as_deref().map_or(true, |collection| {
+            collection == env.contract.address.as_str()
+        }) =>
+        {
+            vec![token_id]
+        }
+        _ => vec![],
+    }
+}
+
+fn assert_transferable(deps: Deps, token_ids: Vec<&String>) -> Result<(), ContractError> {
+    if token_ids.is_empty() || TICKET_CONFIG.load(deps.storage)?.transferable_after_redeem {
+        return Ok(());
+    }
+    match token_ids
+        .into_iter()
+        .find(|token_id| REDEMPTIONS.has(deps.storage, token_id))
+    {
+        Some(token_id) => Err(ContractError::TicketRedeemed {
+            token_id: token_id.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn execute_ticket(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: TicketExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        TicketExecuteMsg::Redeem { token_id } => execute_redeem(deps, env, info, token_id),
+        TicketExecuteMsg::AddChecker { address } => {
+            assert_creator(deps.storage, &info.sender)?;
+            let checker = deps.api.addr_validate(&address)?;
+            let mut config = TICKET_CONFIG.load(deps.storage)?;
+            if !config.checkers.contains(&checker) {
+                config.checkers.push(checker);
+            }
+            TICKET_CONFIG.save(deps.storage, &config)?;
+            Ok(Response::new()
+                .add_attribute("action", "add_checker")
+                .add_attribute("checker", address))
+        }
+        TicketExecuteMsg::RemoveChecker { address } => {
+            assert_creator(deps.storage, &info.sender)?;
+            let mut config = TICKET_CONFIG.load(deps.storage)?;
+            config
+                .checkers
+                .retain(|checker| checker.as_str() != address);
+            TICKET_CONFIG.save(deps.storage, &config)?;
+            Ok(Response::new()
+                .add_attribute("action", "remove_checker")
+                .add_attribute("checker", address))
+        }
+        TicketExecuteMsg::SetTransferableAfterRedeem { transferable } => {
+            assert_creator(deps.storage, &info.sender)?;
+            let mut config = TICKET_CONFIG.load(deps.storage)?;
+            config.transferable_after_redeem = transferable;
+            TICKET_CONFIG.save(deps.storage, &config)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_transferable_after_redeem")
+                .add_attribute("transferable", transferable.to_string()))
+        }
+    }
+}
+
+/// Marks the ticket as redeemed. The redemption stays with the ticket, also when transferred.
+pub fn execute_redeem(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = TICKET_CONFIG.load(deps.storage)?;
+    if !config.checkers.contains(&info.sender) {
+        return Err(ContractError::NotChecker {});
+    }
+    let nft_info = Cw721TicketingContract::default()
+        .config
+        .nft_info
+        .load(deps.storage, &token_id)?;
+    if REDEMPTIONS.has(deps.storage, &token_id) {
+        return Err(ContractError::AlreadyRedeemed { token_id });
+    }
+    let redemption = Redemption {
+        checker: info.sender,
+        redeemed_at: env.block.time,
+    };
+    REDEMPTIONS.save(deps.storage, &token_id, &redemption)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "redeem")
+        .add_attribute("token_id", token_id)
+        .add_attribute("owner", nft_info.owner.to_string())
+        .add_attribute("checker", redemption.checker.to_string())
+        .add_attribute("redeemed_at", redemption.redeemed_at.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Extension { msg } => Ok(query_ticket(deps, msg)?),
+        _ => Ok(Cw721TicketingContract::default().query(deps, &env, msg)?),
+    }
+}
+
+pub fn query_ticket(deps: Deps, msg: TicketQueryMsg) -> StdResult<Binary> {
+    match msg {
+        TicketQueryMsg::TicketConfig {} => to_json_binary(&TICKET_CONFIG.load(deps.storage)?),
+        TicketQueryMsg::RedemptionStatus { token_id } => {
+            to_json_binary(&query_redemption_status(deps, token_id)?)
+        }
+        TicketQueryMsg::RedeemedTokens { start_after, limit } => {
+            to_json_binary(&query_redeemed_tokens(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_redemption_status(
+    deps: Deps,
+    token_id: String,
+) -> StdResult<RedemptionStatusResponse> {
+    let redemption = REDEMPTIONS.may_load(deps.storage, &token_id)?;
+    Ok(RedemptionStatusResponse {
+        token_id,
+        redemption,
+    })
+}
+
+pub fn query_redeemed_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let tokens = REDEMPTIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Cw721TicketingContract::default().migrate(
+        deps,
+        env,
+        msg,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+    )?)
+}
@@ -0,0 +1,21 @@
+use cosmwasm_std::StdError;
+use cw721::error::Cw721ContractError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Base(#[from] Cw721ContractError),
+
+    #[error("Only checkers can redeem tickets")]
+    NotChecker {},
+
+    #[error("Ticket {token_id} already redeemed")]
+    AlreadyRedeemed { token_id: String },
+
+    #[error("Ticket {token_id} is redeemed and can't be transferred")]
+    TicketRedeemed { token_id: String },
+}
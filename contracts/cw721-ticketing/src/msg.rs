@@ -0,0 +1,57 @@
+use cosmwasm_schema::cw_serde;
+use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg};
+use cw721::traits::Cw721CustomMsg;
+use cw721::{
+    DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
+    DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg,
+};
+
+use crate::state::Redemption;
+
+/// Ticketing specific messages, sent via `ExecuteMsg::UpdateExtension`.
+#[cw_serde]
+pub enum TicketExecuteMsg {
+    /// Marks the ticket as redeemed, e.g. on check-in at the event. Only checkers can call this.
+    Redeem { token_id: String },
+    /// Only creator can call this.
+    AddChecker { address: String },
+    /// Only creator can call this.
+    RemoveChecker { address: String },
+    /// Sets whether redeemed tickets can still be transferred. Only creator can call this.
+    SetTransferableAfterRedeem { transferable: bool },
+}
+
+impl Cw721CustomMsg for TicketExecuteMsg {}
+
+/// Ticketing specific queries, sent via `QueryMsg::Extension`.
+#[cw_serde]
+pub enum TicketQueryMsg {
+    /// Returns `TicketConfig`, with checkers and transfer policy.
+    TicketConfig {},
+    /// Returns `RedemptionStatusResponse`.
+    RedemptionStatus { token_id: String },
+    /// Returns `TokensResponse` with all redeemed tickets, ordered by token id.
+    RedeemedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+impl Cw721CustomMsg for TicketQueryMsg {}
+
+#[cw_serde]
+pub struct RedemptionStatusResponse {
+    pub token_id: String,
+    /// Not set, if the ticket has not been redeemed yet.
+    pub redemption: Option<Redemption>,
+}
+
+pub type InstantiateMsg = Cw721InstantiateMsg<DefaultOptionalCollectionExtensionMsg>;
+pub type ExecuteMsg = Cw721ExecuteMsg<
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtensionMsg,
+    TicketExecuteMsg,
+>;
+pub type QueryMsg =
+    Cw721QueryMsg<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, TicketQueryMsg>;
+pub type MigrateMsg = Cw721MigrateMsg;
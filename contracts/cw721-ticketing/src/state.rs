@@ -0,0 +1,22 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct TicketConfig {
+    /// Can redeem (check in) tickets.
+    pub checkers: Vec<Addr>,
+    /// If false, redeemed tickets can no longer be transferred. Otherwise they can, but stay
+    /// flagged as redeemed.
+    pub transferable_after_redeem: bool,
+}
+
+#[cw_serde]
+pub struct Redemption {
+    pub checker: Addr,
+    pub redeemed_at: Timestamp,
+}
+
+pub const TICKET_CONFIG: Item<TicketConfig> = Item::new("ticket_config");
+/// Stored as (token id, redemption). Kept on transfer, removed on burn.
+pub const REDEMPTIONS: Map<&str, Redemption> = Map::new("redemptions");
@@ -0,0 +1,288 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::Addr;
+use cw721::error::Cw721ContractError;
+use cw721::msg::{OwnerOfResponse, TokensResponse};
+use cw721_ticketing::msg::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, RedemptionStatusResponse, TicketExecuteMsg,
+    TicketQueryMsg,
+};
+use cw721_ticketing::ContractError;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+struct Users {
+    creator: Addr,
+    checker: Addr,
+    alice: Addr,
+    bob: Addr,
+}
+
+fn setup_users() -> Users {
+    let api = MockApi::default();
+    Users {
+        creator: api.addr_make("creator"),
+        checker: api.addr_make("checker"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+    }
+}
+
+/// Setup the ticketing contract with tickets "1" and "2" owned by alice, and checker as checker
+fn setup_contract(app: &mut App, users: &Users) -> Addr {
+    use cw721_ticketing::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let tickets = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {
+                name: "tickets".to_string(),
+                symbol: "TICKET".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "tickets".to_string(),
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            users.creator.clone(),
+            tickets.clone(),
+            &ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: users.alice.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        users.creator.clone(),
+        tickets.clone(),
+        &ExecuteMsg::UpdateExtension {
+            msg: TicketExecuteMsg::AddChecker {
+                address: users.checker.to_string(),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    tickets
+}
+
+fn redeem(app: &mut App, tickets: &Addr, sender: &Addr, token_id: &str) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        tickets.clone(),
+        &ExecuteMsg::UpdateExtension {
+            msg: TicketExecuteMsg::Redeem {
+                token_id: token_id.to_string(),
+            },
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn transfer(
+    app: &mut App,
+    tickets: &Addr,
+    sender: &Addr,
+    recipient: &Addr,
+    token_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        tickets.clone(),
+        &ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn redeemed_tokens(app: &App, tickets: &Addr) -> Vec<String> {
+    let res: TokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            tickets.clone(),
+            &QueryMsg::Extension {
+                msg: TicketQueryMsg::RedeemedTokens {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    res.tokens
+}
+
+#[test]
+fn test_redeem_ticket() {
+    let mut app = App::default();
+    let users = setup_users();
+    let tickets = setup_contract(&mut app, &users);
+
+    // only checkers redeem tickets, once
+    assert_eq!(
+        redeem(&mut app, &tickets, &users.alice, "1"),
+        Some(ContractError::NotChecker {})
+    );
+    assert_eq!(redeem(&mut app, &tickets, &users.checker, "1"), None);
+    assert_eq!(
+        redeem(&mut app, &tickets, &users.checker, "1"),
+        Some(ContractError::AlreadyRedeemed {
+            token_id: "1".to_string()
+        })
+    );
+
+    // redeemed tickets are transferable by default, but stay flagged
+    assert_eq!(
+        transfer(&mut app, &tickets, &users.alice, &users.bob, "1"),
+        None
+    );
+    let res: RedemptionStatusResponse = app
+        .wrap()
+        .query_wasm_smart(
+            tickets.clone(),
+            &QueryMsg::Extension {
+                msg: TicketQueryMsg::RedemptionStatus {
+                    token_id: "1".to_string(),
+                },
+            },
+        )
+        .unwrap();
+    let redemption = res.redemption.unwrap();
+    assert_eq!(redemption.checker, users.checker);
+    assert_eq!(redemption.redeemed_at, app.block_info().time);
+    assert_eq!(redeemed_tokens(&app, &tickets), vec!["1".to_string()]);
+}
+
+#[test]
+fn test_redeemed_ticket_non_transferable() {
+    let mut app = App::default();
+    let users = setup_users();
+    let tickets = setup_contract(&mut app, &users);
+
+    // only creator sets the transfer policy
+    let set_transferable = |app: &mut App, sender: &Addr| {
+        app.execute_contract(
+            sender.clone(),
+            tickets.clone(),
+            &ExecuteMsg::UpdateExtension {
+                msg: TicketExecuteMsg::SetTransferableAfterRedeem {
+                    transferable: false,
+                },
+            },
+            &[],
+        )
+        .err()
+        .map(|err| err.downcast::<ContractError>().unwrap())
+    };
+    assert_eq!(
+        set_transferable(&mut app, &users.checker),
+        Some(ContractError::Base(Cw721ContractError::NotCreator {}))
+    );
+    assert_eq!(set_transferable(&mut app, &users.creator), None);
+
+    assert_eq!(redeem(&mut app, &tickets, &users.checker, "1"), None);
+    assert_eq!(
+        transfer(&mut app, &tickets, &users.alice, &users.bob, "1"),
+        Some(ContractError::TicketRedeemed {
+            token_id: "1".to_string()
+        })
+    );
+    assert_eq!(
+        transfer(&mut app, &tickets, &users.alice, &users.bob, "2"),
+        None
+    );
+
+    // burned tickets are no longer listed
+    app.execute_contract(
+        users.alice.clone(),
+        tickets.clone(),
+        &ExecuteMsg::Burn {
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(redeemed_tokens(&app, &tickets).is_empty());
+}
+
+#[test]
+fn test_redeemed_ticket_non_detachable() {
+    let mut app = App::default();
+    let users = setup_users();
+    let tickets = setup_contract(&mut app, &users);
+    app.execute_contract(
+        users.creator.clone(),
+        tickets.clone(),
+        &ExecuteMsg::UpdateExtension {
+            msg: TicketExecuteMsg::SetTransferableAfterRedeem {
+                transferable: false,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ticket is redeemed while attached to another ticket
+    app.execute_contract(
+        users.alice.clone(),
+        tickets.clone(),
+        &ExecuteMsg::AttachToken {
+            token_id: "2".to_string(),
+            parent_token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(redeem(&mut app, &tickets, &users.checker, "2"), None);
+
+    // it can't be detached to another recipient, only back to the holder
+    let detach = |app: &mut App, recipient: Option<&Addr>| {
+        app.execute_contract(
+            users.alice.clone(),
+            tickets.clone(),
+            &ExecuteMsg::DetachToken {
+                parent_token_id: "1".to_string(),
+                collection: None,
+                token_id: "2".to_string(),
+                recipient: recipient.map(ToString::to_string),
+            },
+            &[],
+        )
+        .err()
+        .map(|err| err.downcast::<ContractError>().unwrap())
+    };
+    assert_eq!(
+        detach(&mut app, Some(&users.bob)),
+        Some(ContractError::TicketRedeemed {
+            token_id: "2".to_string()
+        })
+    );
+    assert_eq!(detach(&mut app, None), None);
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            tickets.clone(),
+            &QueryMsg::OwnerOf {
+                token_id: "2".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.owner, users.alice.to_string());
+}
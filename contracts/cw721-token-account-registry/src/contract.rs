@@ -0,0 +1,153 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    instantiate2_address, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw721_token_account::msg::InstantiateMsg as AccountInstantiateMsg;
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    Account, AccountResponse, AccountsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{Config, ACCOUNTS, CONFIG};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-token-account-registry";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 1000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            account_code_id: msg.account_code_id,
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("account_code_id", msg.account_code_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateAccount {
+            collection,
+            token_id,
+        } => execute_create_account(deps, env, info, collection, token_id),
+    }
+}
+
+/// Salt of the token's account, unique per (collection, token_id).
+fn account_salt(collection: &Addr, token_id: &str) -> Binary {
+    let hash = Sha256::new()
+        .chain_update(collection.as_bytes())
+        .chain_update(b"/")
+        .chain_update(token_id.as_bytes())
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+/// Computes the address of the token's account, as instantiated by this registry.
+fn account_address(deps: Deps, env: &Env, collection: &Addr, token_id: &str) -> StdResult<Addr> {
+    let config = CONFIG.load(deps.storage)?;
+    let checksum = deps
+        .querier
+        .query_wasm_code_info(config.account_code_id)?
+        .checksum;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let address = instantiate2_address(
+        checksum.as_slice(),
+        &creator,
+        &account_salt(collection, token_id),
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+    deps.api.addr_humanize(&address)
+}
+
+pub fn execute_create_account(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    if ACCOUNTS.has(deps.storage, (&collection, &token_id)) {
+        return Err(ContractError::AccountExists { token_id });
+    }
+    let address = account_address(deps.as_ref(), &env, &collection, &token_id)?;
+    ACCOUNTS.save(deps.storage, (&collection, &token_id), &address)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let instantiate_msg = WasmMsg::Instantiate2 {
+        admin: None,
+        code_id: config.account_code_id,
+        label: format!("token account {}/{}", collection, token_id),
+        msg: to_json_binary(&AccountInstantiateMsg {
+            collection: collection.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: info.funds,
+        salt: account_salt(&collection, &token_id),
+    };
+
+    Ok(Response::new()
+        .add_message(instantiate_msg)
+        .add_attribute("action", "create_account")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("account", address.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Account {
+            collection,
+            token_id,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let created = ACCOUNTS.has(deps.storage, (&collection, &token_id));
+            to_json_binary(&AccountResponse {
+                address: account_address(deps, &env, &collection, &token_id)?,
+                created,
+            })
+        }
+        QueryMsg::Accounts {
+            collection,
+            start_after,
+            limit,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+            let accounts = ACCOUNTS
+                .prefix(&collection)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(token_id, address)| Account { token_id, address }))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&AccountsResponse { accounts })
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+
+use crate::state::Config;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Code id of the `cw721-token-account` contract.
+    pub account_code_id: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Instantiates the account of the given token at its deterministic address. Can be performed
+    /// by anyone, but only once per token.
+    CreateAccount {
+        collection: String,
+        token_id: String,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+
+    /// Returns the deterministic address of the token's account, even if not created yet.
+    #[returns(AccountResponse)]
+    Account {
+        collection: String,
+        token_id: String,
+    },
+
+    /// Lists created accounts of a collection, ordered by token id.
+    #[returns(AccountsResponse)]
+    Accounts {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct AccountResponse {
+    pub address: Addr,
+    pub created: bool,
+}
+
+#[cw_serde]
+pub struct Account {
+    pub token_id: String,
+    pub address: Addr,
+}
+
+#[cw_serde]
+pub struct AccountsResponse {
+    pub accounts: Vec<Account>,
+}
@@ -0,0 +1,13 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    /// Code id of the `cw721-token-account` contract.
+    pub account_code_id: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Created accounts by (collection, token_id).
+pub const ACCOUNTS: Map<(&Addr, &str), Addr> = Map::new("accounts");
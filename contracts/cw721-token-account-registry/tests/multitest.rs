@@ -0,0 +1,174 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::Addr;
+use cw721_token_account::state::TokenInfo;
+use cw721_token_account_registry::msg::{
+    AccountResponse, AccountsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use cw721_token_account_registry::ContractError;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+struct Contracts {
+    nft_contract: Addr,
+    registry: Addr,
+}
+
+/// Setup a cw721-base collection with tokens "1" and "2", and the registry
+fn setup_contracts(app: &mut App, creator: &Addr) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_token_account_registry::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let account_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_token_account::contract::execute,
+        cw721_token_account::contract::instantiate,
+        cw721_token_account::contract::query,
+    )));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "nft".to_string(),
+                symbol: "NFT".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "nft".to_string(),
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            creator.clone(),
+            nft_contract.clone(),
+            &base_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: creator.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let registry = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &InstantiateMsg { account_code_id },
+            &[],
+            "registry".to_string(),
+            None,
+        )
+        .unwrap();
+
+    Contracts {
+        nft_contract,
+        registry,
+    }
+}
+
+fn query_account(app: &App, contracts: &Contracts, token_id: &str) -> AccountResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contracts.registry.clone(),
+            &QueryMsg::Account {
+                collection: contracts.nft_contract.to_string(),
+                token_id: token_id.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+fn create_account(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    token_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.registry.clone(),
+        &ExecuteMsg::CreateAccount {
+            collection: contracts.nft_contract.to_string(),
+            token_id: token_id.to_string(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+#[test]
+fn test_create_account() {
+    let mut app = App::default();
+    let creator = MockApi::default().addr_make("creator");
+    let anyone = MockApi::default().addr_make("anyone");
+    let contracts = setup_contracts(&mut app, &creator);
+
+    // address is known before the account is created
+    let account = query_account(&app, &contracts, "1");
+    assert!(!account.created);
+    assert_ne!(
+        account.address,
+        query_account(&app, &contracts, "2").address
+    );
+
+    // anyone can create the account, at the predicted address
+    assert_eq!(create_account(&mut app, &contracts, &anyone, "1"), None);
+    assert_eq!(
+        query_account(&app, &contracts, "1"),
+        AccountResponse {
+            address: account.address.clone(),
+            created: true,
+        }
+    );
+    let token: TokenInfo = app
+        .wrap()
+        .query_wasm_smart(
+            account.address,
+            &cw721_token_account::msg::QueryMsg::Token {},
+        )
+        .unwrap();
+    assert_eq!(
+        token,
+        TokenInfo {
+            collection: contracts.nft_contract.clone(),
+            token_id: "1".to_string(),
+        }
+    );
+
+    // only one account per token
+    assert_eq!(
+        create_account(&mut app, &contracts, &anyone, "1"),
+        Some(ContractError::AccountExists {
+            token_id: "1".to_string()
+        })
+    );
+
+    assert_eq!(create_account(&mut app, &contracts, &anyone, "2"), None);
+    let res: AccountsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.registry.clone(),
+            &QueryMsg::Accounts {
+                collection: contracts.nft_contract.to_string(),
+                start_after: Some("1".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.accounts.len(), 1);
+    assert_eq!(res.accounts[0].token_id, "2");
+}
@@ -0,0 +1,109 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::traits::Cw721Calls;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, OwnerResponse, QueryMsg};
+use crate::state::{TokenInfo, TOKEN};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-token-account";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let token = TokenInfo {
+        collection: deps.api.addr_validate(&msg.collection)?,
+        token_id: msg.token_id,
+    };
+    TOKEN.save(deps.storage, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("collection", token.collection.to_string())
+        .add_attribute("token_id", token.token_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Execute { msgs } => execute_execute(deps, info, msgs),
+        ExecuteMsg::ReceiveNft(receive_msg) => {
+            execute_receive_nft(deps, info, vec![receive_msg.token_id])
+        }
+        ExecuteMsg::ReceiveNftBatch(receive_msg) => {
+            execute_receive_nft(deps, info, receive_msg.token_ids)
+        }
+        ExecuteMsg::Receive(receive_msg) => Ok(Response::new()
+            .add_attribute("action", "receive_cw20")
+            .add_attribute("token", info.sender.to_string())
+            .add_attribute("amount", receive_msg.amount)),
+    }
+}
+
+/// Returns the current owner of the token, checked live on the collection.
+fn query_token_owner(deps: Deps) -> StdResult<String> {
+    let token = TOKEN.load(deps.storage)?;
+    let owner = DefaultCw721Helper::new(token.collection)
+        .owner_of(&deps.querier, token.token_id, false)?
+        .owner;
+    Ok(owner)
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    if query_token_owner(deps.as_ref())? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender.to_string()))
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    // owning its own token would lock the account forever
+    let token = TOKEN.load(deps.storage)?;
+    if token.collection == info.sender && token_ids.contains(&token.token_id) {
+        return Err(ContractError::OwnershipCycle {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_nft")
+        .add_attribute("collection", info.sender.to_string())
+        .add_attribute("token_ids", token_ids.join(",")))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Token {} => to_json_binary(&TOKEN.load(deps.storage)?),
+        QueryMsg::Owner {} => to_json_binary(&OwnerResponse {
+            owner: query_token_owner(deps)?,
+        }),
+    }
+}
@@ -0,0 +1,14 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Only the owner of the token can execute messages")]
+    Unauthorized {},
+
+    #[error("An account can't own its own token")]
+    OwnershipCycle {},
+}
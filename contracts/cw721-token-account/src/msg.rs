@@ -0,0 +1,43 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::CosmosMsg;
+use cw20::Cw20ReceiveMsg;
+use cw721::receiver::{Cw721BatchReceiveMsg, Cw721ReceiveMsg};
+
+use crate::state::TokenInfo;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The collection of the token controlling this account.
+    pub collection: String,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Executes the given messages on behalf of this account. Can only be performed by the current
+    /// owner of the token.
+    Execute { msgs: Vec<CosmosMsg> },
+    /// Accepts NFTs sent via `SendNft`. The msg is ignored.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Accepts NFTs sent via `SendNftBatch`. The msg is ignored.
+    ReceiveNftBatch(Cw721BatchReceiveMsg),
+    /// Accepts CW20 tokens sent via `Send`. The msg is ignored.
+    Receive(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the token controlling this account.
+    #[returns(TokenInfo)]
+    Token {},
+
+    /// Returns the current owner of the token, who controls this account.
+    #[returns(OwnerResponse)]
+    Owner {},
+}
+
+#[cw_serde]
+pub struct OwnerResponse {
+    pub owner: String,
+}
@@ -0,0 +1,12 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+/// The token controlling this account.
+#[cw_serde]
+pub struct TokenInfo {
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+pub const TOKEN: Item<TokenInfo> = Item::new("token");
@@ -0,0 +1,234 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, BankMsg, Empty, WasmMsg};
+use cw721::msg::OwnerOfResponse;
+use cw721_token_account::msg::{ExecuteMsg, InstantiateMsg, OwnerResponse, QueryMsg};
+use cw721_token_account::ContractError;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+
+const DENOM: &str = "uatom";
+
+struct Contracts {
+    nft_contract: Addr,
+    account: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    alice: Addr,
+    bob: Addr,
+}
+
+fn setup_app() -> (App, Users) {
+    let api = MockApi::default();
+    let users = Users {
+        creator: api.addr_make("creator"),
+        alice: api.addr_make("alice"),
+        bob: api.addr_make("bob"),
+    };
+    let app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &users.alice, coins(1_000, DENOM))
+            .unwrap();
+    });
+    (app, users)
+}
+
+/// Setup a cw721-base collection with character "hero" and item "sword" owned by alice, and the
+/// account of "hero"
+fn setup_contracts(app: &mut App, users: &Users) -> Contracts {
+    use cw721_base::msg as base_msg;
+    use cw721_token_account::contract::*;
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    )));
+
+    let nft_contract = app
+        .instantiate_contract(
+            nft_code_id,
+            users.creator.clone(),
+            &base_msg::InstantiateMsg {
+                name: "game".to_string(),
+                symbol: "GAME".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                ics721_bridge: None,
+            },
+            &[],
+            "game".to_string(),
+            None,
+        )
+        .unwrap();
+    for token_id in ["hero", "sword"] {
+        app.execute_contract(
+            users.creator.clone(),
+            nft_contract.clone(),
+            &base_msg::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: users.alice.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let account = app
+        .instantiate_contract(
+            code_id,
+            users.creator.clone(),
+            &InstantiateMsg {
+                collection: nft_contract.to_string(),
+                token_id: "hero".to_string(),
+            },
+            &[],
+            "hero account".to_string(),
+            None,
+        )
+        .unwrap();
+
+    Contracts {
+        nft_contract,
+        account,
+    }
+}
+
+fn send_nft(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    token_id: &str,
+) -> Option<ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::SendNft {
+            contract: contracts.account.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&Empty {}).unwrap(),
+        },
+        &[],
+    )
+    .err()
+    .map(|err| err.downcast().unwrap())
+}
+
+fn owner_of(app: &App, contracts: &Contracts, token_id: &str) -> String {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.nft_contract.clone(),
+            &cw721_base::msg::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn test_token_account() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    // account holds NFTs and funds
+    assert_eq!(send_nft(&mut app, &contracts, &users.alice, "sword"), None);
+    assert_eq!(owner_of(&app, &contracts, "sword"), contracts.account);
+    app.send_tokens(
+        users.alice.clone(),
+        contracts.account.clone(),
+        &coins(100, DENOM),
+    )
+    .unwrap();
+
+    // token owner controls the account
+    let res: OwnerResponse = app
+        .wrap()
+        .query_wasm_smart(contracts.account.clone(), &QueryMsg::Owner {})
+        .unwrap();
+    assert_eq!(res.owner, users.alice);
+    let err: ContractError = app
+        .execute_contract(
+            users.bob.clone(),
+            contracts.account.clone(),
+            &ExecuteMsg::Execute { msgs: vec![] },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // account and its contents move along with the token
+    app.execute_contract(
+        users.alice.clone(),
+        contracts.nft_contract.clone(),
+        &cw721_base::msg::ExecuteMsg::TransferNft {
+            recipient: users.bob.to_string(),
+            token_id: "hero".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            users.alice.clone(),
+            contracts.account.clone(),
+            &ExecuteMsg::Execute { msgs: vec![] },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        users.bob.clone(),
+        contracts.account.clone(),
+        &ExecuteMsg::Execute {
+            msgs: vec![
+                WasmMsg::Execute {
+                    contract_addr: contracts.nft_contract.to_string(),
+                    msg: to_json_binary(&cw721_base::msg::ExecuteMsg::TransferNft {
+                        recipient: users.bob.to_string(),
+                        token_id: "sword".to_string(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: users.bob.to_string(),
+                    amount: coins(100, DENOM),
+                }
+                .into(),
+            ],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&app, &contracts, "sword"), users.bob);
+    assert_eq!(
+        app.wrap().query_balance(&users.bob, DENOM).unwrap(),
+        coin(100, DENOM)
+    );
+}
+
+#[test]
+fn test_ownership_cycle() {
+    let (mut app, users) = setup_app();
+    let contracts = setup_contracts(&mut app, &users);
+
+    assert_eq!(
+        send_nft(&mut app, &contracts, &users.alice, "hero"),
+        Some(ContractError::OwnershipCycle {})
+    );
+    assert_eq!(owner_of(&app, &contracts, "hero"), users.alice);
+}
@@ -43,12 +43,102 @@ pub enum Cw721ContractError {
     #[error("Caller is neither minter nor collection creator")]
     NotMinterOrCreator {},
 
+    #[error("Metadata editor {editor} is not eligible to update token {token_id}")]
+    MetadataEditorOutOfScope { editor: String, token_id: String },
+
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
 
+    #[error("No pending transfer for token {token_id}")]
+    NoPendingTransfer { token_id: String },
+
+    #[error("Pending transfer for token {token_id} has expired")]
+    PendingTransferExpired { token_id: String },
+
+    #[error("Caller is not recipient of pending transfer")]
+    NotPendingTransferRecipient {},
+
+    #[error("Token {token_id} is locked")]
+    TokenLocked { token_id: String },
+
+    #[error("Token {token_id} is not locked")]
+    TokenNotLocked { token_id: String },
+
+    #[error("Permit signer is not the token owner")]
+    PermitSignerNotOwner {},
+
+    #[error("Invalid permit nonce. Expected {expected}, got {actual}")]
+    InvalidPermitNonce { expected: u64, actual: u64 },
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
+
+    #[error("No voucher signer set")]
+    NoVoucherSigner {},
+
+    #[error("Voucher signer does not belong to minter")]
+    VoucherSignerNotMinter {},
+
+    #[error("Voucher has expired")]
+    VoucherExpired {},
+
+    #[error("Voucher nonce {nonce} already used")]
+    VoucherNonceUsed { nonce: u64 },
+
+    #[error("Invalid voucher signature")]
+    InvalidVoucherSignature {},
+
+    #[error("Voucher price not paid: {price}")]
+    VoucherPriceNotPaid { price: String },
+
+    #[error("No token ids given")]
+    NoTokenIds {},
+
+    #[error("Owner snapshots must be enabled before the first mint")]
+    OwnerSnapshotsRequireNoTokens {},
+
+    #[error("The ICS-721 bridge is the only minter of a voucher collection")]
+    Ics721BridgeIsMinter {},
+
+    #[error("Only the ICS-721 bridge can burn vouchers")]
+    NotIcs721Bridge {},
+
+    #[error("A token can't be attached to itself or to one of its attached tokens")]
+    AttachmentCycle {},
+
+    #[error("Token {token_id} is not attached to the given parent")]
+    NotAttached { token_id: String },
+
+    #[error("Token {token_id} was not sent to this contract")]
+    TokenNotReceived { token_id: String },
+
+    #[error("Only the root owner can detach tokens")]
+    NotRootOwner {},
+
+    #[error("Token {token_id} has attached tokens, which must be detached first")]
+    TokenHasChildren { token_id: String },
+
+    #[error("Edition master {master_id} already registered")]
+    EditionMasterExists { master_id: String },
+
+    #[error("Edition master {master_id} not found")]
+    EditionMasterNotFound { master_id: String },
+
+    #[error("Token id {token_id} collides with the editions of {master_id}")]
+    EditionIdClaimed { token_id: String, master_id: String },
+
+    #[error("All editions of {master_id} have been minted")]
+    EditionsSoldOut { master_id: String },
+
+    #[error("Edition size must not be zero")]
+    InvalidEditionSize {},
+
+    #[error("Template {template_id} not found")]
+    TemplateNotFound { template_id: String },
+
     #[error("No withdraw address set")]
     NoWithdrawAddress {},
 
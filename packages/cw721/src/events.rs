@@ -0,0 +1,229 @@
+//! Typed events emitted by cw721 actions.
+//!
+//! Each event is added to the response as `cosmwasm_std::Event` with type `cw721-<name>`. On chain,
+//! wasmd prefixes custom events, so indexers find them under a stable `wasm-cw721-<name>` type.
+//! Events can be parsed back into their structs, e.g. for tests and off-chain consumers.
+use cosmwasm_std::{from_json, to_json_string, Event, Response, StdError, StdResult};
+use cw_utils::Expiration;
+
+/// Common behaviour of all typed cw721 events.
+pub trait Cw721Event: Sized + Into<Event> {
+    /// Event type, as set in the response. On chain it is prefixed with `wasm-`.
+    const TYPE: &'static str;
+
+    /// Parses an event of this type, with or without `wasm-` prefix.
+    fn from_event(event: &Event) -> StdResult<Self>;
+
+    /// Returns true if the event is of this type, with or without `wasm-` prefix.
+    fn matches(event: &Event) -> bool {
+        event.ty == Self::TYPE || event.ty.strip_prefix("wasm-") == Some(Self::TYPE)
+    }
+
+    /// Parses all events of this type from the given events, e.g. events of a tx.
+    fn from_events(events: &[Event]) -> StdResult<Vec<Self>> {
+        events
+            .iter()
+            .filter(|event| Self::matches(event))
+            .map(Self::from_event)
+            .collect()
+    }
+
+    /// Parses all events of this type from the given response.
+    fn from_response<T>(response: &Response<T>) -> StdResult<Vec<Self>> {
+        Self::from_events(&response.events)
+    }
+}
+
+/// Emitted when a token is minted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintEvent {
+    pub token_id: String,
+    pub minter: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+}
+
+impl From<MintEvent> for Event {
+    fn from(event: MintEvent) -> Self {
+        let mut res = Event::new(MintEvent::TYPE)
+            .add_attribute("token_id", event.token_id)
+            .add_attribute("minter", event.minter)
+            .add_attribute("owner", event.owner);
+        if let Some(token_uri) = event.token_uri {
+            res = res.add_attribute("token_uri", token_uri);
+        }
+        res
+    }
+}
+
+impl Cw721Event for MintEvent {
+    const TYPE: &'static str = "cw721-mint";
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        assert_type::<Self>(event)?;
+        Ok(MintEvent {
+            token_id: attribute(event, "token_id")?,
+            minter: attribute(event, "minter")?,
+            owner: attribute(event, "owner")?,
+            token_uri: optional_attribute(event, "token_uri"),
+        })
+    }
+}
+
+/// Emitted when ownership of a token moves, e.g. on transfer, send or an accepted two-step transfer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferEvent {
+    pub token_id: String,
+    pub sender: String,
+    pub previous_owner: String,
+    pub recipient: String,
+}
+
+impl From<TransferEvent> for Event {
+    fn from(event: TransferEvent) -> Self {
+        Event::new(TransferEvent::TYPE)
+            .add_attribute("token_id", event.token_id)
+            .add_attribute("sender", event.sender)
+            .add_attribute("previous_owner", event.previous_owner)
+            .add_attribute("recipient", event.recipient)
+    }
+}
+
+impl Cw721Event for TransferEvent {
+    const TYPE: &'static str = "cw721-transfer";
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        assert_type::<Self>(event)?;
+        Ok(TransferEvent {
+            token_id: attribute(event, "token_id")?,
+            sender: attribute(event, "sender")?,
+            previous_owner: attribute(event, "previous_owner")?,
+            recipient: attribute(event, "recipient")?,
+        })
+    }
+}
+
+/// Emitted when a token is burned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BurnEvent {
+    pub token_id: String,
+    pub sender: String,
+    pub owner: String,
+}
+
+impl From<BurnEvent> for Event {
+    fn from(event: BurnEvent) -> Self {
+        Event::new(BurnEvent::TYPE)
+            .add_attribute("token_id", event.token_id)
+            .add_attribute("sender", event.sender)
+            .add_attribute("owner", event.owner)
+    }
+}
+
+impl Cw721Event for BurnEvent {
+    const TYPE: &'static str = "cw721-burn";
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        assert_type::<Self>(event)?;
+        Ok(BurnEvent {
+            token_id: attribute(event, "token_id")?,
+            sender: attribute(event, "sender")?,
+            owner: attribute(event, "owner")?,
+        })
+    }
+}
+
+/// Emitted when a spender is approved for a token, e.g. via approve or permit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApproveEvent {
+    pub token_id: String,
+    pub sender: String,
+    pub owner: String,
+    pub spender: String,
+    pub expires: Expiration,
+}
+
+impl From<ApproveEvent> for Event {
+    fn from(event: ApproveEvent) -> Self {
+        // expiration is JSON encoded, so it can be parsed back
+        let expires = to_json_string(&event.expires).unwrap_or_default();
+        Event::new(ApproveEvent::TYPE)
+            .add_attribute("token_id", event.token_id)
+            .add_attribute("sender", event.sender)
+            .add_attribute("owner", event.owner)
+            .add_attribute("spender", event.spender)
+            .add_attribute("expires", expires)
+    }
+}
+
+impl Cw721Event for ApproveEvent {
+    const TYPE: &'static str = "cw721-approve";
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        assert_type::<Self>(event)?;
+        Ok(ApproveEvent {
+            token_id: attribute(event, "token_id")?,
+            sender: attribute(event, "sender")?,
+            owner: attribute(event, "owner")?,
+            spender: attribute(event, "spender")?,
+            expires: from_json(attribute(event, "expires")?)?,
+        })
+    }
+}
+
+/// Emitted when token uri or onchain metadata of a token is updated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetadataUpdateEvent {
+    pub token_id: String,
+    /// Creator or metadata editor, who updated the metadata. None in case of an internal update.
+    pub editor: Option<String>,
+}
+
+impl From<MetadataUpdateEvent> for Event {
+    fn from(event: MetadataUpdateEvent) -> Self {
+        let mut res =
+            Event::new(MetadataUpdateEvent::TYPE).add_attribute("token_id", event.token_id);
+        if let Some(editor) = event.editor {
+            res = res.add_attribute("editor", editor);
+        }
+        res
+    }
+}
+
+impl Cw721Event for MetadataUpdateEvent {
+    const TYPE: &'static str = "cw721-metadata-update";
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        assert_type::<Self>(event)?;
+        Ok(MetadataUpdateEvent {
+            token_id: attribute(event, "token_id")?,
+            editor: optional_attribute(event, "editor"),
+        })
+    }
+}
+
+fn assert_type<T: Cw721Event>(event: &Event) -> StdResult<()> {
+    if T::matches(event) {
+        Ok(())
+    } else {
+        Err(StdError::generic_err(format!(
+            "Expected event type {}, got {}",
+            T::TYPE,
+            event.ty
+        )))
+    }
+}
+
+fn optional_attribute(event: &Event, key: &str) -> Option<String> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+}
+
+fn attribute(event: &Event, key: &str) -> StdResult<String> {
+    optional_attribute(event, key).ok_or_else(|| {
+        StdError::generic_err(format!("Missing attribute {} in event {}", key, event.ty))
+    })
+}
@@ -1,23 +1,34 @@
 use cosmwasm_std::{
-    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult, Storage,
+    from_json, has_coins, to_json_binary, to_json_vec, Addr, Api, BankMsg, Binary, Coin, CustomMsg,
+    Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    WasmMsg,
 };
 use cw_ownable::{none_or, Action, Ownership, OwnershipError};
-use cw_storage_plus::Item;
-use cw_utils::Expiration;
+use cw_storage_plus::{Bound, Item};
+use cw_utils::{Duration, Expiration};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use url::Url;
 
 use crate::{
     error::Cw721ContractError,
+    events::{ApproveEvent, BurnEvent, MetadataUpdateEvent, MintEvent, TransferEvent},
     extension::{
         Cw721BaseExtensions, Cw721EmptyExtensions, Cw721Extensions, Cw721OnchainExtensions,
     },
-    helpers::value_or_empty,
-    msg::{CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg, NftInfoMsg},
-    query::query_collection_info_and_extension,
-    receiver::Cw721ReceiveMsg,
-    state::{CollectionInfo, Cw721Config, NftInfo, CREATOR, MINTER},
+    helpers::{value_or_empty, DefaultCw721Helper},
+    msg::{
+        empty_as_none, AttachTokenMsg, CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg,
+        Cw721MigrateMsg, MintVoucher, MintVoucherPayload, NftInfoMsg, PermitPayload,
+    },
+    query::{query_collection_info_and_extension, query_root_owner},
+    receiver::{Cw721BatchReceiveMsg, Cw721ReceiveMsg},
+    state::{
+        CollectionInfo, Cw721Config, Edition, EditionMaster, MetadataEditorScope, NftInfo,
+        PendingTransfer, TokenLock, TokenUser, CREATOR, MINTER,
+    },
     traits::{
-        Cw721CustomMsg, Cw721Execute, Cw721State, FromAttributesState, StateFactory,
+        Cw721Calls, Cw721CustomMsg, Cw721Execute, Cw721State, FromAttributesState, StateFactory,
         ToAttributesState,
     },
     Approval, DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
@@ -73,8 +84,16 @@ where
     }
 
     // ---- set minter and creator ----
+    // an ICS-721 voucher collection is minted by the bridge only
+    if let Some(bridge) = msg.ics721_bridge.as_deref() {
+        let bridge = deps.api.addr_validate(bridge)?;
+        if msg.minter.as_deref().is_some_and(|minter| minter != bridge) {
+            return Err(Cw721ContractError::Ics721BridgeIsMinter {});
+        }
+        config.ics721_bridge.save(deps.storage, &bridge)?;
+    }
     // use info.sender if None is passed
-    let minter: &str = match msg.minter.as_deref() {
+    let minter: &str = match msg.ics721_bridge.as_deref().or(msg.minter.as_deref()) {
         Some(minter) => minter,
         None => info.sender.as_str(),
     };
@@ -92,9 +111,13 @@ where
         set_withdraw_address::<TCustomResponseMsg>(deps, &creator, withdraw_address)?;
     }
 
-    Ok(Response::default()
+    let mut response = Response::default()
         .add_attribute("minter", minter)
-        .add_attribute("creator", creator))
+        .add_attribute("creator", creator);
+    if let Some(bridge) = msg.ics721_bridge {
+        response = response.add_attribute("ics721_bridge", bridge);
+    }
+    Ok(response)
 }
 
 // ------- helper cw721 functions -------
@@ -120,19 +143,210 @@ pub fn transfer_nft<TNftExtension>(
     info: &MessageInfo,
     recipient: &str,
     token_id: &str,
-) -> Result<NftInfo<TNftExtension>, Cw721ContractError>
+) -> Result<TransferEvent, Cw721ContractError>
 where
     TNftExtension: Cw721State,
 {
     let config = Cw721Config::<TNftExtension>::default();
     let mut token = config.nft_info.load(deps.storage, token_id)?;
     // ensure we have permissions
-    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    check_can_send(deps.as_ref(), env, info.sender.as_str(), token_id, &token)?;
     // set owner and remove existing approvals
+    let previous_owner = token.owner;
     token.owner = deps.api.addr_validate(recipient)?;
     token.approvals = vec![];
     config.nft_info.save(deps.storage, token_id, &token)?;
-    Ok(token)
+    config.snapshot_owner_change(
+        deps.storage,
+        env.block.height,
+        token_id,
+        Some(&previous_owner),
+        Some(&token.owner),
+    )?;
+    // a direct transfer supersedes any pending two-step transfer
+    config.pending_transfers.remove(deps.storage, token_id);
+    // remove elapsed lock, new owner starts unlocked
+    config.token_locks.remove(deps.storage, token_id);
+    // usage rights end with ownership
+    config.token_users.remove(deps.storage, token_id);
+    Ok(TransferEvent {
+        token_id: token_id.to_string(),
+        sender: info.sender.to_string(),
+        previous_owner: previous_owner.to_string(),
+        recipient: token.owner.to_string(),
+    })
+}
+
+/// Transfers all given tokens to `contract` and triggers a single `ReceiveNftBatch` action on it.
+pub fn send_nft_batch<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    contract: String,
+    token_ids: Vec<String>,
+    msg: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    if token_ids.is_empty() {
+        return Err(Cw721ContractError::NoTokenIds {});
+    }
+    // Transfer tokens
+    let mut transfer_events: Vec<Event> = vec![];
+    for token_id in &token_ids {
+        let transfer_event =
+            transfer_nft::<TNftExtension>(deps.branch(), env, info, &contract, token_id)?;
+        transfer_events.push(transfer_event.into());
+    }
+
+    let send = Cw721BatchReceiveMsg {
+        sender: info.sender.to_string(),
+        token_ids: token_ids.clone(),
+        msg,
+    };
+
+    // Send message
+    Ok(Response::new()
+        .add_message(send.into_cosmos_msg(contract.clone())?)
+        .add_events(transfer_events)
+        .add_attribute("action", "send_nft_batch")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", contract)
+        .add_attribute("token_ids", token_ids.join(",")))
+}
+
+/// First step of a two-step transfer: ownership only moves once `recipient` accepts the transfer.
+/// Any existing proposal for the same token is replaced.
+pub fn propose_transfer<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let token = config.nft_info.load(deps.storage, &token_id)?;
+    // ensure we have permissions
+    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token_id, &token)?;
+    // reject expired data as invalid
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(Cw721ContractError::Expired {});
+    }
+    let pending = PendingTransfer {
+        proposer: info.sender.clone(),
+        recipient: deps.api.addr_validate(&recipient)?,
+        expires,
+    };
+    config
+        .pending_transfers
+        .save(deps.storage, &token_id, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_transfer")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
+/// Second step of a two-step transfer: only the proposed recipient is eligible to accept, as long as
+/// the proposer is still eligible to send the token.
+pub fn accept_transfer<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let pending = config
+        .pending_transfers
+        .may_load(deps.storage, &token_id)?
+        .ok_or_else(|| Cw721ContractError::NoPendingTransfer {
+            token_id: token_id.clone(),
+        })?;
+    if pending.recipient != info.sender {
+        return Err(Cw721ContractError::NotPendingTransferRecipient {});
+    }
+    if pending.is_expired(&env.block) {
+        return Err(Cw721ContractError::PendingTransferExpired { token_id });
+    }
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    // proposer may have lost its approval in the meantime
+    check_can_send(
+        deps.as_ref(),
+        env,
+        pending.proposer.as_str(),
+        &token_id,
+        &token,
+    )?;
+    // set owner and remove existing approvals
+    let transfer_event = TransferEvent {
+        token_id: token_id.clone(),
+        sender: info.sender.to_string(),
+        previous_owner: token.owner.to_string(),
+        recipient: pending.recipient.to_string(),
+    };
+    let previous_owner = token.owner;
+    token.owner = pending.recipient;
+    token.approvals = vec![];
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    config.snapshot_owner_change(
+        deps.storage,
+        env.block.height,
+        &token_id,
+        Some(&previous_owner),
+        Some(&token.owner),
+    )?;
+    config.pending_transfers.remove(deps.storage, &token_id);
+    config.token_locks.remove(deps.storage, &token_id);
+    config.token_users.remove(deps.storage, &token_id);
+
+    Ok(Response::new()
+        .add_event(transfer_event.into())
+        .add_attribute("action", "accept_transfer")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id))
+}
+
+/// A pending transfer may be cancelled by anyone eligible to send the token, or declined by its recipient.
+pub fn cancel_transfer<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let pending = config
+        .pending_transfers
+        .may_load(deps.storage, &token_id)?
+        .ok_or_else(|| Cw721ContractError::NoPendingTransfer {
+            token_id: token_id.clone(),
+        })?;
+    if pending.recipient != info.sender {
+        let token = config.nft_info.load(deps.storage, &token_id)?;
+        check_can_send(deps.as_ref(), env, info.sender.as_str(), &token_id, &token)?;
+    }
+    config.pending_transfers.remove(deps.storage, &token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_transfer")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id))
 }
 
 pub fn send_nft<TNftExtension, TCustomResponseMsg>(
@@ -148,7 +362,7 @@ where
     TCustomResponseMsg: CustomMsg,
 {
     // Transfer token
-    transfer_nft::<TNftExtension>(deps, env, info, &contract, &token_id)?;
+    let transfer_event = transfer_nft::<TNftExtension>(deps, env, info, &contract, &token_id)?;
 
     let send = Cw721ReceiveMsg {
         sender: info.sender.to_string(),
@@ -159,12 +373,49 @@ where
     // Send message
     Ok(Response::new()
         .add_message(send.into_cosmos_msg(contract.clone())?)
+        .add_event(transfer_event.into())
         .add_attribute("action", "send_nft")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("recipient", contract)
         .add_attribute("token_id", token_id))
 }
 
+/// Transfers the token to `recipient`. In case `recipient` is a contract, the transfer is
+/// delivered via `ReceiveNft`, so it fails if the contract doesn't handle (or rejects) the NFT.
+pub fn safe_transfer_nft<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: String,
+    token_id: String,
+    msg: Option<Binary>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let is_contract = deps.querier.query_wasm_contract_info(&recipient).is_ok();
+
+    // Transfer token
+    let transfer_event = transfer_nft::<TNftExtension>(deps, env, info, &recipient, &token_id)?;
+
+    let mut response = Response::new().add_event(transfer_event.into());
+    if is_contract {
+        let send = Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg: msg.unwrap_or_default(),
+        };
+        response = response.add_message(send.into_cosmos_msg(recipient.clone())?);
+    }
+
+    Ok(response
+        .add_attribute("action", "safe_transfer_nft")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
 pub fn approve<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -177,9 +428,18 @@ where
     TNftExtension: Cw721State,
     TCustomResponseMsg: CustomMsg,
 {
-    update_approvals::<TNftExtension>(deps, env, info, &spender, &token_id, true, expires)?;
+    let token =
+        update_approvals::<TNftExtension>(deps, env, info, &spender, &token_id, true, expires)?;
+    let approve_event = ApproveEvent {
+        token_id: token_id.clone(),
+        sender: info.sender.to_string(),
+        owner: token.owner.to_string(),
+        spender: spender.clone(),
+        expires: expires.unwrap_or_default(),
+    };
 
     Ok(Response::new()
+        .add_event(approve_event.into())
         .add_attribute("action", "approve")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("spender", spender)
@@ -203,7 +463,7 @@ where
     let config = Cw721Config::<TNftExtension>::default();
     let mut token = config.nft_info.load(deps.storage, token_id)?;
     // ensure we have permissions
-    check_can_approve(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    check_can_approve(deps.as_ref(), env, info.sender.as_str(), token_id, &token)?;
 
     // update the approval list (remove any for the same spender before adding)
     let spender_addr = deps.api.addr_validate(spender)?;
@@ -228,6 +488,89 @@ where
     Ok(token)
 }
 
+/// Approves `spender` on behalf of the owner, who signed the permit off-chain.
+/// The signature is verified over sha256 of the JSON serialized `PermitPayload`, and the
+/// public key must derive (`ripemd160(sha256(pubkey))`) to the owner's address.
+#[allow(clippy::too_many_arguments)]
+pub fn permit<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    spender: String,
+    expires: Option<Expiration>,
+    nonce: u64,
+    signature: Binary,
+    pubkey: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let owner = config.nft_info.load(deps.storage, &token_id)?.owner;
+
+    // ensure pubkey belongs to owner
+    if !pubkey_derives_to(deps.api, &pubkey, &owner)? {
+        return Err(Cw721ContractError::PermitSignerNotOwner {});
+    }
+
+    // ensure permit can't be replayed
+    let expected = config
+        .permit_nonces
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    if nonce != expected {
+        return Err(Cw721ContractError::InvalidPermitNonce {
+            expected,
+            actual: nonce,
+        });
+    }
+
+    let payload = PermitPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        token_id: token_id.clone(),
+        spender: spender.clone(),
+        expires,
+        nonce,
+    };
+    let message_hash = Sha256::digest(to_json_vec(&payload)?);
+    let verified = deps
+        .api
+        .secp256k1_verify(&message_hash, &signature, &pubkey)
+        .map_err(StdError::from)?;
+    if !verified {
+        return Err(Cw721ContractError::InvalidPermitSignature {});
+    }
+    config
+        .permit_nonces
+        .save(deps.storage, &owner, &(nonce + 1))?;
+
+    // approve on behalf of owner
+    let owner_info = MessageInfo {
+        sender: owner.clone(),
+        funds: vec![],
+    };
+    update_approvals::<TNftExtension>(deps, env, &owner_info, &spender, &token_id, true, expires)?;
+    let approve_event = ApproveEvent {
+        token_id: token_id.clone(),
+        sender: info.sender.to_string(),
+        owner: owner.to_string(),
+        spender: spender.clone(),
+        expires: expires.unwrap_or_default(),
+    };
+
+    Ok(Response::new()
+        .add_event(approve_event.into())
+        .add_attribute("action", "permit")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id)
+        .add_attribute("nonce", nonce.to_string()))
+}
+
 pub fn revoke<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -247,6 +590,54 @@ where
         .add_attribute("token_id", token_id))
 }
 
+/// Sets (or removes, if `user` is none) the user of a token, with the same permissions as for sending the token.
+/// The user is removed once the token is transferred or burned.
+pub fn set_user<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    user: Option<String>,
+    expires: Option<Expiration>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let token = config.nft_info.load(deps.storage, &token_id)?;
+    // ensure we have permissions
+    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token_id, &token)?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_user")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id.clone());
+    match user {
+        Some(user) => {
+            // reject expired data as invalid
+            let expires = expires.unwrap_or_default();
+            if expires.is_expired(&env.block) {
+                return Err(Cw721ContractError::Expired {});
+            }
+            let token_user = TokenUser {
+                user: deps.api.addr_validate(&user)?,
+                expires,
+            };
+            config
+                .token_users
+                .save(deps.storage, &token_id, &token_user)?;
+            Ok(response
+                .add_attribute("user", user)
+                .add_attribute("expires", expires.to_string()))
+        }
+        None => {
+            config.token_users.remove(deps.storage, &token_id);
+            Ok(response)
+        }
+    }
+}
+
 pub fn approve_all<TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -293,6 +684,278 @@ pub fn revoke_all<TCustomResponseMsg>(
         .add_attribute("operator", operator))
 }
 
+/// Only the owner is eligible to lock a token. Locking removes existing approvals and a pending transfer,
+/// and cancels a requested unlock.
+pub fn lock_token<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    token.approvals = vec![];
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    config.pending_transfers.remove(deps.storage, &token_id);
+    config
+        .token_locks
+        .save(deps.storage, &token_id, &TokenLock { unlocks_at: None })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_token")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id))
+}
+
+/// Only the owner is eligible to unlock a token. Unlock takes effect after the configured unlock delay.
+/// Requesting an unlock again does not postpone an already requested unlock.
+pub fn unlock_token<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let token = config.nft_info.load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    let mut lock = config
+        .token_locks
+        .may_load(deps.storage, &token_id)?
+        .filter(|lock| lock.is_locked(&env.block))
+        .ok_or_else(|| Cw721ContractError::TokenNotLocked {
+            token_id: token_id.clone(),
+        })?;
+    let unlocks_at = match lock.unlocks_at {
+        Some(unlocks_at) => unlocks_at,
+        None => config.unlock_delay(deps.storage)?.after(&env.block),
+    };
+    lock.unlocks_at = Some(unlocks_at);
+    config.token_locks.save(deps.storage, &token_id, &lock)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock_token")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("unlocks_at", unlocks_at.to_string()))
+}
+
+/// Only the creator is eligible to set the delay until an unlock takes effect.
+/// Already requested unlocks are not affected.
+pub fn set_unlock_delay<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    delay: Duration,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_creator(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    config.unlock_delay.save(deps.storage, &delay)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_unlock_delay")
+        .add_attribute("sender", sender.to_string())
+        .add_attribute("delay", delay.to_string()))
+}
+
+/// Only the creator is eligible to opt in to snapshotting ownership by block height.
+/// Snapshots must be enabled before the first mint, so they cover the full ownership history.
+pub fn enable_owner_snapshots<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_creator(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    if !config.owner_snapshots_enabled(deps.storage)? {
+        if config.token_count(deps.storage)? > 0 {
+            return Err(Cw721ContractError::OwnerSnapshotsRequireNoTokens {});
+        }
+        config.owner_snapshots_enabled.save(deps.storage, &true)?;
+    }
+    Ok(Response::new()
+        .add_attribute("action", "enable_owner_snapshots")
+        .add_attribute("sender", sender.to_string()))
+}
+
+/// Links a token (of this or another collection) to its parent token of this collection.
+fn save_attachment(
+    storage: &mut dyn Storage,
+    contract: &Addr,
+    parent_token_id: &str,
+    collection: &Addr,
+    token_id: &str,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // ensure parent exists
+    config.nft_info.load(storage, parent_token_id)?;
+    if collection == contract {
+        // attaching a token below itself would detach the whole branch from any owner
+        let mut ancestor = Some(parent_token_id.to_string());
+        while let Some(current) = ancestor {
+            if current == token_id {
+                return Err(Cw721ContractError::AttachmentCycle {});
+            }
+            ancestor = config
+                .token_parents
+                .may_load(storage, (contract, &current))?;
+        }
+    }
+    config.token_parents.save(
+        storage,
+        (collection, token_id),
+        &parent_token_id.to_string(),
+    )?;
+    config.token_children.save(
+        storage,
+        (parent_token_id, (collection, token_id)),
+        &Empty {},
+    )?;
+    Ok(())
+}
+
+pub fn attach_token<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    parent_token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let contract = &env.contract.address;
+    save_attachment(
+        deps.storage,
+        contract,
+        &parent_token_id,
+        contract,
+        &token_id,
+    )?;
+    // attached token is owned by this contract, so only the root owner can detach it
+    let transfer_event =
+        transfer_nft::<TNftExtension>(deps.branch(), env, info, contract.as_str(), &token_id)?;
+
+    Ok(Response::new()
+        .add_event(transfer_event.into())
+        .add_attribute("action", "attach_token")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("parent_token_id", parent_token_id))
+}
+
+pub fn receive_nft<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let AttachTokenMsg { parent_token_id } = from_json(&receive_msg.msg)?;
+    // the sending collection is the sender, so it must be a contract that handed over the token
+    let received = deps.querier.query_wasm_contract_info(&info.sender).is_ok()
+        && DefaultCw721Helper::new(info.sender.clone())
+            .owner_of(&deps.querier, &receive_msg.token_id, false)
+            .is_ok_and(|res| res.owner == env.contract.address.as_str());
+    if !received {
+        return Err(Cw721ContractError::TokenNotReceived {
+            token_id: receive_msg.token_id,
+        });
+    }
+    save_attachment(
+        deps.storage,
+        &env.contract.address,
+        &parent_token_id,
+        &info.sender,
+        &receive_msg.token_id,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "attach_token")
+        .add_attribute("sender", receive_msg.sender)
+        .add_attribute("collection", info.sender.to_string())
+        .add_attribute("token_id", receive_msg.token_id)
+        .add_attribute("parent_token_id", parent_token_id))
+}
+
+pub fn detach_token<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    parent_token_id: String,
+    collection: Option<String>,
+    token_id: String,
+    recipient: Option<String>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let contract = &env.contract.address;
+    let collection = match collection {
+        Some(collection) => deps.api.addr_validate(&collection)?,
+        None => contract.clone(),
+    };
+    if !config
+        .token_children
+        .has(deps.storage, (&parent_token_id, (&collection, &token_id)))
+    {
+        return Err(Cw721ContractError::NotAttached { token_id });
+    }
+    if query_root_owner(deps.as_ref(), env, parent_token_id.clone())?.owner != info.sender {
+        return Err(Cw721ContractError::NotRootOwner {});
+    }
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+    config
+        .token_parents
+        .remove(deps.storage, (&collection, &token_id));
+    config
+        .token_children
+        .remove(deps.storage, (&parent_token_id, (&collection, &token_id)));
+
+    let response = Response::new()
+        .add_attribute("action", "detach_token")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("parent_token_id", parent_token_id)
+        .add_attribute("recipient", recipient.to_string());
+    if &collection != contract {
+        let transfer_msg = WasmMsg::Execute {
+            contract_addr: collection.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
+                recipient: recipient.to_string(),
+                token_id,
+            })?,
+            funds: vec![],
+        };
+        return Ok(response.add_message(transfer_msg));
+    }
+
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    token.owner = recipient;
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    config.snapshot_owner_change(
+        deps.storage,
+        env.block.height,
+        &token_id,
+        Some(contract),
+        Some(&token.owner),
+    )?;
+    let transfer_event = TransferEvent {
+        token_id,
+        sender: info.sender.to_string(),
+        previous_owner: contract.to_string(),
+        recipient: token.owner.to_string(),
+    };
+    Ok(response.add_event(transfer_event.into()))
+}
+
 pub fn burn_nft<TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -301,12 +964,62 @@ pub fn burn_nft<TCustomResponseMsg>(
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
     let config = Cw721Config::<Option<Empty>>::default();
     let token = config.nft_info.load(deps.storage, &token_id)?;
-    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    // vouchers are burned by the bridge only, when sent back to the source chain
+    match config.ics721_bridge.may_load(deps.storage)? {
+        Some(bridge) if bridge != info.sender => {
+            return Err(Cw721ContractError::NotIcs721Bridge {})
+        }
+        Some(_) => {}
+        None => check_can_send(deps.as_ref(), env, info.sender.as_str(), &token_id, &token)?,
+    }
+    if config
+        .token_children
+        .prefix(&token_id)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(Cw721ContractError::TokenHasChildren { token_id });
+    }
+    // a burned voucher may still be attached
+    if let Some(parent_token_id) = config
+        .token_parents
+        .may_load(deps.storage, (&env.contract.address, &token_id))?
+    {
+        config
+            .token_parents
+            .remove(deps.storage, (&env.contract.address, &token_id));
+        config.token_children.remove(
+            deps.storage,
+            (&parent_token_id, (&env.contract.address, &token_id)),
+        );
+    }
 
+    if let Some(edition) = &token.edition {
+        config
+            .editions
+            .remove(deps.storage, (&edition.master_id, edition.number));
+    }
     config.nft_info.remove(deps.storage, &token_id)?;
+    config.pending_transfers.remove(deps.storage, &token_id);
+    config.token_locks.remove(deps.storage, &token_id);
+    config.token_users.remove(deps.storage, &token_id);
     config.decrement_tokens(deps.storage)?;
+    config.snapshot_owner_change(
+        deps.storage,
+        env.block.height,
+        &token_id,
+        Some(&token.owner),
+        None,
+    )?;
+    let burn_event = BurnEvent {
+        token_id: token_id.clone(),
+        sender: info.sender.to_string(),
+        owner: token.owner.to_string(),
+    };
 
     Ok(Response::new()
+        .add_event(burn_event.into())
         .add_attribute("action", "burn")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("token_id", token_id))
@@ -367,25 +1080,402 @@ where
         extension,
     };
     let token = token_msg.create(deps.as_ref(), env, info.into(), None)?;
+    save_minted_token(deps, env, &token_id, token)?;
+    Ok(mint_response(&info.sender, owner, token_id, token_uri))
+}
+
+/// Saves a newly minted token, already validated by the caller.
+fn save_minted_token<TNftExtension>(
+    deps: DepsMut,
+    env: &Env,
+    token_id: &str,
+    token: NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let token_owner = token.owner.clone();
     let config = Cw721Config::<TNftExtension>::default();
+    // ids `{master_id}/{edition_number}` are reserved for the editions of registered masters
+    if token.edition.is_none() {
+        if let Some((master_id, edition_number)) = token_id.rsplit_once('/') {
+            if edition_number.parse::<u64>().is_ok()
+                && config.edition_masters.has(deps.storage, master_id)
+            {
+                return Err(Cw721ContractError::EditionIdClaimed {
+                    token_id: token_id.to_string(),
+                    master_id: master_id.to_string(),
+                });
+            }
+        }
+    }
     config
         .nft_info
-        .update(deps.storage, &token_id, |old| match old {
+        .update(deps.storage, token_id, |old| match old {
             Some(_) => Err(Cw721ContractError::Claimed {}),
             None => Ok(token),
         })?;
 
     config.increment_tokens(deps.storage)?;
+    config.snapshot_owner_change(
+        deps.storage,
+        env.block.height,
+        token_id,
+        None,
+        Some(&token_owner),
+    )?;
+    Ok(())
+}
+
+fn mint_response<TCustomResponseMsg>(
+    minter: &Addr,
+    owner: String,
+    token_id: String,
+    token_uri: Option<String>,
+) -> Response<TCustomResponseMsg>
+where
+    TCustomResponseMsg: CustomMsg,
+{
+    let mint_event = MintEvent {
+        token_id: token_id.clone(),
+        minter: minter.to_string(),
+        owner: owner.clone(),
+        token_uri: token_uri.clone(),
+    };
 
     let mut res = Response::new()
+        .add_event(mint_event.into())
         .add_attribute("action", "mint")
-        .add_attribute("minter", info.sender.to_string())
+        .add_attribute("minter", minter.to_string())
         .add_attribute("owner", owner)
         .add_attribute("token_id", token_id);
     if let Some(token_uri) = token_uri {
         res = res.add_attribute("token_uri", value_or_empty(&token_uri));
     }
-    Ok(res)
+    res
+}
+
+/// Same as `mint`, but additionally notifies the owner contract via `Cw721ReceiveMsg` (with minter as sender).
+/// Owner must be a contract implementing the Cw721Receiver interface, otherwise the whole tx fails.
+#[allow(clippy::too_many_arguments)]
+pub fn safe_mint<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+    msg: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps,
+        env,
+        info,
+        token_id.clone(),
+        owner.clone(),
+        token_uri,
+        extension,
+    )?;
+
+    let receive = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id,
+        msg,
+    };
+    Ok(res.add_message(receive.into_cosmos_msg(owner)?))
+}
+
+/// Lazy minting: anyone may redeem a voucher signed off-chain by the minter, by paying its price (if any).
+/// The voucher is verified against the minter's registered voucher signer key, then the NFT is minted
+/// on behalf of the minter. Paid funds remain in the contract, and can be withdrawn via `WithdrawFunds`.
+pub fn mint_with_voucher<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    voucher: MintVoucher<TNftExtensionMsg>,
+    signature: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let minter = MINTER
+        .get_ownership(deps.storage)?
+        .owner
+        .ok_or(Cw721ContractError::NotMinter {})?;
+    let pubkey = config
+        .voucher_signer
+        .may_load(deps.storage)?
+        .ok_or(Cw721ContractError::NoVoucherSigner {})?;
+    // signer may be outdated, in case minter ownership has been transferred
+    if !pubkey_derives_to(deps.api, &pubkey, &minter)? {
+        return Err(Cw721ContractError::VoucherSignerNotMinter {});
+    }
+    if voucher
+        .expires
+        .is_some_and(|expires| expires.is_expired(&env.block))
+    {
+        return Err(Cw721ContractError::VoucherExpired {});
+    }
+    if config.used_voucher_nonces.has(deps.storage, voucher.nonce) {
+        return Err(Cw721ContractError::VoucherNonceUsed {
+            nonce: voucher.nonce,
+        });
+    }
+
+    let payload = MintVoucherPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        voucher: voucher.clone(),
+    };
+    let message_hash = Sha256::digest(to_json_vec(&payload)?);
+    let verified = deps
+        .api
+        .secp256k1_verify(&message_hash, &signature, &pubkey)
+        .map_err(StdError::from)?;
+    if !verified {
+        return Err(Cw721ContractError::InvalidVoucherSignature {});
+    }
+    if let Some(price) = &voucher.price {
+        if !has_coins(&info.funds, price) {
+            return Err(Cw721ContractError::VoucherPriceNotPaid {
+                price: price.to_string(),
+            });
+        }
+    }
+    config
+        .used_voucher_nonces
+        .save(deps.storage, voucher.nonce, &Empty {})?;
+
+    // voucher without owner can be redeemed by anyone
+    let owner = match voucher.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+    // mint on behalf of minter
+    let minter_info = MessageInfo {
+        sender: minter,
+        funds: vec![],
+    };
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps,
+        env,
+        &minter_info,
+        voucher.token_id,
+        owner.to_string(),
+        voucher.token_uri,
+        voucher.extension,
+    )?;
+    Ok(res
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("voucher_nonce", voucher.nonce.to_string()))
+}
+
+/// Only the minter is eligible to set the public key used for signing mint vouchers.
+/// The public key must belong to the minter.
+pub fn set_voucher_signer<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    pubkey: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_minter(deps.storage, sender)?;
+    if !pubkey_derives_to(deps.api, &pubkey, sender)? {
+        return Err(Cw721ContractError::VoucherSignerNotMinter {});
+    }
+    let config = Cw721Config::<Option<Empty>>::default();
+    config.voucher_signer.save(deps.storage, &pubkey)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_voucher_signer")
+        .add_attribute("sender", sender.to_string())
+        .add_attribute("pubkey", pubkey.to_base64()))
+}
+
+/// Registers an edition master, whose token uri and extension are stored once and shared by all its editions.
+pub fn register_edition_master<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    master_id: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+    max_size: u64,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_creator(deps.storage, &info.sender)?;
+    if max_size == 0 {
+        return Err(Cw721ContractError::InvalidEditionSize {});
+    }
+    let config = Cw721Config::<TNftExtension>::default();
+    if config.edition_masters.has(deps.storage, &master_id) {
+        return Err(Cw721ContractError::EditionMasterExists { master_id });
+    }
+    // editions are minted as `{master_id}/{edition_number}`, so no token may be prefixed that way yet
+    let prefix = format!("{}/", master_id);
+    let claimed = config
+        .nft_info
+        .keys(
+            deps.storage,
+            Some(Bound::inclusive(prefix.as_str())),
+            None,
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?
+        .filter(|token_id| token_id.starts_with(&prefix));
+    if let Some(token_id) = claimed {
+        return Err(Cw721ContractError::EditionIdClaimed {
+            token_id,
+            master_id,
+        });
+    }
+    let token_uri = empty_as_none(token_uri);
+    if let Some(token_uri) = &token_uri {
+        Url::parse(token_uri)?;
+    }
+    let extension = extension.create(deps.as_ref(), env, info.into(), None)?;
+    let master = EditionMaster {
+        token_uri,
+        extension,
+        max_size,
+        minted: 0,
+    };
+    config
+        .edition_masters
+        .save(deps.storage, &master_id, &master)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_edition_master")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("master_id", master_id)
+        .add_attribute("max_size", max_size.to_string()))
+}
+
+/// Mints the next edition of a master, as token `{master_id}/{edition_number}`. The token references the master
+/// for its shared metadata, with the edition number added to the extension on queries.
+pub fn mint_edition<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    master_id: String,
+    owner: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State + Default,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_minter(deps.storage, &info.sender)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut master = config
+        .edition_masters
+        .may_load(deps.storage, &master_id)?
+        .ok_or_else(|| Cw721ContractError::EditionMasterNotFound {
+            master_id: master_id.clone(),
+        })?;
+    if master.minted >= master.max_size {
+        return Err(Cw721ContractError::EditionsSoldOut { master_id });
+    }
+    master.minted += 1;
+    let edition_number = master.minted;
+    config
+        .edition_masters
+        .save(deps.storage, &master_id, &master)?;
+
+    let token = NftInfo {
+        owner: deps.api.addr_validate(&owner)?,
+        approvals: vec![],
+        token_uri: None,
+        extension: TNftExtension::default(),
+        template_id: None,
+        edition: Some(Edition {
+            master_id: master_id.clone(),
+            number: edition_number,
+        }),
+    };
+    let token_id = format!("{}/{}", master_id, edition_number);
+    save_minted_token(deps.branch(), env, &token_id, token)?;
+    config
+        .editions
+        .save(deps.storage, (&master_id, edition_number), &Empty {})?;
+    Ok(
+        mint_response(&info.sender, owner, token_id, master.token_uri)
+            .add_attribute("master_id", master_id)
+            .add_attribute("edition_number", edition_number.to_string()),
+    )
+}
+
+/// Saves a template with an extension shared by all tokens minted from it. Updating a template updates the
+/// metadata of all its tokens.
+pub fn set_template<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    template_id: String,
+    extension: TNftExtensionMsg,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_creator(deps.storage, &info.sender)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let current = config.templates.may_load(deps.storage, &template_id)?;
+    let extension = extension.create(deps.as_ref(), env, info.into(), current.as_ref())?;
+    config
+        .templates
+        .save(deps.storage, &template_id, &extension)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_template")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("template_id", template_id))
+}
+
+/// Same as `mint`, but the token references a template for its shared extension, and only stores
+/// the given per-token overrides.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_from_template<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    template_id: String,
+    overrides: TNftExtensionMsg,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    if !config.templates.has(deps.storage, &template_id) {
+        return Err(Cw721ContractError::TemplateNotFound { template_id });
+    }
+    let token_msg = NftInfoMsg {
+        owner: owner.clone(),
+        approvals: vec![],
+        token_uri: token_uri.clone(),
+        extension: overrides,
+    };
+    let mut token = token_msg.create(deps.as_ref(), env, info.into(), None)?;
+    token.template_id = Some(template_id.clone());
+    save_minted_token(deps, env, &token_id, token)?;
+    Ok(mint_response(&info.sender, owner, token_id, token_uri)
+        .add_attribute("template_id", template_id))
 }
 
 pub fn update_minter_ownership<TCustomResponseMsg>(
@@ -395,6 +1485,10 @@ pub fn update_minter_ownership<TCustomResponseMsg>(
     info: &MessageInfo,
     action: Action,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if config.ics721_bridge.may_load(storage)?.is_some() {
+        return Err(Cw721ContractError::Ics721BridgeIsMinter {});
+    }
     let ownership = MINTER.update_ownership(api, storage, &env.block, &info.sender, action)?;
     Ok(Response::new()
         .add_attribute("update_minter_ownership", info.sender.to_string())
@@ -414,7 +1508,7 @@ pub fn update_creator_ownership<TCustomResponseMsg>(
         .add_attributes(ownership.into_attributes()))
 }
 
-/// The creator is the only one eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
+/// The creator and metadata editors (within their scope) are eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
 /// NOTE: approvals and owner are not affected by this call, since they belong to the NFT owner.
 pub fn update_nft_info<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
     deps: DepsMut,
@@ -429,6 +1523,9 @@ where
     TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCustomResponseMsg: CustomMsg,
 {
+    if let Some(info) = info {
+        assert_creator_or_metadata_editor(deps.storage, &info.sender, Some(&token_id))?;
+    }
     let contract = Cw721Config::<TNftExtension>::default();
     let current_nft_info = contract.nft_info.load(deps.storage, &token_id)?;
     let nft_info_msg = NftInfoMsg {
@@ -439,11 +1536,51 @@ where
     };
     let updated = nft_info_msg.create(deps.as_ref(), env, info, Some(&current_nft_info))?;
     contract.nft_info.save(deps.storage, &token_id, &updated)?;
+    let metadata_update_event = MetadataUpdateEvent {
+        token_id: token_id.clone(),
+        editor: info.map(|info| info.sender.to_string()),
+    };
     Ok(Response::new()
+        .add_event(metadata_update_event.into())
         .add_attribute("action", "update_nft_info")
         .add_attribute("token_id", token_id))
 }
 
+/// Only the creator is eligible to grant (or update) metadata editor rights.
+pub fn set_metadata_editor<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    editor: String,
+    scope: MetadataEditorScope,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_creator(deps.storage, sender)?;
+    let editor_addr = deps.api.addr_validate(&editor)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    config
+        .metadata_editors
+        .save(deps.storage, &editor_addr, &scope)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_metadata_editor")
+        .add_attribute("sender", sender.to_string())
+        .add_attribute("editor", editor))
+}
+
+/// Only the creator is eligible to revoke metadata editor rights.
+pub fn remove_metadata_editor<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    editor: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_creator(deps.storage, sender)?;
+    let editor_addr = deps.api.addr_validate(&editor)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    config.metadata_editors.remove(deps.storage, &editor_addr);
+    Ok(Response::new()
+        .add_attribute("action", "remove_metadata_editor")
+        .add_attribute("sender", sender.to_string())
+        .add_attribute("editor", editor))
+}
+
 pub fn set_withdraw_address<TCustomResponseMsg>(
     deps: DepsMut,
     sender: &Addr,
@@ -504,11 +1641,14 @@ pub fn check_can_approve<TNftExtension>(
     deps: Deps,
     env: &Env,
     sender: &str,
+    token_id: &str,
     token: &NftInfo<TNftExtension>,
 ) -> Result<(), Cw721ContractError>
 where
     TNftExtension: Cw721State,
 {
+    // locked tokens cannot be approved, not even by the owner
+    assert_not_locked(deps.storage, env, token_id)?;
     let sender = deps.api.addr_validate(sender)?;
     // owner can approve
     if token.owner == sender {
@@ -536,8 +1676,11 @@ pub fn check_can_send<TNftExtension>(
     deps: Deps,
     env: &Env,
     sender: &str,
+    token_id: &str,
     token: &NftInfo<TNftExtension>,
 ) -> Result<(), Cw721ContractError> {
+    // locked tokens cannot be sent, not even by the owner
+    assert_not_locked(deps.storage, env, token_id)?;
     let sender = deps.api.addr_validate(sender)?;
     // owner can send
     if token.owner == sender {
@@ -572,6 +1715,26 @@ pub fn check_can_send<TNftExtension>(
     }
 }
 
+pub fn assert_not_locked(
+    storage: &dyn Storage,
+    env: &Env,
+    token_id: &str,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if config.is_locked(storage, &env.block, token_id)? {
+        return Err(Cw721ContractError::TokenLocked {
+            token_id: token_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns true if the secp256k1 public key derives (`ripemd160(sha256(pubkey))`) to the given address.
+pub fn pubkey_derives_to(api: &dyn Api, pubkey: &[u8], addr: &Addr) -> StdResult<bool> {
+    let derived = Ripemd160::digest(Sha256::digest(pubkey));
+    Ok(api.addr_canonicalize(addr.as_str())?.as_slice() == derived.as_slice())
+}
+
 pub fn assert_minter(storage: &dyn Storage, sender: &Addr) -> Result<(), Cw721ContractError> {
     if MINTER.assert_owner(storage, sender).is_err() {
         return Err(Cw721ContractError::NotMinter {});
@@ -586,6 +1749,34 @@ pub fn assert_creator(storage: &dyn Storage, sender: &Addr) -> Result<(), Cw721C
     Ok(())
 }
 
+/// Creator is always eligible. A metadata editor is eligible in case:
+/// - `token_id` is none (e.g. in `StateFactory::validate()`, where token id is unknown), or
+/// - editor's scope includes the given `token_id`.
+///
+/// In case sender is neither creator nor metadata editor, `NotCreator` is returned.
+pub fn assert_creator_or_metadata_editor(
+    storage: &dyn Storage,
+    sender: &Addr,
+    token_id: Option<&str>,
+) -> Result<(), Cw721ContractError> {
+    if assert_creator(storage, sender).is_ok() {
+        return Ok(());
+    }
+    let scope = Cw721Config::<Option<Empty>>::default()
+        .metadata_editors
+        .may_load(storage, sender)?
+        .ok_or(Cw721ContractError::NotCreator {})?;
+    match token_id {
+        Some(token_id) if !scope.includes(token_id) => {
+            Err(Cw721ContractError::MetadataEditorOutOfScope {
+                editor: sender.to_string(),
+                token_id: token_id.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 // ------- migrate -------
 pub fn migrate(
     deps: DepsMut,
@@ -793,7 +1984,7 @@ impl<
         TCustomResponseMsg,
     >
 where
-    TNftExtension: Cw721State,
+    TNftExtension: Cw721State + Default,
     TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + ToAttributesState + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg + StateFactory<TCollectionExtension>,
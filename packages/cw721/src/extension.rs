@@ -144,6 +144,7 @@ impl Default for Cw721EmptyExtensions<'static> {
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     ics721_bridge: None,
 /// };
 /// //...
 /// // mint:
@@ -3,20 +3,22 @@ use std::collections::HashMap;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
     to_json_binary, Addr, Binary, Coin, ContractInfoResponse, Decimal, Deps, Env, MessageInfo,
-    Timestamp,
+    Timestamp, Uint128,
 };
 use cw_ownable::{Action, Ownership};
-use cw_utils::Expiration;
-use serde::Serialize;
+use cw_utils::{Duration, Expiration};
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::error::Cw721ContractError;
-use crate::execute::{assert_creator, assert_minter};
+use crate::execute::{assert_creator, assert_creator_or_metadata_editor, assert_minter};
+use crate::receiver::Cw721ReceiveMsg;
 use crate::state::{
-    Attribute, CollectionExtension, CollectionExtensionAttributes, CollectionInfo, NftInfo, Trait,
-    ATTRIBUTE_DESCRIPTION, ATTRIBUTE_EXPLICIT_CONTENT, ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE,
-    ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_START_TRADING_TIME, CREATOR,
-    MAX_COLLECTION_DESCRIPTION_LENGTH, MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
+    Attribute, CollectionExtension, CollectionExtensionAttributes, CollectionInfo,
+    MetadataEditorScope, NftInfo, PendingTransfer, Trait, ATTRIBUTE_DESCRIPTION,
+    ATTRIBUTE_EXPLICIT_CONTENT, ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE, ATTRIBUTE_ROYALTY_INFO,
+    ATTRIBUTE_START_TRADING_TIME, CREATOR, MAX_COLLECTION_DESCRIPTION_LENGTH,
+    MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
 };
 use crate::traits::{Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState};
 use crate::NftExtension;
@@ -53,6 +55,35 @@ pub enum Cw721ExecuteMsg<
         token_id: String,
         msg: Binary,
     },
+    /// Safe alternative to `TransferNft`: in case `recipient` is a contract, the transfer is delivered
+    /// via `ReceiveNft` (like `SendNft`) and fails if the contract doesn't accept the token.
+    SafeTransferNft {
+        recipient: String,
+        token_id: String,
+        msg: Option<Binary>,
+    },
+    /// Same as `SendNft`, but transfers all given tokens to a contract and triggers a single
+    /// `ReceiveNftBatch` action on the receiving contract.
+    SendNftBatch {
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    },
+    /// Proposes a two-step transfer: ownership only moves once `recipient` accepts it.
+    /// Replaces any existing proposal for the token. If expiration is set, the proposal has a time/height limit.
+    ProposeTransfer {
+        recipient: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Accepts a pending transfer. Only the proposed recipient is eligible.
+    AcceptTransfer {
+        token_id: String,
+    },
+    /// Cancels a pending transfer. Eligible are sender of the token or the proposed recipient.
+    CancelTransfer {
+        token_id: String,
+    },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
@@ -65,6 +96,25 @@ pub enum Cw721ExecuteMsg<
         spender: String,
         token_id: String,
     },
+    /// Approves `spender` on behalf of the owner, using a signature created off-chain by the owner (e.g. for gasless listings).
+    /// The signature is verified over sha256 of the JSON serialized `PermitPayload`.
+    /// `pubkey` is the owner's compressed secp256k1 public key, and `nonce` must match the owner's next permit nonce.
+    Permit {
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    },
+    /// Sets user of the token, having usage rights but not ownership (e.g. for renting).
+    /// User is removed when unset, or once the token is transferred.
+    /// If expiration is set, then usage rights have a time/height limit
+    SetUser {
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
+    },
     /// Allows operator to transfer / send any token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     ApproveAll {
@@ -75,6 +125,39 @@ pub enum Cw721ExecuteMsg<
     RevokeAll {
         operator: String,
     },
+    /// Locks the token, so it cannot be transferred, sent, burned or approved - not even by the owner.
+    /// Removes existing approvals and pending transfer. Only owner can call this.
+    LockToken {
+        token_id: String,
+    },
+    /// Requests to unlock the token, taking effect after the unlock delay. Only owner can call this.
+    UnlockToken {
+        token_id: String,
+    },
+    /// Sets the delay until an unlock takes effect. Only creator can call this.
+    SetUnlockDelay {
+        delay: Duration,
+    },
+    /// Opts in to snapshotting ownership by block height, e.g. for DAO votes. Only creator can call this,
+    /// and only before the first mint. Once enabled, snapshots can't be disabled.
+    EnableOwnerSnapshots {},
+    /// Attaches the token to a parent token of this collection, e.g. an item to a character. The attached token is
+    /// owned by this contract and moves along with its parent. Only owner (or approved spender/operator) can call this.
+    AttachToken {
+        token_id: String,
+        parent_token_id: String,
+    },
+    /// Detaches a token from its parent, and transfers it to recipient (sender, if not set). Collection of the
+    /// attached token defaults to this collection. Only the root owner of the parent can call this.
+    DetachToken {
+        parent_token_id: String,
+        collection: Option<String>,
+        token_id: String,
+        recipient: Option<String>,
+    },
+    /// Attaches a token of another collection to a token of this collection, sent via `SendNft` with
+    /// `AttachTokenMsg` as msg.
+    ReceiveNft(Cw721ReceiveMsg),
 
     /// Mint a new NFT, can only be called by the contract minter
     Mint {
@@ -89,6 +172,66 @@ pub enum Cw721ExecuteMsg<
         /// Any custom extension used by this contract
         extension: TNftExtensionMsg,
     },
+    /// Same as `Mint`, but additionally sends `Cw721ReceiveMsg` (with minter as sender) to the owner, which must be a contract.
+    /// This allows contracts (e.g. vaults or staking) to accept newly minted NFTs atomically.
+    SafeMint {
+        /// Unique ID of the NFT
+        token_id: String,
+        /// The owner of the newly minted NFT, must be a contract implementing the Cw721Receiver interface
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: TNftExtensionMsg,
+        /// Passed to the owner contract in `Cw721ReceiveMsg`
+        msg: Binary,
+    },
+    /// Lazy minting: mints a new NFT using a voucher signed off-chain by the minter.
+    /// Can be called by anyone, paying the voucher's price (if any).
+    MintWithVoucher {
+        voucher: MintVoucher<TNftExtensionMsg>,
+        /// secp256k1 signature over sha256 of the JSON serialized `MintVoucherPayload`
+        signature: Binary,
+    },
+    /// Sets the minter's compressed secp256k1 public key, used for verifying mint vouchers.
+    /// Only minter can call this.
+    SetVoucherSigner {
+        pubkey: Binary,
+    },
+    /// Registers an edition master, whose token uri and extension are shared by all its editions.
+    /// Only creator can call this.
+    RegisterEditionMaster {
+        master_id: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        /// Maximum number of editions.
+        max_size: u64,
+    },
+    /// Mints the next edition of a master, with token id `{master_id}/{edition_number}` and the
+    /// edition number added to the extension. Only minter can call this.
+    MintEdition {
+        master_id: String,
+        /// The owner of the newly minted edition
+        owner: String,
+    },
+    /// Saves a template with an extension shared by the tokens minted from it. Only creator can call this.
+    SetTemplate {
+        template_id: String,
+        extension: TNftExtensionMsg,
+    },
+    /// Mints a token referencing a template, storing only the per-token overrides of its extension.
+    /// Only minter can call this.
+    MintFromTemplate {
+        /// Unique ID of the NFT
+        token_id: String,
+        /// The owner of the newly minted NFT
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        template_id: String,
+        /// Per-token overrides of the template's extension
+        overrides: TNftExtensionMsg,
+    },
 
     /// Burn an NFT the sender has access to
     Burn {
@@ -100,7 +243,7 @@ pub enum Cw721ExecuteMsg<
         msg: TExtensionMsg,
     },
 
-    /// The creator is the only one eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
+    /// The creator and metadata editors (within their scope) are eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
     /// NOTE: approvals and owner are not affected by this call, since they belong to the NFT owner.
     UpdateNftInfo {
         token_id: String,
@@ -109,6 +252,16 @@ pub enum Cw721ExecuteMsg<
         extension: TNftExtensionMsg,
     },
 
+    /// Grants (or updates) rights to update NFT metadata for the given scope. Only creator can call this.
+    SetMetadataEditor {
+        editor: String,
+        scope: MetadataEditorScope,
+    },
+    /// Revokes previously granted metadata editor rights. Only creator can call this.
+    RemoveMetadataEditor {
+        editor: String,
+    },
+
     /// Sets address to send withdrawn fees to. Only owner can call this.
     SetWithdrawAddress {
         address: String,
@@ -140,6 +293,10 @@ pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
     pub creator: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Instantiates an ICS-721 voucher collection for NFTs bridged from another chain: the given bridge contract
+    /// is the only minter and burner. Minter must be either unset or the bridge.
+    pub ics721_bridge: Option<String>,
 }
 
 #[cw_serde]
@@ -159,6 +316,9 @@ pub enum Cw721QueryMsg<
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
     },
+    /// Return the active user of the given token, none if unset or expired. Error if token does not exist.
+    #[returns(UserOfResponse)]
+    UserOf { token_id: String },
     /// Return operator that can access all of the owner's tokens.
     #[returns(ApprovalResponse)]
     Approval {
@@ -281,6 +441,88 @@ pub enum Cw721QueryMsg<
 
     #[returns(Option<String>)]
     GetWithdrawAddress {},
+
+    /// Returns scope of the given metadata editor, or none if not an editor.
+    #[returns(Option<MetadataEditorScope>)]
+    GetMetadataEditor { editor: String },
+
+    /// Lists all metadata editors with their scopes.
+    #[returns(MetadataEditorsResponse)]
+    GetMetadataEditors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns whether the token is locked, and when a requested unlock takes effect.
+    #[returns(IsLockedResponse)]
+    IsLocked { token_id: String },
+
+    /// Returns the minter's public key used for verifying mint vouchers.
+    #[returns(Option<Binary>)]
+    GetVoucherSigner {},
+
+    /// Returns true if a mint voucher with the given nonce has been redeemed.
+    #[returns(bool)]
+    IsVoucherNonceUsed { nonce: u64 },
+
+    /// Returns the nonce expected in the next permit signed by the given owner.
+    #[returns(u64)]
+    GetPermitNonce { owner: String },
+
+    /// Returns the delay until an unlock takes effect.
+    #[returns(Duration)]
+    GetUnlockDelay {},
+
+    /// Returns the owner of the token at the beginning of the block at given height (before any changes in this block).
+    /// Requires owner snapshots to be enabled.
+    #[returns(OwnerOfAtHeightResponse)]
+    OwnerOfAtHeight { token_id: String, height: u64 },
+
+    /// Returns the number of tokens held by owner at the beginning of the block at given height (before any changes in this block).
+    /// Requires owner snapshots to be enabled.
+    #[returns(BalanceAtHeightResponse)]
+    BalanceAtHeight { owner: String, height: u64 },
+
+    /// Returns the ICS-721 class data of this collection, as sent by a bridge in cross-chain transfers.
+    #[returns(ClassDataResponse)]
+    ClassData {},
+
+    /// Returns the ICS-721 token data (token uri and base64 encoded extension) of the given tokens.
+    #[returns(TokenDataResponse)]
+    TokenData { token_ids: Vec<String> },
+
+    /// Returns the ICS-721 bridge of a voucher collection.
+    #[returns(Option<Addr>)]
+    GetIcs721Bridge {},
+
+    /// Returns the tokens attached to the given token, from this or other collections.
+    #[returns(ChildrenResponse)]
+    Children {
+        token_id: String,
+        start_after: Option<ChildToken>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the owner of the top-most parent of the given token, who controls all tokens attached below it.
+    /// For tokens without parent, this is the owner of the token.
+    #[returns(RootOwnerResponse)]
+    RootOwner { token_id: String },
+
+    /// Returns the edition master, with its shared metadata and number of minted editions.
+    #[returns(EditionResponse<TNftExtension>)]
+    Edition { master_id: String },
+
+    /// Returns the token ids of all existing editions of a master, ordered by edition number.
+    #[returns(TokensResponse)]
+    EditionsOf {
+        master_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the shared extension of a template.
+    #[returns(TNftExtension)]
+    Template { template_id: String },
 }
 
 #[cw_serde]
@@ -591,6 +833,30 @@ impl From<RoyaltyInfo> for RoyaltyInfoResponse {
     }
 }
 
+/// Royalty query of cw2981 collections, see https://eips.ethereum.org/EIPS/eip-2981
+#[cw_serde]
+pub enum Cw2981RoyaltyQueryMsg {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    // Note that this must be the same denom as that passed in to RoyaltyInfo
+    // rounding up or down is at the discretion of the implementer
+    pub royalty_amount: Uint128,
+}
+
+/// Royalty owed on a sale, see `Cw721Calls::royalty`.
+#[cw_serde]
+pub struct RoyaltyPayment {
+    pub recipient: Addr,
+    pub amount: Uint128,
+}
+
 /// This is a wrapper around CollectionInfo that includes the extension.
 #[cw_serde]
 pub struct ConfigResponse<TCollectionExtension> {
@@ -805,6 +1071,138 @@ pub struct OwnerOfResponse {
     pub owner: String,
     /// If set this address is approved to transfer/send the token as well
     pub approvals: Vec<Approval>,
+    /// Two-step transfer awaiting recipient's acceptance, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_transfer: Option<PendingTransfer>,
+}
+
+/// Mint voucher signed off-chain by the minter, allowing anyone to mint the NFT.
+#[cw_serde]
+pub struct MintVoucher<TNftExtensionMsg> {
+    pub token_id: String,
+    /// Owner of the NFT. If none, the voucher can be redeemed by anyone, becoming the owner.
+    pub owner: Option<String>,
+    pub token_uri: Option<String>,
+    pub extension: TNftExtensionMsg,
+    /// Price to be paid by the redeemer, if any.
+    pub price: Option<Coin>,
+    /// Voucher can't be redeemed after expiration.
+    pub expires: Option<Expiration>,
+    /// Any nonce, unique per voucher. Each nonce can only be redeemed once.
+    pub nonce: u64,
+}
+
+/// Canonical payload signed by the minter for a `MintWithVoucher`.
+#[cw_serde]
+pub struct MintVoucherPayload<TNftExtensionMsg> {
+    /// Prevents replaying voucher on other chains.
+    pub chain_id: String,
+    /// Prevents replaying voucher on other collections.
+    pub contract: String,
+    pub voucher: MintVoucher<TNftExtensionMsg>,
+}
+
+/// Canonical payload signed by the owner for a `Permit`.
+#[cw_serde]
+pub struct PermitPayload {
+    /// Prevents replaying permit on other chains.
+    pub chain_id: String,
+    /// Prevents replaying permit on other collections.
+    pub contract: String,
+    pub token_id: String,
+    pub spender: String,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct UserOfResponse {
+    /// Active user of the token
+    pub user: Option<String>,
+    /// When usage rights of user end
+    pub expires: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct OwnerOfAtHeightResponse {
+    /// None in case the token didn't exist at this height.
+    pub owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct BalanceAtHeightResponse {
+    pub balance: u64,
+}
+
+/// ICS-721 class data, base64 encoded in `ClassDataResponse`.
+#[cw_serde]
+pub struct ClassData<TCollectionExtension> {
+    pub name: String,
+    pub symbol: String,
+    pub num_tokens: u64,
+    pub extension: TCollectionExtension,
+}
+
+#[cw_serde]
+pub struct ClassDataResponse {
+    /// Class id of a collection native to this chain, which is the contract address.
+    pub class_id: String,
+    /// Base64 encoded JSON of `ClassData`.
+    pub class_data: Binary,
+}
+
+#[cw_serde]
+pub struct TokenData {
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    /// Base64 encoded JSON of the NFT extension.
+    pub token_data: Binary,
+}
+
+#[cw_serde]
+pub struct TokenDataResponse {
+    pub tokens: Vec<TokenData>,
+}
+
+/// Sent as msg in `SendNft` for attaching a token of another collection to a token of this collection.
+#[cw_serde]
+pub struct AttachTokenMsg {
+    pub parent_token_id: String,
+}
+
+#[cw_serde]
+pub struct ChildToken {
+    pub collection: Addr,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct ChildrenResponse {
+    pub children: Vec<ChildToken>,
+}
+
+#[cw_serde]
+pub struct RootOwnerResponse {
+    pub owner: String,
+    /// The top-most parent, or the token itself if it has no parent.
+    pub root_token_id: String,
+}
+
+#[cw_serde]
+pub struct EditionResponse<TNftExtension> {
+    pub master_id: String,
+    pub token_uri: Option<String>,
+    pub extension: TNftExtension,
+    pub max_size: u64,
+    /// Number of editions minted so far, including burned ones.
+    pub minted: u64,
+}
+
+#[cw_serde]
+pub struct IsLockedResponse {
+    pub locked: bool,
+    /// Set in case an unlock has been requested and not yet taken effect
+    pub unlocks_at: Option<Expiration>,
 }
 
 #[cw_serde]
@@ -827,6 +1225,17 @@ pub struct OperatorsResponse {
     pub operators: Vec<Approval>,
 }
 
+#[cw_serde]
+pub struct MetadataEditor {
+    pub editor: Addr,
+    pub scope: MetadataEditorScope,
+}
+
+#[cw_serde]
+pub struct MetadataEditorsResponse {
+    pub editors: Vec<MetadataEditor>,
+}
+
 #[cw_serde]
 pub struct NumTokensResponse {
     pub count: u64,
@@ -842,6 +1251,14 @@ pub struct NftInfoResponse<TNftExtension> {
     pub extension: TNftExtension,
 }
 
+/// Lenient view on an NFT extension of any type, reading its attributes only and ignoring all other fields.
+/// E.g. for querying `NftInfoResponse<Option<NftAttributes>>` of collections with unknown extension types.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NftAttributes {
+    #[serde(default)]
+    pub attributes: Option<Vec<Trait>>,
+}
+
 #[cw_serde]
 pub struct AllNftInfoResponse<TNftExtension> {
     /// Who can transfer the token
@@ -918,6 +1335,8 @@ where
                     approvals: vec![],
                     token_uri,
                     extension,
+                    template_id: None,
+                    edition: None,
                 })
             }
         }
@@ -935,8 +1354,8 @@ where
             // current is none: only minter can create new NFT
             assert_minter(deps.storage, &info.sender)?;
         } else {
-            // current is some: only creator can update NFT
-            assert_creator(deps.storage, &info.sender)?;
+            // current is some: only creator or metadata editor can update NFT
+            assert_creator_or_metadata_editor(deps.storage, &info.sender, None)?;
         }
         // validate token_uri is a URL
         let token_uri = empty_as_none(self.token_uri.clone());
@@ -1053,7 +1472,7 @@ impl StateFactory<NftExtension> for NftExtensionMsg {
     ) -> Result<(), Cw721ContractError> {
         // assert here is different to NFT Info:
         // - creator and minter can create NFT metadata
-        // - only creator can update NFT metadata
+        // - only creator or metadata editor can update NFT metadata
         if current.is_none() {
             let info = info.ok_or(Cw721ContractError::NoInfo)?;
             // current is none: minter and creator can create new NFT metadata
@@ -1064,8 +1483,9 @@ impl StateFactory<NftExtension> for NftExtensionMsg {
             }
         } else {
             let info = info.ok_or(Cw721ContractError::NoInfo)?;
-            // current is some: only creator can update NFT metadata
-            assert_creator(deps.storage, &info.sender)?;
+            // current is some: only creator or metadata editor can update NFT metadata
+            // NOTE: editor's scope is checked in `update_nft_info()`, since token id is unknown here
+            assert_creator_or_metadata_editor(deps.storage, &info.sender, None)?;
         }
         // check URLs
         let image = empty_as_none(self.image.clone());
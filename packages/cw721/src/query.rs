@@ -1,9 +1,10 @@
 use cosmwasm_std::{
-    Addr, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError, StdResult, Storage,
+    to_json_binary, Addr, Binary, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError,
+    StdResult, Storage,
 };
 use cw_ownable::Ownership;
 use cw_storage_plus::Bound;
-use cw_utils::{maybe_addr, Expiration};
+use cw_utils::{maybe_addr, Duration, Expiration};
 
 use crate::{
     error::Cw721ContractError,
@@ -12,14 +13,20 @@ use crate::{
     },
     msg::{
         AllInfoResponse, AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, ConfigResponse, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        BalanceAtHeightResponse, ChildToken, ChildrenResponse, ClassData, ClassDataResponse,
+        CollectionInfoAndExtensionResponse, ConfigResponse, EditionResponse, IsLockedResponse,
+        MetadataEditor, MetadataEditorsResponse, MinterResponse, NftInfoResponse,
+        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfAtHeightResponse,
+        OwnerOfResponse, RootOwnerResponse, TokenData, TokenDataResponse, TokensResponse,
+        UserOfResponse,
     },
     state::{
-        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, NftInfo, CREATOR,
-        MINTER,
+        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, MetadataEditorScope,
+        NftInfo, PendingTransfer, CREATOR, MINTER,
+    },
+    traits::{
+        Contains, Cw721CustomMsg, Cw721Query, Cw721State, FromAttributesState, WithOverrides,
     },
-    traits::{Contains, Cw721CustomMsg, Cw721Query, Cw721State, FromAttributesState},
     DefaultOptionalCollectionExtension, DefaultOptionalNftExtension,
     EmptyOptionalCollectionExtension, EmptyOptionalNftExtension,
 };
@@ -159,11 +166,11 @@ pub fn query_nft_info<TNftExtension>(
     token_id: String,
 ) -> StdResult<NftInfoResponse<TNftExtension>>
 where
-    TNftExtension: Cw721State,
+    TNftExtension: Cw721State + WithOverrides,
 {
-    let info = Cw721Config::<TNftExtension>::default()
-        .nft_info
-        .load(storage, &token_id)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let info = config.nft_info.load(storage, &token_id)?;
+    let info = config.materialize_nft_info(storage, info)?;
     Ok(NftInfoResponse {
         token_uri: info.token_uri,
         extension: info.extension,
@@ -177,17 +184,18 @@ pub fn query_nft_by_extension<TNftExtension>(
     limit: Option<u32>,
 ) -> StdResult<Option<Vec<NftInfoResponse<TNftExtension>>>>
 where
-    TNftExtension: Cw721State + Contains,
+    TNftExtension: Cw721State + Contains + WithOverrides,
 {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
-    let nfts: Vec<Option<NftInfo<TNftExtension>>> = Cw721Config::<TNftExtension>::default()
+    let config = Cw721Config::<TNftExtension>::default();
+    let nfts: Vec<Option<NftInfo<TNftExtension>>> = config
         .nft_info
         .range(storage, start, None, Order::Ascending)
         .take(limit)
         .map(|kv| {
-            let nft = kv?.1;
+            let nft = config.materialize_nft_info(storage, kv?.1)?;
             let result = if nft.extension.contains(&extension) {
                 Some(nft)
             } else {
@@ -223,9 +231,41 @@ pub fn query_owner_of(
     Ok(OwnerOfResponse {
         owner: nft_info.owner.to_string(),
         approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
+        pending_transfer: query_pending_transfer(
+            deps.storage,
+            &env.block,
+            &token_id,
+            include_expired_approval,
+        )?,
+    })
+}
+
+pub fn query_user_of(deps: Deps, env: &Env, token_id: String) -> StdResult<UserOfResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // ensure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+    let token_user = config
+        .token_users
+        .may_load(deps.storage, &token_id)?
+        .filter(|u| !u.is_expired(&env.block));
+    Ok(UserOfResponse {
+        user: token_user.as_ref().map(|u| u.user.to_string()),
+        expires: token_user.map(|u| u.expires),
     })
 }
 
+pub fn query_pending_transfer(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    token_id: &str,
+    include_expired: bool,
+) -> StdResult<Option<PendingTransfer>> {
+    let pending = Cw721Config::<Option<Empty>>::default()
+        .pending_transfers
+        .may_load(storage, token_id)?;
+    Ok(pending.filter(|p| include_expired || !p.is_expired(block)))
+}
+
 /// operator returns the approval status of an operator for a given owner if exists
 pub fn query_operator(
     deps: Deps,
@@ -396,15 +436,21 @@ pub fn query_all_nft_info<TNftExtension>(
     include_expired_approval: bool,
 ) -> StdResult<AllNftInfoResponse<TNftExtension>>
 where
-    TNftExtension: Cw721State,
+    TNftExtension: Cw721State + WithOverrides,
 {
-    let nft_info = Cw721Config::<TNftExtension>::default()
-        .nft_info
-        .load(deps.storage, &token_id)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let nft_info = config.nft_info.load(deps.storage, &token_id)?;
+    let nft_info = config.materialize_nft_info(deps.storage, nft_info)?;
     Ok(AllNftInfoResponse {
         access: OwnerOfResponse {
             owner: nft_info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
+            pending_transfer: query_pending_transfer(
+                deps.storage,
+                &env.block,
+                &token_id,
+                include_expired_approval,
+            )?,
         },
         info: NftInfoResponse {
             token_uri: nft_info.token_uri,
@@ -413,12 +459,256 @@ where
     })
 }
 
+pub fn query_is_locked(deps: Deps, env: &Env, token_id: String) -> StdResult<IsLockedResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // ensure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+    let lock = config
+        .token_locks
+        .may_load(deps.storage, &token_id)?
+        .filter(|lock| lock.is_locked(&env.block));
+    Ok(IsLockedResponse {
+        locked: lock.is_some(),
+        unlocks_at: lock.and_then(|lock| lock.unlocks_at),
+    })
+}
+
+pub fn query_voucher_signer(storage: &dyn Storage) -> StdResult<Option<Binary>> {
+    Cw721Config::<Option<Empty>>::default()
+        .voucher_signer
+        .may_load(storage)
+}
+
+pub fn query_is_voucher_nonce_used(storage: &dyn Storage, nonce: u64) -> StdResult<bool> {
+    Ok(Cw721Config::<Option<Empty>>::default()
+        .used_voucher_nonces
+        .has(storage, nonce))
+}
+
+pub fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<u64> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    Ok(Cw721Config::<Option<Empty>>::default()
+        .permit_nonces
+        .may_load(deps.storage, &owner_addr)?
+        .unwrap_or_default())
+}
+
+pub fn query_unlock_delay(storage: &dyn Storage) -> StdResult<Duration> {
+    Cw721Config::<Option<Empty>>::default().unlock_delay(storage)
+}
+
+pub fn query_owner_of_at_height(
+    storage: &dyn Storage,
+    token_id: String,
+    height: u64,
+) -> StdResult<OwnerOfAtHeightResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    assert_owner_snapshots_enabled(storage)?;
+    let owner = config
+        .owner_snapshots
+        .may_load_at_height(storage, &token_id, height)?;
+    Ok(OwnerOfAtHeightResponse { owner })
+}
+
+pub fn query_balance_at_height(
+    deps: Deps,
+    owner: String,
+    height: u64,
+) -> StdResult<BalanceAtHeightResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    assert_owner_snapshots_enabled(deps.storage)?;
+    let balance = config
+        .balance_snapshots
+        .may_load_at_height(deps.storage, &owner_addr, height)?
+        .unwrap_or_default();
+    Ok(BalanceAtHeightResponse { balance })
+}
+
+fn assert_owner_snapshots_enabled(storage: &dyn Storage) -> StdResult<()> {
+    if !Cw721Config::<Option<Empty>>::default().owner_snapshots_enabled(storage)? {
+        return Err(StdError::generic_err("Owner snapshots are not enabled"));
+    }
+    Ok(())
+}
+
 pub fn query_withdraw_address(deps: Deps) -> StdResult<Option<String>> {
     Cw721Config::<Option<Empty>>::default()
         .withdraw_address
         .may_load(deps.storage)
 }
 
+pub fn query_metadata_editor(deps: Deps, editor: String) -> StdResult<Option<MetadataEditorScope>> {
+    let editor_addr = deps.api.addr_validate(&editor)?;
+    Cw721Config::<Option<Empty>>::default()
+        .metadata_editors
+        .may_load(deps.storage, &editor_addr)
+}
+
+pub fn query_metadata_editors(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MetadataEditorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let editors = Cw721Config::<Option<Empty>>::default()
+        .metadata_editors
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(editor, scope)| MetadataEditor { editor, scope }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(MetadataEditorsResponse { editors })
+}
+
+pub fn query_class_data<TCollectionExtension>(
+    deps: Deps,
+    env: &Env,
+) -> Result<ClassDataResponse, Cw721ContractError>
+where
+    TCollectionExtension: Cw721State + FromAttributesState,
+{
+    let collection = query_collection_info_and_extension::<TCollectionExtension>(deps)?;
+    let class_data = ClassData {
+        name: collection.name,
+        symbol: collection.symbol,
+        num_tokens: query_num_tokens(deps.storage)?.count,
+        extension: collection.extension,
+    };
+    Ok(ClassDataResponse {
+        class_id: env.contract.address.to_string(),
+        class_data: to_json_binary(&class_data)?,
+    })
+}
+
+pub fn query_token_data<TNftExtension>(
+    storage: &dyn Storage,
+    token_ids: Vec<String>,
+) -> StdResult<TokenDataResponse>
+where
+    TNftExtension: Cw721State + WithOverrides,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let tokens = token_ids
+        .into_iter()
+        .map(|token_id| {
+            let info = config.nft_info.load(storage, &token_id)?;
+            let info = config.materialize_nft_info(storage, info)?;
+            Ok(TokenData {
+                token_id,
+                token_uri: info.token_uri,
+                token_data: to_json_binary(&info.extension)?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokenDataResponse { tokens })
+}
+
+pub fn query_ics721_bridge(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    Cw721Config::<Option<Empty>>::default()
+        .ics721_bridge
+        .may_load(storage)
+}
+
+pub fn query_children(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<ChildToken>,
+    limit: Option<u32>,
+) -> StdResult<ChildrenResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|child| Bound::exclusive((&child.collection, child.token_id.as_str())));
+
+    let children = Cw721Config::<Option<Empty>>::default()
+        .token_children
+        .prefix(&token_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(collection, token_id)| ChildToken {
+                collection,
+                token_id,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ChildrenResponse { children })
+}
+
+pub fn query_root_owner(deps: Deps, env: &Env, token_id: String) -> StdResult<RootOwnerResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let mut root_token_id = token_id;
+    while let Some(parent_token_id) = config
+        .token_parents
+        .may_load(deps.storage, (&env.contract.address, &root_token_id))?
+    {
+        root_token_id = parent_token_id;
+    }
+    let owner = config.nft_info.load(deps.storage, &root_token_id)?.owner;
+    Ok(RootOwnerResponse {
+        owner: owner.to_string(),
+        root_token_id,
+    })
+}
+
+pub fn query_edition<TNftExtension>(
+    deps: Deps,
+    master_id: String,
+) -> StdResult<EditionResponse<TNftExtension>>
+where
+    TNftExtension: Cw721State,
+{
+    let master = Cw721Config::<TNftExtension>::default()
+        .edition_masters
+        .load(deps.storage, &master_id)?;
+    Ok(EditionResponse {
+        master_id,
+        token_uri: master.token_uri,
+        extension: master.extension,
+        max_size: master.max_size,
+        minted: master.minted,
+    })
+}
+
+/// Returns the token ids of existing editions, skipping burned ones.
+pub fn query_editions_of(
+    deps: Deps,
+    master_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let config = Cw721Config::<Option<Empty>>::default();
+    // unknown masters are an error, not an empty list
+    config.edition_masters.load(deps.storage, &master_id)?;
+    let start = start_after.map(Bound::exclusive);
+    let tokens = config
+        .editions
+        .prefix(&master_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|edition_number| {
+            edition_number.map(|edition_number| format!("{}/{}", master_id, edition_number))
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_template<TNftExtension>(
+    storage: &dyn Storage,
+    template_id: String,
+) -> StdResult<TNftExtension>
+where
+    TNftExtension: Cw721State,
+{
+    Cw721Config::<TNftExtension>::default()
+        .templates
+        .load(storage, &template_id)
+}
+
 impl<'a> Cw721Query<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>
     for Cw721OnchainExtensions<'a>
 {
@@ -455,7 +745,7 @@ impl<
         TCustomResponseMsg,
     >
 where
-    TNftExtension: Cw721State + Contains,
+    TNftExtension: Cw721State + Contains + WithOverrides,
     TNftExtensionMsg: Cw721CustomMsg,
     TCollectionExtension: Cw721State + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg,
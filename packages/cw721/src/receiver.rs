@@ -36,9 +36,43 @@ impl Cw721ReceiveMsg {
     }
 }
 
-/// This is just a helper to properly serialize the above message.
-/// The actual receiver should include this variant in the larger ExecuteMsg enum
+/// Cw721BatchReceiveMsg should be de/serialized under `ReceiveNftBatch()` variant in a ExecuteMsg
+#[cw_serde]
+pub struct Cw721BatchReceiveMsg {
+    pub sender: String,
+    pub token_ids: Vec<String>,
+    pub msg: Binary,
+}
+
+impl Cw721BatchReceiveMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::ReceiveNftBatch(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<TAddress: Into<String>, TCustomResponseMsg>(
+        self,
+        contract_addr: TAddress,
+    ) -> StdResult<CosmosMsg<TCustomResponseMsg>>
+    where
+        TCustomResponseMsg: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+/// This is just a helper to properly serialize the above messages.
+/// The actual receiver should include these variants in the larger ExecuteMsg enum
 #[cw_serde]
 pub enum ReceiverExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
+    ReceiveNftBatch(Cw721BatchReceiveMsg),
 }
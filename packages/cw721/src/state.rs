@@ -4,12 +4,14 @@ use cosmwasm_std::{
     StdResult, Storage, Timestamp,
 };
 use cw_ownable::{OwnershipStore, OWNERSHIP};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
-use cw_utils::Expiration;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration};
 use serde::de::DeserializeOwned;
 
 use crate::error::Cw721ContractError;
-use crate::traits::{Contains, Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState};
+use crate::traits::{
+    Contains, Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState, WithOverrides,
+};
 use crate::{traits::StateFactory, NftExtensionMsg};
 
 /// Creator owns this contract and can update collection info!
@@ -32,6 +34,10 @@ pub const MAX_COLLECTION_DESCRIPTION_LENGTH: u32 = 512;
 pub const MAX_ROYALTY_SHARE_DELTA_PCT: u64 = 2;
 /// Max royalty share percentage.
 pub const MAX_ROYALTY_SHARE_PCT: u64 = 10;
+/// Default delay until an unlock of a token takes effect (1 day).
+pub const DEFAULT_UNLOCK_DELAY: Duration = Duration::Time(86400);
+/// Trait type of the edition number, added to the metadata of editions.
+pub const EDITION_NUMBER_TRAIT: &str = "edition_number";
 // ----------------------
 pub const ATTRIBUTE_DESCRIPTION: &str = "description";
 pub const ATTRIBUTE_IMAGE: &str = "image";
@@ -57,6 +63,40 @@ pub struct Cw721Config<
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub nft_info: IndexedMap<'a, &'a str, NftInfo<TNftExtension>, TokenIndexes<'a, TNftExtension>>,
     pub withdraw_address: Item<'a, String>,
+    /// Stored as (editor, scope), granted by creator for updating NFT metadata (`NftInfo.token_uri` and `NftInfo.extension`).
+    pub metadata_editors: Map<'a, &'a Addr, MetadataEditorScope>,
+    /// Stored as (token id, pending transfer), proposed by owner (or approved spender/operator) and awaiting recipient's acceptance.
+    pub pending_transfers: Map<'a, &'a str, PendingTransfer>,
+    /// Stored as (token id, lock), set by NFT owner for protecting a token against being transferred or approved.
+    pub token_locks: Map<'a, &'a str, TokenLock>,
+    /// Delay until an `UnlockToken` takes effect, set by creator. Defaults to `DEFAULT_UNLOCK_DELAY`.
+    pub unlock_delay: Item<'a, Duration>,
+    /// Stored as (token id, user), set by NFT owner (or approved spender/operator) for renting a token without transferring ownership.
+    pub token_users: Map<'a, &'a str, TokenUser>,
+    /// Stored as (owner, nonce), the next nonce expected in a permit signed by owner.
+    pub permit_nonces: Map<'a, &'a Addr, u64>,
+    /// Minter's public key for verifying mint vouchers.
+    pub voucher_signer: Item<'a, Binary>,
+    /// Nonces of redeemed mint vouchers, preventing replay.
+    pub used_voucher_nonces: Map<'a, u64, Empty>,
+    /// Opt-in, set by creator: whether ownership is snapshotted by block height (e.g. for DAO votes).
+    pub owner_snapshots_enabled: Item<'a, bool>,
+    /// Stored as (token id, owner), snapshotted on every mint, transfer and burn.
+    pub owner_snapshots: SnapshotMap<'a, &'a str, Addr>,
+    /// Stored as (owner, number of tokens), snapshotted on every mint, transfer and burn.
+    pub balance_snapshots: SnapshotMap<'a, &'a Addr, u64>,
+    /// Set on instantiation of an ICS-721 voucher collection: the bridge contract is the only minter and burner.
+    pub ics721_bridge: Item<'a, Addr>,
+    /// Stored as ((child collection, child token id), parent token id) for tokens attached to a token of this collection.
+    pub token_parents: Map<'a, (&'a Addr, &'a str), String>,
+    /// Stored as ((parent token id, (child collection, child token id)), empty), the reverse of `token_parents`.
+    pub token_children: Map<'a, (&'a str, (&'a Addr, &'a str)), Empty>,
+    /// Stored as (master id, edition master), registered by creator for minting numbered editions sharing the same metadata.
+    pub edition_masters: Map<'a, &'a str, EditionMaster<TNftExtension>>,
+    /// Stored as ((master id, edition number), empty) for existing editions, removed on burn.
+    pub editions: Map<'a, (&'a str, u64), Empty>,
+    /// Stored as (template id, extension), shared metadata referenced by `NftInfo.template_id`.
+    pub templates: Map<'a, &'a str, TNftExtension>,
 }
 
 impl<TNftExtension> Default for Cw721Config<'static, TNftExtension>
@@ -73,6 +113,27 @@ where
             "tokens",
             "tokens__owner",
             "withdraw_address",
+            "metadata_editors",
+            "pending_transfers",
+            "token_locks",
+            "unlock_delay",
+            "token_users",
+            "permit_nonces",
+            "voucher_signer",
+            "used_voucher_nonces",
+            "owner_snapshots_enabled",
+            "owner_snapshots",
+            "owner_snapshots__checkpoints",
+            "owner_snapshots__changelog",
+            "balance_snapshots",
+            "balance_snapshots__checkpoints",
+            "balance_snapshots__changelog",
+            "ics721_bridge",
+            "token_parents",
+            "token_children",
+            "edition_masters",
+            "editions",
+            "templates",
         )
     }
 }
@@ -81,6 +142,7 @@ impl<'a, TNftExtension> Cw721Config<'a, TNftExtension>
 where
     TNftExtension: Cw721State,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         collection_info_key: &'a str,
         collection_info_extension_key: &'a str,
@@ -89,6 +151,27 @@ where
         nft_info_key: &'a str,
         nft_info_owner_key: &'a str,
         withdraw_address_key: &'a str,
+        metadata_editors_key: &'a str,
+        pending_transfers_key: &'a str,
+        token_locks_key: &'a str,
+        unlock_delay_key: &'a str,
+        token_users_key: &'a str,
+        permit_nonces_key: &'a str,
+        voucher_signer_key: &'a str,
+        used_voucher_nonces_key: &'a str,
+        owner_snapshots_enabled_key: &'a str,
+        owner_snapshots_key: &'a str,
+        owner_snapshots_checkpoints_key: &'a str,
+        owner_snapshots_changelog_key: &'a str,
+        balance_snapshots_key: &'a str,
+        balance_snapshots_checkpoints_key: &'a str,
+        balance_snapshots_changelog_key: &'a str,
+        ics721_bridge_key: &'a str,
+        token_parents_key: &'a str,
+        token_children_key: &'a str,
+        edition_masters_key: &'a str,
+        editions_key: &'a str,
+        templates_key: &'a str,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, nft_info_key, nft_info_owner_key),
@@ -100,7 +183,125 @@ where
             nft_info: IndexedMap::new(nft_info_key, indexes),
             withdraw_address: Item::new(withdraw_address_key),
             collection_extension: Map::new(collection_info_extension_key),
+            metadata_editors: Map::new(metadata_editors_key),
+            pending_transfers: Map::new(pending_transfers_key),
+            token_locks: Map::new(token_locks_key),
+            unlock_delay: Item::new(unlock_delay_key),
+            token_users: Map::new(token_users_key),
+            permit_nonces: Map::new(permit_nonces_key),
+            voucher_signer: Item::new(voucher_signer_key),
+            used_voucher_nonces: Map::new(used_voucher_nonces_key),
+            owner_snapshots_enabled: Item::new(owner_snapshots_enabled_key),
+            owner_snapshots: SnapshotMap::new(
+                owner_snapshots_key,
+                owner_snapshots_checkpoints_key,
+                owner_snapshots_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            balance_snapshots: SnapshotMap::new(
+                balance_snapshots_key,
+                balance_snapshots_checkpoints_key,
+                balance_snapshots_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            ics721_bridge: Item::new(ics721_bridge_key),
+            token_parents: Map::new(token_parents_key),
+            token_children: Map::new(token_children_key),
+            edition_masters: Map::new(edition_masters_key),
+            editions: Map::new(editions_key),
+            templates: Map::new(templates_key),
+        }
+    }
+
+    pub fn owner_snapshots_enabled(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self
+            .owner_snapshots_enabled
+            .may_load(storage)?
+            .unwrap_or(false))
+    }
+
+    /// Snapshots an ownership change of a token at the given height: `previous_owner` is none on mint,
+    /// and `new_owner` is none on burn. Does nothing, unless owner snapshots are enabled.
+    pub fn snapshot_owner_change(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        token_id: &str,
+        previous_owner: Option<&Addr>,
+        new_owner: Option<&Addr>,
+    ) -> StdResult<()> {
+        if !self.owner_snapshots_enabled(storage)? {
+            return Ok(());
+        }
+        match new_owner {
+            Some(owner) => self
+                .owner_snapshots
+                .save(storage, token_id, owner, height)?,
+            None => self.owner_snapshots.remove(storage, token_id, height)?,
+        }
+        if let Some(owner) = previous_owner {
+            let balance = self
+                .balance_snapshots
+                .may_load(storage, owner)?
+                .unwrap_or_default();
+            self.balance_snapshots
+                .save(storage, owner, &balance.saturating_sub(1), height)?;
+        }
+        if let Some(owner) = new_owner {
+            let balance = self
+                .balance_snapshots
+                .may_load(storage, owner)?
+                .unwrap_or_default();
+            self.balance_snapshots
+                .save(storage, owner, &(balance + 1), height)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the token with its extension merged into its template or edition master (if any), as returned
+    /// by queries.
+    pub fn materialize_nft_info(
+        &self,
+        storage: &dyn Storage,
+        mut nft_info: NftInfo<TNftExtension>,
+    ) -> StdResult<NftInfo<TNftExtension>>
+    where
+        TNftExtension: WithOverrides,
+    {
+        if let Some(template_id) = &nft_info.template_id {
+            let template = self.templates.load(storage, template_id)?;
+            nft_info.extension = template.with_overrides(&nft_info.extension);
         }
+        if let Some(edition) = &nft_info.edition {
+            let master = self.edition_masters.load(storage, &edition.master_id)?;
+            nft_info.token_uri = nft_info.token_uri.or(master.token_uri);
+            nft_info.extension = master
+                .extension
+                .with_overrides(&nft_info.extension)
+                .with_edition_number(edition.number);
+        }
+        Ok(nft_info)
+    }
+
+    pub fn unlock_delay(&self, storage: &dyn Storage) -> StdResult<Duration> {
+        Ok(self
+            .unlock_delay
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_UNLOCK_DELAY))
+    }
+
+    /// Returns true if the token is locked and its unlock (if any) has not taken effect yet.
+    pub fn is_locked(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+    ) -> StdResult<bool> {
+        Ok(self
+            .token_locks
+            .may_load(storage, token_id)?
+            .map(|lock| lock.is_locked(block))
+            .unwrap_or(false))
     }
 
     pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
@@ -137,7 +338,36 @@ pub struct NftInfo<TNftExtension> {
     pub token_uri: Option<String>,
 
     /// You can add any custom metadata here when you extend cw721-base
+    /// For tokens with a template, this only holds the per-token overrides.
     pub extension: TNftExtension,
+
+    /// Template with the shared extension of this token, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+
+    /// Edition master with the shared token uri and extension of this token, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edition: Option<Edition>,
+}
+
+/// Shared metadata of numbered editions, e.g. "1 of 100" prints of one artwork.
+#[cw_serde]
+pub struct EditionMaster<TNftExtension> {
+    /// Token uri of all editions.
+    pub token_uri: Option<String>,
+    /// Extension of all editions, with the edition number added on queries.
+    pub extension: TNftExtension,
+    /// Maximum number of editions.
+    pub max_size: u64,
+    /// Number of editions minted so far, including burned ones.
+    pub minted: u64,
+}
+
+/// Reference of an edition to its edition master.
+#[cw_serde]
+pub struct Edition {
+    pub master_id: String,
+    pub number: u64,
 }
 
 #[cw_serde]
@@ -154,6 +384,74 @@ impl Approval {
     }
 }
 
+/// A two-step transfer, where ownership only moves once `recipient` accepts it.
+#[cw_serde]
+pub struct PendingTransfer {
+    /// Account that proposed the transfer, must still be eligible to send the token on accept.
+    pub proposer: Addr,
+    /// Account eligible to accept the transfer.
+    pub recipient: Addr,
+    /// When the proposal expires and can no longer be accepted.
+    pub expires: Expiration,
+}
+
+impl PendingTransfer {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+/// A user (e.g. renter) of a token, having usage rights but not ownership (ERC-4907).
+#[cw_serde]
+pub struct TokenUser {
+    pub user: Addr,
+    /// When usage rights end.
+    pub expires: Expiration,
+}
+
+impl TokenUser {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+/// Owner's lock on a token, preventing transfers and approvals.
+#[cw_serde]
+pub struct TokenLock {
+    /// Set once the owner requests an unlock. Token remains locked until then.
+    pub unlocks_at: Option<Expiration>,
+}
+
+impl TokenLock {
+    pub fn is_locked(&self, block: &BlockInfo) -> bool {
+        match self.unlocks_at {
+            Some(unlocks_at) => !unlocks_at.is_expired(block),
+            None => true,
+        }
+    }
+}
+
+/// Defines which NFTs a metadata editor is eligible to update.
+#[cw_serde]
+pub enum MetadataEditorScope {
+    /// Editor may update all NFTs in the collection.
+    All {},
+    /// Editor may only update NFTs with a token id starting with `prefix`.
+    TokenIdPrefix { prefix: String },
+    /// Editor may only update the given NFTs.
+    TokenIds { token_ids: Vec<String> },
+}
+
+impl MetadataEditorScope {
+    pub fn includes(&self, token_id: &str) -> bool {
+        match self {
+            MetadataEditorScope::All {} => true,
+            MetadataEditorScope::TokenIdPrefix { prefix } => token_id.starts_with(prefix.as_str()),
+            MetadataEditorScope::TokenIds { token_ids } => token_ids.iter().any(|t| t == token_id),
+        }
+    }
+}
+
 pub struct TokenIndexes<'a, TNftExtension>
 where
     TNftExtension: Cw721State,
@@ -350,6 +648,96 @@ where
     }
 }
 
+impl WithOverrides for Empty {
+    fn with_overrides(&self, _overrides: &Self) -> Self {
+        Empty {}
+    }
+}
+
+/// Fields set in the overrides replace the template's, and override attributes replace template attributes
+/// with the same trait type (other attributes are appended).
+impl WithOverrides for NftExtension {
+    fn with_overrides(&self, overrides: &Self) -> Self {
+        let attributes = match (&self.attributes, &overrides.attributes) {
+            (Some(attributes), Some(overrides)) => {
+                let mut merged: Vec<Trait> = attributes
+                    .iter()
+                    .filter(|a| !overrides.iter().any(|o| o.trait_type == a.trait_type))
+                    .cloned()
+                    .collect();
+                merged.extend(overrides.iter().cloned());
+                Some(merged)
+            }
+            (attributes, overrides) => overrides.clone().or_else(|| attributes.clone()),
+        };
+        NftExtension {
+            image: overrides.image.clone().or_else(|| self.image.clone()),
+            image_data: overrides
+                .image_data
+                .clone()
+                .or_else(|| self.image_data.clone()),
+            external_url: overrides
+                .external_url
+                .clone()
+                .or_else(|| self.external_url.clone()),
+            description: overrides
+                .description
+                .clone()
+                .or_else(|| self.description.clone()),
+            name: overrides.name.clone().or_else(|| self.name.clone()),
+            attributes,
+            background_color: overrides
+                .background_color
+                .clone()
+                .or_else(|| self.background_color.clone()),
+            animation_url: overrides
+                .animation_url
+                .clone()
+                .or_else(|| self.animation_url.clone()),
+            youtube_url: overrides
+                .youtube_url
+                .clone()
+                .or_else(|| self.youtube_url.clone()),
+        }
+    }
+
+    /// Adds an `edition_number` trait to the attributes.
+    fn with_edition_number(&self, edition_number: u64) -> Self {
+        let mut extension = self.clone();
+        extension
+            .attributes
+            .get_or_insert_with(Vec::new)
+            .push(Trait {
+                display_type: None,
+                trait_type: EDITION_NUMBER_TRAIT.to_string(),
+                value: edition_number.to_string(),
+            });
+        extension
+    }
+}
+
+impl<T> WithOverrides for Option<T>
+where
+    T: WithOverrides + Default,
+{
+    fn with_overrides(&self, overrides: &Self) -> Self {
+        match (self, overrides) {
+            (Some(template), Some(overrides)) => Some(template.with_overrides(overrides)),
+            (template, None) => template.clone(),
+            (None, overrides) => overrides.clone(),
+        }
+    }
+
+    /// Editions of a master without extension still get the edition number.
+    fn with_edition_number(&self, edition_number: u64) -> Self {
+        Some(
+            self.clone()
+                .unwrap_or_default()
+                .with_edition_number(edition_number),
+        )
+    }
+}
+
 #[cw_serde]
 pub struct Trait {
     pub display_type: Option<String>,
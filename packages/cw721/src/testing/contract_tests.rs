@@ -1,19 +1,30 @@
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Coin, CosmosMsg, DepsMut, Empty, Response, StdError,
-    Timestamp, WasmMsg,
+    from_json, to_json_binary, to_json_vec, Addr, Api, Binary, Coin, ContractInfoResponse,
+    ContractResult, CosmosMsg, DepsMut, Empty, Env, Response, StdError, SystemError, SystemResult,
+    Timestamp, WasmMsg, WasmQuery,
 };
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 use crate::error::Cw721ContractError;
+use crate::events::{
+    ApproveEvent, BurnEvent, Cw721Event, MetadataUpdateEvent, MintEvent, TransferEvent,
+};
 use crate::extension::Cw721OnchainExtensions;
 use crate::msg::{
-    ApprovalResponse, CollectionExtensionMsg, NftExtensionMsg, NftInfoResponse, OperatorResponse,
-    OperatorsResponse, OwnerOfResponse, RoyaltyInfoResponse,
+    ApprovalResponse, CollectionExtensionMsg, IsLockedResponse, MintVoucher, MintVoucherPayload,
+    NftExtensionMsg, NftInfoResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
+    PermitPayload, RoyaltyInfoResponse, UserOfResponse,
 };
 use crate::msg::{CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg};
 use crate::receiver::Cw721ReceiveMsg;
-use crate::state::{NftExtension, Trait, CREATOR, MINTER};
+use crate::state::{
+    Edition, MetadataEditorScope, NftExtension, PendingTransfer, Trait, CREATOR,
+    DEFAULT_UNLOCK_DELAY, MINTER,
+};
 use crate::{
     traits::{Cw721Execute, Cw721Query},
     Approval, DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtension,
@@ -21,6 +32,7 @@ use crate::{
 };
 use crate::{CollectionExtension, CollectionInfoAndExtensionResponse, RoyaltyInfo};
 use cw_ownable::{Action, Ownership, OwnershipError};
+use cw_utils::Duration;
 
 const MINTER_ADDR: &str = "minter";
 const CREATOR_ADDR: &str = "creator";
@@ -36,6 +48,7 @@ fn setup_contract(deps: DepsMut<'_>) -> Cw721OnchainExtensions<'static> {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: None,
+        ics721_bridge: None,
     };
     let info_creator = mock_info(CREATOR_ADDR, &[]);
     let res = contract
@@ -64,6 +77,7 @@ fn test_instantiate() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        ics721_bridge: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -139,6 +153,7 @@ fn test_instantiate_with_collection_info_and_extension() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        ics721_bridge: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -222,6 +237,7 @@ fn test_instantiate_with_minimal_collection_info_and_extension() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        ics721_bridge: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -340,6 +356,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -406,6 +423,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -458,6 +476,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -470,197 +489,651 @@ fn test_mint() {
 }
 
 #[test]
-fn test_update_nft_info() {
+fn test_mint_with_voucher() {
     let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
+    // voucher signer must derive to minter's bech32 address
+    deps.api = MockApi::default().with_prefix("cosmwasm");
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
 
-    let token_id = "1".to_string();
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("owner"),
-        token_uri: Some("ipfs://foo.bar".to_string()),
-        extension: None,
+    // minter's key and address
+    let signing_key = SigningKey::from_bytes(&[1u8; 32].into()).unwrap();
+    let pubkey = Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let minter_canonical = Ripemd160::digest(Sha256::digest(pubkey.as_slice())).to_vec();
+    let minter = deps.api.addr_humanize(&minter_canonical.into()).unwrap();
+    let creator = deps.api.addr_make(CREATOR_ADDR);
+    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(minter.to_string()),
+        creator: Some(creator.to_string()),
+        withdraw_address: None,
+        ics721_bridge: None,
     };
-
-    // mint nft
-    let info_minter = mock_info(MINTER_ADDR, &[]);
-    let env = mock_env();
     contract
-        .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+        .instantiate(deps.as_mut(), &env, &mock_info(creator.as_str(), &[]), msg)
         .unwrap();
 
-    // minter update unknown nft info
-    let update_msg = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: "unknown".to_string(),
-        token_uri: Some("ipfs://to.the.moon".to_string()),
-        extension: None,
+    // only minter can set voucher signer
+    let set_signer_msg = Cw721ExecuteMsg::SetVoucherSigner {
+        pubkey: pubkey.clone(),
     };
-    // throws NotFound error
-    contract
-        .execute(deps.as_mut(), &env, &info_minter, update_msg)
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(creator.as_str(), &[]),
+            set_signer_msg.clone(),
+        )
         .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(minter.as_str(), &[]),
+            set_signer_msg,
+        )
+        .unwrap();
 
-    // minter udpate nft info
-    let update_msg_without_extension = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: token_id.clone(),
-        token_uri: Some("".to_string()), // sets token uri to none
+    let voucher = MintVoucher {
+        token_id: "lazy".to_string(),
+        owner: None,
+        token_uri: None,
         extension: None,
+        price: Some(Coin::new(100, "uark")),
+        expires: Some(Expiration::AtHeight(env.block.height + 10)),
+        nonce: 42,
     };
+    let payload = MintVoucherPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        voucher: voucher.clone(),
+    };
+    let message_hash = Sha256::digest(to_json_vec(&payload).unwrap());
+    let signature: Signature = signing_key.sign_prehash(&message_hash).unwrap();
+    let mint_msg = Cw721ExecuteMsg::MintWithVoucher {
+        voucher: voucher.clone(),
+        signature: Binary::from(signature.to_bytes().as_slice()),
+    };
+
+    // price must be paid
+    let buyer = deps.api.addr_make("buyer");
     let err = contract
         .execute(
             deps.as_mut(),
             &env,
-            &info_minter,
-            update_msg_without_extension.clone(),
+            &mock_info(buyer.as_str(), &[]),
+            mint_msg.clone(),
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(
+        err,
+        Cw721ContractError::VoucherPriceNotPaid {
+            price: "100uark".to_string()
+        }
+    );
 
-    // other udpate nft metadata extension
-    let update_msg_only_extension = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: token_id.clone(),
-        token_uri: None,
-        extension: Some(NftExtensionMsg {
-            image: Some("ipfs://foo.bar/image.png".to_string()),
-            image_data: None,
-            external_url: None,
-            description: None,
-            name: None,
-            attributes: None,
-            background_color: None,
-            animation_url: None,
-            youtube_url: None,
-        }),
+    // voucher cannot be redeemed after expiration
+    let mut later = mock_env();
+    later.block.height += 10;
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &later,
+            &mock_info(buyer.as_str(), &[Coin::new(100, "uark")]),
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::VoucherExpired {});
+
+    // tampered voucher is rejected
+    let tampered_msg = Cw721ExecuteMsg::MintWithVoucher {
+        voucher: MintVoucher {
+            price: None,
+            ..voucher
+        },
+        signature: Binary::from(signature.to_bytes().as_slice()),
     };
-    let info_other = mock_info("other", &[]);
     let err = contract
         .execute(
             deps.as_mut(),
             &env,
-            &info_other,
-            update_msg_only_extension.clone(),
+            &mock_info(buyer.as_str(), &[]),
+            tampered_msg,
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(err, Cw721ContractError::InvalidVoucherSignature {});
 
-    // creator updates nft info
+    // anyone paying can redeem voucher without owner
     contract
         .execute(
             deps.as_mut(),
             &env,
-            &mock_info(CREATOR_ADDR, &[]),
-            update_msg_without_extension,
+            &mock_info(buyer.as_str(), &[Coin::new(100, "uark")]),
+            mint_msg.clone(),
+        )
+        .unwrap();
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "lazy".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, buyer.to_string());
+    assert!(contract
+        .query_is_voucher_nonce_used(deps.as_ref().storage, 42)
+        .unwrap());
+
+    // voucher cannot be replayed
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(buyer.as_str(), &[Coin::new(100, "uark")]),
+            mint_msg,
         )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::VoucherNonceUsed { nonce: 42 });
+}
+
+#[test]
+fn test_editions() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let register_msg = Cw721ExecuteMsg::RegisterEditionMaster {
+        master_id: "print".to_string(),
+        token_uri: Some("https://example.com/print.json".to_string()),
+        extension: Some(NftExtensionMsg {
+            name: Some("Print".to_string()),
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "artist".to_string(),
+                value: "alice".to_string(),
+            }]),
+            ..NftExtensionMsg::default()
+        }),
+        max_size: 2,
+    };
+
+    // only creator can register an edition master
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, register_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    contract
+        .execute(deps.as_mut(), &env, &creator, register_msg.clone())
         .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, register_msg)
+        .unwrap_err();
     assert_eq!(
+        err,
+        Cw721ContractError::EditionMasterExists {
+            master_id: "print".to_string()
+        }
+    );
+
+    // only minter can mint editions, numbered up to max size
+    let mint_msg = Cw721ExecuteMsg::MintEdition {
+        master_id: "print".to_string(),
+        owner: "owner".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, mint_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    for _ in 0..2 {
         contract
-            .query_nft_info(deps.as_ref().storage, token_id.clone())
-            .unwrap(),
-        NftInfoResponse {
-            token_uri: None,
-            extension: None,
+            .execute(deps.as_mut(), &env, &minter, mint_msg.clone())
+            .unwrap();
+    }
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::EditionsSoldOut {
+            master_id: "print".to_string()
         }
     );
 
-    // creator updates nft metadata extension
+    // shared metadata, with the edition number added
+    let res = contract
+        .query_nft_info(deps.as_ref().storage, "print/2".to_string())
+        .unwrap();
+    assert_eq!(
+        res.token_uri,
+        Some("https://example.com/print.json".to_string())
+    );
+    assert_eq!(
+        res.extension.unwrap().attributes,
+        Some(vec![
+            Trait {
+                display_type: None,
+                trait_type: "artist".to_string(),
+                value: "alice".to_string(),
+            },
+            Trait {
+                display_type: None,
+                trait_type: "edition_number".to_string(),
+                value: "2".to_string(),
+            },
+        ])
+    );
+    let res = contract
+        .query_edition(deps.as_ref(), "print".to_string())
+        .unwrap();
+    assert_eq!((res.max_size, res.minted), (2, 2));
+
+    // only the reference to the master is stored per token
+    let stored = contract
+        .config
+        .nft_info
+        .load(deps.as_ref().storage, "print/2")
+        .unwrap();
+    assert_eq!((stored.token_uri, stored.extension), (None, None));
+    assert_eq!(
+        stored.edition,
+        Some(Edition {
+            master_id: "print".to_string(),
+            number: 2,
+        })
+    );
+
+    // editions of a master without extension still get their edition number
     contract
         .execute(
             deps.as_mut(),
             &env,
-            &mock_info(CREATOR_ADDR, &[]),
-            update_msg_only_extension,
+            &creator,
+            Cw721ExecuteMsg::RegisterEditionMaster {
+                master_id: "plain".to_string(),
+                token_uri: None,
+                extension: None,
+                max_size: 1,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::MintEdition {
+                master_id: "plain".to_string(),
+                owner: "owner".to_string(),
+            },
         )
         .unwrap();
+    let res = contract
+        .query_nft_info(deps.as_ref().storage, "plain/1".to_string())
+        .unwrap();
     assert_eq!(
-        contract
-            .query_nft_info(deps.as_ref().storage, token_id)
-            .unwrap(),
-        NftInfoResponse {
-            token_uri: None,
-            extension: Some(NftExtension {
-                image: Some("ipfs://foo.bar/image.png".to_string()),
-                image_data: None,
-                external_url: None,
-                description: None,
-                name: None,
-                attributes: None,
-                background_color: None,
-                animation_url: None,
-                youtube_url: None,
-            }),
-        }
+        res.extension.unwrap().attributes,
+        Some(vec![Trait {
+            display_type: None,
+            trait_type: "edition_number".to_string(),
+            value: "1".to_string(),
+        }])
     );
+
+    // burned editions are not listed, and their numbers are not reused
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("owner", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "print/1".to_string(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_editions_of(deps.as_ref(), "print".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, vec!["print/2".to_string()]);
+    let res = contract
+        .query_editions_of(deps.as_ref(), "print".to_string(), None, Some(1))
+        .unwrap();
+    assert_eq!(res.tokens, vec!["print/2".to_string()]);
+    let res = contract
+        .query_editions_of(deps.as_ref(), "print".to_string(), Some(2), None)
+        .unwrap();
+    assert!(res.tokens.is_empty());
 }
 
 #[test]
-fn test_mint_with_metadata() {
-    // case 1: mint with valid metadata
-    {
-        let mut deps = mock_dependencies();
-        let contract = setup_contract(deps.as_mut());
+fn test_edition_ids_reserved() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: "owner".to_string(),
+        token_uri: None,
+        extension: None,
+    };
+    let register_msg = |master_id: &str| Cw721ExecuteMsg::RegisterEditionMaster {
+        master_id: master_id.to_string(),
+        token_uri: None,
+        extension: None,
+        max_size: 3,
+    };
 
-        let token_id = "1".to_string();
-        let token_uri = "ipfs://foo.bar".to_string();
-        let valid_extension_msg = NftExtensionMsg {
-            image: Some("ipfs://foo.bar/image.png".to_string()),
-            image_data: Some("image data".to_string()),
-            external_url: Some("https://github.com".to_string()),
-            description: Some("description".to_string()),
-            name: Some("name".to_string()),
-            attributes: Some(vec![Trait {
-                trait_type: "trait_type".to_string(),
-                value: "value".to_string(),
-                display_type: Some("display_type".to_string()),
-            }]),
-            background_color: Some("background_color".to_string()),
-            animation_url: Some("ssl://animation_url".to_string()),
-            youtube_url: Some("file://youtube_url".to_string()),
-        };
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri),
-            extension: Some(valid_extension_msg.clone()),
-        };
+    // masters can't be registered, once a token is minted with one of their edition ids
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("early/3"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, register_msg("early"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::EditionIdClaimed {
+            token_id: "early/3".to_string(),
+            master_id: "early".to_string(),
+        }
+    );
 
-        let info_minter = mock_info(MINTER_ADDR, &[]);
-        let env = mock_env();
+    // tokens can't be minted with edition ids of a registered master
+    contract
+        .execute(deps.as_mut(), &env, &creator, register_msg("print"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("print/3"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::EditionIdClaimed {
+            token_id: "print/3".to_string(),
+            master_id: "print".to_string(),
+        }
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetTemplate {
+                template_id: "ticket".to_string(),
+                extension: None,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::MintFromTemplate {
+                token_id: "print/3".to_string(),
+                owner: "owner".to_string(),
+                token_uri: None,
+                template_id: "ticket".to_string(),
+                overrides: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::EditionIdClaimed {
+            token_id: "print/3".to_string(),
+            master_id: "print".to_string(),
+        }
+    );
+
+    // other ids starting with the master id are fine
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("print/poster"))
+        .unwrap();
+
+    // all editions can still be minted
+    for _ in 0..3 {
         contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(
+                deps.as_mut(),
+                &env,
+                &minter,
+                Cw721ExecuteMsg::MintEdition {
+                    master_id: "print".to_string(),
+                    owner: "owner".to_string(),
+                },
+            )
             .unwrap();
-        assert_eq!(
-            contract
-                .query_nft_info(deps.as_ref().storage, token_id)
-                .unwrap(),
-            NftInfoResponse {
-                token_uri: Some("ipfs://foo.bar".to_string()),
-                extension: Some(valid_extension_msg.clone().into()),
+    }
+}
+
+#[test]
+fn test_templates() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let trait_of = |trait_type: &str, value: &str| Trait {
+        display_type: None,
+        trait_type: trait_type.to_string(),
+        value: value.to_string(),
+    };
+
+    // only creator can save a template
+    let template_msg = Cw721ExecuteMsg::SetTemplate {
+        template_id: "ticket".to_string(),
+        extension: Some(NftExtensionMsg {
+            name: Some("Ticket".to_string()),
+            description: Some("Concert ticket".to_string()),
+            attributes: Some(vec![
+                trait_of("event", "concert"),
+                trait_of("tier", "general"),
+            ]),
+            ..NftExtensionMsg::default()
+        }),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, template_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    contract
+        .execute(deps.as_mut(), &env, &creator, template_msg)
+        .unwrap();
+
+    let mint_msg =
+        |token_id: &str, template_id: &str, overrides: DefaultOptionalNftExtensionMsg| {
+            Cw721ExecuteMsg::MintFromTemplate {
+                token_id: token_id.to_string(),
+                owner: "owner".to_string(),
+                token_uri: None,
+                template_id: template_id.to_string(),
+                overrides,
             }
-        );
+        };
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1", "unknown", None))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TemplateNotFound {
+            template_id: "unknown".to_string()
+        }
+    );
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1", "ticket", None))
+        .unwrap();
+    let overrides = Some(NftExtensionMsg {
+        name: Some("VIP ticket".to_string()),
+        attributes: Some(vec![trait_of("tier", "vip")]),
+        ..NftExtensionMsg::default()
+    });
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            mint_msg("2", "ticket", overrides),
+        )
+        .unwrap();
 
-        // mint with empty token uri and empty extension
-        let mint_msg = Cw721ExecuteMsg::<
-            DefaultOptionalNftExtensionMsg,
-            DefaultOptionalCollectionExtensionMsg,
-            Empty,
-        >::Mint {
-            token_id: "2".to_string(),
-            owner: String::from("medusa"),
+    // only overrides are stored per token
+    let stored = contract
+        .config
+        .nft_info
+        .load(deps.as_ref().storage, "1")
+        .unwrap();
+    assert_eq!(stored.extension, None);
+    assert_eq!(stored.template_id, Some("ticket".to_string()));
+
+    // queries return the template merged with the overrides
+    let template = contract
+        .query_template(deps.as_ref().storage, "ticket".to_string())
+        .unwrap();
+    let res = contract
+        .query_nft_info(deps.as_ref().storage, "1".to_string())
+        .unwrap();
+    assert_eq!(res.extension, template);
+    let res = contract
+        .query_all_nft_info(deps.as_ref(), &env, "2".to_string(), false)
+        .unwrap();
+    let extension = res.info.extension.unwrap();
+    assert_eq!(extension.name, Some("VIP ticket".to_string()));
+    assert_eq!(extension.description, Some("Concert ticket".to_string()));
+    assert_eq!(
+        extension.attributes,
+        Some(vec![trait_of("event", "concert"), trait_of("tier", "vip")])
+    );
+}
+
+#[test]
+fn test_update_nft_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "1".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("owner"),
+        token_uri: Some("ipfs://foo.bar".to_string()),
+        extension: None,
+    };
+
+    // mint nft
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let env = mock_env();
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+        .unwrap();
+
+    // minter update unknown nft info
+    let update_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: "unknown".to_string(),
+        token_uri: Some("ipfs://to.the.moon".to_string()),
+        extension: None,
+    };
+    // throws NotFound error
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, update_msg)
+        .unwrap_err();
+
+    // minter udpate nft info
+    let update_msg_without_extension = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: token_id.clone(),
+        token_uri: Some("".to_string()), // sets token uri to none
+        extension: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            update_msg_without_extension.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // other udpate nft metadata extension
+    let update_msg_only_extension = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: token_id.clone(),
+        token_uri: None,
+        extension: Some(NftExtensionMsg {
+            image: Some("ipfs://foo.bar/image.png".to_string()),
+            image_data: None,
+            external_url: None,
+            description: None,
+            name: None,
+            attributes: None,
+            background_color: None,
+            animation_url: None,
+            youtube_url: None,
+        }),
+    };
+    let info_other = mock_info("other", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_other,
+            update_msg_only_extension.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // creator updates nft info
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_msg_without_extension,
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_nft_info(deps.as_ref().storage, token_id.clone())
+            .unwrap(),
+        NftInfoResponse {
             token_uri: None,
-            extension: Some(NftExtensionMsg {
-                image: None,
+            extension: None,
+        }
+    );
+
+    // creator updates nft metadata extension
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_msg_only_extension,
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_nft_info(deps.as_ref().storage, token_id)
+            .unwrap(),
+        NftInfoResponse {
+            token_uri: None,
+            extension: Some(NftExtension {
+                image: Some("ipfs://foo.bar/image.png".to_string()),
                 image_data: None,
                 external_url: None,
                 description: None,
@@ -670,36 +1143,236 @@ fn test_mint_with_metadata() {
                 animation_url: None,
                 youtube_url: None,
             }),
+        }
+    );
+}
+
+#[test]
+fn test_metadata_editor() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    // mint nfts
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["sword:1", "shield:1"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("owner"),
+            token_uri: None,
+            extension: Some(NftExtensionMsg::default()),
         };
         contract
             .execute(deps.as_mut(), &env, &info_minter, mint_msg)
             .unwrap();
-        assert_eq!(
-            contract
-                .query_nft_info(deps.as_ref().storage, "2".to_string())
-                .unwrap(),
-            NftInfoResponse {
-                token_uri: None,
-                extension: Some(NftExtension {
-                    image: None,
-                    image_data: None,
-                    external_url: None,
-                    description: None,
-                    name: None,
-                    attributes: None,
-                    background_color: None,
-                    animation_url: None,
-                    youtube_url: None,
-                }),
-            }
-        );
-        // empty description
-        let token_id = "3".to_string();
-        let mut metadata = valid_extension_msg.clone();
-        metadata.description = Some("".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
+    }
+
+    let update_msg = |token_id: &str| Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: token_id.to_string(),
+        token_uri: None,
+        extension: Some(NftExtensionMsg {
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "level".to_string(),
+                value: "2".to_string(),
+            }]),
+            ..NftExtensionMsg::default()
+        }),
+    };
+
+    // game server is not an editor yet
+    let info_editor = mock_info("game_server", &[]);
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_editor, update_msg("sword:1"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // only creator can grant editor rights
+    let set_editor_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::SetMetadataEditor {
+        editor: "game_server".to_string(),
+        scope: MetadataEditorScope::TokenIdPrefix {
+            prefix: "sword:".to_string(),
+        },
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_minter, set_editor_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            set_editor_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_metadata_editor(deps.as_ref(), "game_server".to_string())
+            .unwrap(),
+        Some(MetadataEditorScope::TokenIdPrefix {
+            prefix: "sword:".to_string(),
+        })
+    );
+
+    // editor can update nft within scope
+    contract
+        .execute(deps.as_mut(), &env, &info_editor, update_msg("sword:1"))
+        .unwrap();
+    let nft_info = contract
+        .query_nft_info(deps.as_ref().storage, "sword:1".to_string())
+        .unwrap();
+    assert_eq!(
+        nft_info.extension.unwrap().attributes,
+        Some(vec![Trait {
+            display_type: None,
+            trait_type: "level".to_string(),
+            value: "2".to_string(),
+        }])
+    );
+
+    // editor cannot update nft outside of scope
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_editor, update_msg("shield:1"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::MetadataEditorOutOfScope {
+            editor: "game_server".to_string(),
+            token_id: "shield:1".to_string(),
+        }
+    );
+
+    // creator revokes editor rights
+    let remove_editor_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::RemoveMetadataEditor {
+        editor: "game_server".to_string(),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            remove_editor_msg,
+        )
+        .unwrap();
+    let editors = contract
+        .query_metadata_editors(deps.as_ref(), None, None)
+        .unwrap();
+    assert!(editors.editors.is_empty());
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_editor, update_msg("sword:1"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+}
+
+#[test]
+fn test_mint_with_metadata() {
+    // case 1: mint with valid metadata
+    {
+        let mut deps = mock_dependencies();
+        let contract = setup_contract(deps.as_mut());
+
+        let token_id = "1".to_string();
+        let token_uri = "ipfs://foo.bar".to_string();
+        let valid_extension_msg = NftExtensionMsg {
+            image: Some("ipfs://foo.bar/image.png".to_string()),
+            image_data: Some("image data".to_string()),
+            external_url: Some("https://github.com".to_string()),
+            description: Some("description".to_string()),
+            name: Some("name".to_string()),
+            attributes: Some(vec![Trait {
+                trait_type: "trait_type".to_string(),
+                value: "value".to_string(),
+                display_type: Some("display_type".to_string()),
+            }]),
+            background_color: Some("background_color".to_string()),
+            animation_url: Some("ssl://animation_url".to_string()),
+            youtube_url: Some("file://youtube_url".to_string()),
+        };
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri),
+            extension: Some(valid_extension_msg.clone()),
+        };
+
+        let info_minter = mock_info(MINTER_ADDR, &[]);
+        let env = mock_env();
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        assert_eq!(
+            contract
+                .query_nft_info(deps.as_ref().storage, token_id)
+                .unwrap(),
+            NftInfoResponse {
+                token_uri: Some("ipfs://foo.bar".to_string()),
+                extension: Some(valid_extension_msg.clone().into()),
+            }
+        );
+
+        // mint with empty token uri and empty extension
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: "2".to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: Some(NftExtensionMsg {
+                image: None,
+                image_data: None,
+                external_url: None,
+                description: None,
+                name: None,
+                attributes: None,
+                background_color: None,
+                animation_url: None,
+                youtube_url: None,
+            }),
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        assert_eq!(
+            contract
+                .query_nft_info(deps.as_ref().storage, "2".to_string())
+                .unwrap(),
+            NftInfoResponse {
+                token_uri: None,
+                extension: Some(NftExtension {
+                    image: None,
+                    image_data: None,
+                    external_url: None,
+                    description: None,
+                    name: None,
+                    attributes: None,
+                    background_color: None,
+                    animation_url: None,
+                    youtube_url: None,
+                }),
+            }
+        );
+        // empty description
+        let token_id = "3".to_string();
+        let mut metadata = valid_extension_msg.clone();
+        metadata.description = Some("".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
             token_uri: None,
             extension: Some(metadata),
         };
@@ -908,261 +1581,899 @@ fn test_update_collection_info() {
     let _ = contract
         .execute(
             deps.as_mut(),
-            &mock_env(),
-            &creator_info,
-            update_collection_info_msg,
+            &mock_env(),
+            &creator_info,
+            update_collection_info_msg,
+        )
+        .unwrap();
+
+    // Update the owner to "random". The new owner should be able to
+    // mint new tokens, the old one should not.
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &creator_info,
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+
+    // Creator does not change until ownership transfer completes.
+    // Pending ownership transfer should be discoverable via query.
+    let ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ownership,
+        Ownership::<Addr> {
+            owner: Some(Addr::unchecked(CREATOR_ADDR)),
+            pending_owner: Some(Addr::unchecked("random")),
+            pending_expiry: None,
+        }
+    );
+
+    // Accept the ownership transfer.
+    let random_info = mock_info("random", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &random_info,
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::AcceptOwnership),
+        )
+        .unwrap();
+
+    // Creator changes after ownership transfer is accepted.
+    let creator_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(creator_ownership.owner, Some(random_info.sender.clone()));
+
+    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: Some("new name".to_string()),
+            symbol: Some("NEW".to_string()),
+            extension: None,
+        },
+    };
+
+    // Old owner can not update.
+    let err: Cw721ContractError = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &creator_info,
+            update_collection_info_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // New owner can update.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &random_info,
+            update_collection_info_msg,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_update_minter() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id,
+        owner: String::from("medusa"),
+        token_uri: Some(token_uri.clone()),
+        extension: None,
+    };
+
+    // Minter can mint
+    let current_minter_info = mock_info(MINTER_ADDR, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &current_minter_info, mint_msg)
+        .unwrap();
+
+    // Update the owner to "random". The new owner should be able to
+    // mint new tokens, the old one should not.
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &current_minter_info,
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+
+    // Minter does not change until ownership transfer completes.
+    // Pending ownership transfer should be discoverable via query.
+    let ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetMinterOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ownership,
+        Ownership::<Addr> {
+            owner: Some(Addr::unchecked(MINTER_ADDR)),
+            pending_owner: Some(Addr::unchecked("random")),
+            pending_expiry: None,
+        }
+    );
+
+    // Accept the ownership transfer.
+    let new_minter_info = mock_info("random", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &new_minter_info,
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::AcceptOwnership),
+        )
+        .unwrap();
+
+    // Minter changes after ownership transfer is accepted.
+    let minter_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetMinterOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(minter_ownership.owner, Some(new_minter_info.sender.clone()));
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "randoms_token".to_string(),
+        owner: String::from("medusa"),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    // Old owner can not mint.
+    let err: Cw721ContractError = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &current_minter_info,
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+
+    // New owner can mint.
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &new_minter_info, mint_msg)
+        .unwrap();
+}
+
+#[test]
+fn test_burn() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: MINTER_ADDR.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
+
+    // mint some NFT
+    let allowed = mock_info(MINTER_ADDR, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &allowed, mint_msg)
+        .unwrap();
+
+    // random not allowed to burn
+    let random = mock_info("random", &[]);
+    let env = mock_env();
+    let err = contract
+        .execute(deps.as_mut(), &env, &random, burn_msg.clone())
+        .unwrap_err();
+
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let _ = contract
+        .execute(deps.as_mut(), &env, &allowed, burn_msg)
+        .unwrap();
+
+    // ensure num tokens decreases
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(0, count.count);
+
+    // trying to get nft returns error
+    let _ = contract
+        .query_nft_info(deps.as_ref().storage, "petrify".to_string())
+        .unwrap_err();
+
+    // list the token_ids
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .unwrap();
+    assert!(tokens.tokens.is_empty());
+}
+
+#[test]
+fn test_events() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: token_id.clone(),
+                owner: String::from("venus"),
+                token_uri: Some(token_uri.clone()),
+                extension: None,
+            },
+        )
+        .unwrap();
+    let mint_event = MintEvent {
+        token_id: token_id.clone(),
+        minter: String::from(MINTER_ADDR),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+    };
+    assert_eq!(
+        MintEvent::from_response(&res).unwrap(),
+        vec![mint_event.clone()]
+    );
+    // other event types are ignored
+    assert_eq!(BurnEvent::from_response(&res).unwrap(), vec![]);
+
+    // events emitted on chain are prefixed with `wasm-`
+    let mut event: cosmwasm_std::Event = mint_event.clone().into();
+    event.ty = format!("wasm-{}", event.ty);
+    assert_eq!(event.ty, "wasm-cw721-mint");
+    assert_eq!(MintEvent::from_event(&event).unwrap(), mint_event);
+    assert!(BurnEvent::from_event(&event).is_err());
+
+    // creator updates metadata
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::UpdateNftInfo {
+                token_id: token_id.clone(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        MetadataUpdateEvent::from_response(&res).unwrap(),
+        vec![MetadataUpdateEvent {
+            token_id: token_id.clone(),
+            editor: Some(String::from(CREATOR_ADDR)),
+        }]
+    );
+
+    // approved spender burns
+    let venus = mock_info("venus", &[]);
+    let expires = Expiration::AtHeight(env.block.height + 10);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &venus,
+            Cw721ExecuteMsg::Approve {
+                spender: String::from("random"),
+                token_id: token_id.clone(),
+                expires: Some(expires),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        ApproveEvent::from_response(&res).unwrap(),
+        vec![ApproveEvent {
+            token_id: token_id.clone(),
+            sender: String::from("venus"),
+            owner: String::from("venus"),
+            spender: String::from("random"),
+            expires,
+        }]
+    );
+    let random = mock_info("random", &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::Burn {
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        BurnEvent::from_response(&res).unwrap(),
+        vec![BurnEvent {
+            token_id,
+            sender: String::from("random"),
+            owner: String::from("venus"),
+        }]
+    );
+}
+
+#[test]
+fn test_owner_snapshots() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let start_height = env.block.height;
+
+    // queries fail unless enabled
+    let err = contract
+        .query_balance_at_height(deps.as_ref(), String::from("venus"), start_height)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Owner snapshots are not enabled")
+    );
+
+    // only creator can enable
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::EnableOwnerSnapshots {},
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::EnableOwnerSnapshots {},
+        )
+        .unwrap();
+
+    // mint, transfer and burn in subsequent blocks
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &minter,
+                Cw721ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: String::from("venus"),
+                    token_uri: None,
+                    extension: None,
+                },
+            )
+            .unwrap();
+    }
+    env.block.height += 1;
+    let venus = mock_info("venus", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &venus,
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("mars"),
+                token_id: String::from("1"),
+            },
         )
         .unwrap();
-
-    // Update the owner to "random". The new owner should be able to
-    // mint new tokens, the old one should not.
+    env.block.height += 1;
     contract
         .execute(
             deps.as_mut(),
-            &mock_env(),
-            &creator_info,
-            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
-                new_owner: "random".to_string(),
-                expiry: None,
-            }),
+            &env,
+            &venus,
+            Cw721ExecuteMsg::Burn {
+                token_id: String::from("2"),
+            },
         )
         .unwrap();
+    env.block.height += 1;
 
-    // Creator does not change until ownership transfer completes.
-    // Pending ownership transfer should be discoverable via query.
-    let ownership: Ownership<Addr> = from_json(
+    // snapshot reflects state at beginning of block
+    let owner_of = |deps: cosmwasm_std::Deps, height: u64| {
         contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetCreatorOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
-
+            .query_owner_of_at_height(deps.storage, String::from("1"), height)
+            .unwrap()
+            .owner
+    };
+    assert_eq!(owner_of(deps.as_ref(), start_height), None);
     assert_eq!(
-        ownership,
-        Ownership::<Addr> {
-            owner: Some(Addr::unchecked(CREATOR_ADDR)),
-            pending_owner: Some(Addr::unchecked("random")),
-            pending_expiry: None,
-        }
+        owner_of(deps.as_ref(), start_height + 1),
+        Some(Addr::unchecked("venus"))
+    );
+    assert_eq!(
+        owner_of(deps.as_ref(), start_height + 2),
+        Some(Addr::unchecked("mars"))
     );
+    let res = contract
+        .query_owner_of_at_height(deps.as_ref().storage, String::from("2"), start_height + 3)
+        .unwrap();
+    assert_eq!(res.owner, None);
 
-    // Accept the ownership transfer.
-    let random_info = mock_info("random", &[]);
+    let balance_of = |deps: cosmwasm_std::Deps, owner: &str, height: u64| {
+        contract
+            .query_balance_at_height(deps, owner.to_string(), height)
+            .unwrap()
+            .balance
+    };
+    assert_eq!(balance_of(deps.as_ref(), "venus", start_height), 0);
+    assert_eq!(balance_of(deps.as_ref(), "venus", start_height + 1), 2);
+    assert_eq!(balance_of(deps.as_ref(), "venus", start_height + 2), 1);
+    assert_eq!(balance_of(deps.as_ref(), "venus", start_height + 3), 0);
+    assert_eq!(balance_of(deps.as_ref(), "mars", start_height + 1), 0);
+    assert_eq!(balance_of(deps.as_ref(), "mars", start_height + 3), 1);
+
+    // can't be enabled once tokens are minted
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
     contract
         .execute(
             deps.as_mut(),
-            &mock_env(),
-            &random_info,
-            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::AcceptOwnership),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: String::from("1"),
+                owner: String::from("venus"),
+                token_uri: None,
+                extension: None,
+            },
         )
         .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::EnableOwnerSnapshots {},
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::OwnerSnapshotsRequireNoTokens {});
+}
 
-    // Creator changes after ownership transfer is accepted.
-    let creator_ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetCreatorOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(creator_ownership.owner, Some(random_info.sender.clone()));
+#[test]
+fn test_transfer_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
 
-    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
-        collection_info: CollectionInfoMsg {
-            name: Some("new name".to_string()),
-            symbol: Some("NEW".to_string()),
-            extension: None,
-        },
+    // Mint a token
+    let token_id = "melt".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+        extension: None,
     };
 
-    // Old owner can not update.
-    let err: Cw721ContractError = contract
-        .execute(
-            deps.as_mut(),
-            &mock_env(),
-            &creator_info,
-            update_collection_info_msg.clone(),
-        )
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .unwrap();
+
+    // random cannot transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // New owner can update.
-    let _ = contract
-        .execute(
-            deps.as_mut(),
-            &mock_env(),
-            &random_info,
-            update_collection_info_msg,
-        )
+    // owner can
+    let random = mock_info("venus", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
         .unwrap();
+
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("venus"),
+                    previous_owner: String::from("venus"),
+                    recipient: String::from("random"),
+                }
+                .into()
+            )
+            .add_attribute("action", "transfer_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "random")
+            .add_attribute("token_id", token_id)
+    );
 }
 
 #[test]
-fn test_update_minter() {
+fn test_two_step_transfer() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
 
-    let token_id = "petrify".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
-
+    // Mint a token
+    let token_id = "melt".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id,
-        owner: String::from("medusa"),
-        token_uri: Some(token_uri.clone()),
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: None,
         extension: None,
     };
-
-    // Minter can mint
-    let current_minter_info = mock_info(MINTER_ADDR, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &current_minter_info, mint_msg)
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
         .unwrap();
 
-    // Update the owner to "random". The new owner should be able to
-    // mint new tokens, the old one should not.
+    // random cannot propose
+    let propose_msg = Cw721ExecuteMsg::ProposeTransfer {
+        recipient: String::from("mars"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), &env, &random, propose_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // owner can propose, ownership does not move yet
+    let venus = mock_info("venus", &[]);
     contract
-        .execute(
-            deps.as_mut(),
-            &mock_env(),
-            &current_minter_info,
-            Cw721ExecuteMsg::UpdateMinterOwnership(Action::TransferOwnership {
-                new_owner: "random".to_string(),
-                expiry: None,
-            }),
-        )
+        .execute(deps.as_mut(), &env, &venus, propose_msg.clone())
+        .unwrap();
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, token_id.clone(), false)
         .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("venus"),
+            approvals: vec![],
+            pending_transfer: Some(PendingTransfer {
+                proposer: Addr::unchecked("venus"),
+                recipient: Addr::unchecked("mars"),
+                expires: Expiration::Never {},
+            }),
+        }
+    );
 
-    // Minter does not change until ownership transfer completes.
-    // Pending ownership transfer should be discoverable via query.
-    let ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetMinterOwnership {},
+    // only recipient can accept
+    let accept_msg = Cw721ExecuteMsg::AcceptTransfer {
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &random, accept_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotPendingTransferRecipient {});
+    let mars = mock_info("mars", &[]);
+    let res = contract
+        .execute(deps.as_mut(), &env, &mars, accept_msg.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("mars"),
+                    previous_owner: String::from("venus"),
+                    recipient: String::from("mars"),
+                }
+                .into()
             )
-            .unwrap(),
-    )
-    .unwrap();
+            .add_attribute("action", "accept_transfer")
+            .add_attribute("sender", "mars")
+            .add_attribute("token_id", token_id.clone())
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, token_id.clone(), false)
+        .unwrap();
+    assert_eq!(res.owner, "mars");
+    assert_eq!(res.pending_transfer, None);
+
+    // accepting twice fails
+    let err = contract
+        .execute(deps.as_mut(), &env, &mars, accept_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NoPendingTransfer {
+            token_id: token_id.clone()
+        }
+    );
+
+    // expired proposal cannot be accepted
+    let propose_msg = Cw721ExecuteMsg::ProposeTransfer {
+        recipient: String::from("venus"),
+        token_id: token_id.clone(),
+        expires: Some(Expiration::AtHeight(env.block.height + 1)),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mars, propose_msg)
+        .unwrap();
+    let mut later = mock_env();
+    later.block.height += 1;
+    let err = contract
+        .execute(deps.as_mut(), &later, &venus, accept_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::PendingTransferExpired {
+            token_id: token_id.clone()
+        }
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &later, token_id.clone(), false)
+        .unwrap();
+    assert_eq!(res.pending_transfer, None);
+
+    // owner can cancel
+    let cancel_msg = Cw721ExecuteMsg::CancelTransfer {
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &random, cancel_msg.clone())
+        .unwrap_err();
+    contract
+        .execute(deps.as_mut(), &env, &mars, cancel_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, accept_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NoPendingTransfer {
+            token_id: token_id.clone()
+        }
+    );
 
+    // normal transfer clears pending transfer
+    let propose_msg = Cw721ExecuteMsg::ProposeTransfer {
+        recipient: String::from("venus"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mars, propose_msg)
+        .unwrap();
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mars, transfer_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, accept_msg.clone())
+        .unwrap_err();
     assert_eq!(
-        ownership,
-        Ownership::<Addr> {
-            owner: Some(Addr::unchecked(MINTER_ADDR)),
-            pending_owner: Some(Addr::unchecked("random")),
-            pending_expiry: None,
+        err,
+        Cw721ContractError::NoPendingTransfer {
+            token_id: token_id.clone()
         }
     );
 
-    // Accept the ownership transfer.
-    let new_minter_info = mock_info("random", &[]);
+    // proposal of a spender can't be accepted once its approval is revoked
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("spender"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
     contract
-        .execute(
-            deps.as_mut(),
-            &mock_env(),
-            &new_minter_info,
-            Cw721ExecuteMsg::UpdateMinterOwnership(Action::AcceptOwnership),
-        )
+        .execute(deps.as_mut(), &env, &random, approve_msg)
         .unwrap();
-
-    // Minter changes after ownership transfer is accepted.
-    let minter_ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetMinterOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(minter_ownership.owner, Some(new_minter_info.sender.clone()));
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: "randoms_token".to_string(),
-        owner: String::from("medusa"),
-        token_uri: Some(token_uri),
-        extension: None,
+    let spender = mock_info("spender", &[]);
+    let propose_msg = Cw721ExecuteMsg::ProposeTransfer {
+        recipient: String::from("venus"),
+        token_id: token_id.clone(),
+        expires: None,
     };
-
-    // Old owner can not mint.
-    let err: Cw721ContractError = contract
-        .execute(
-            deps.as_mut(),
-            &mock_env(),
-            &current_minter_info,
-            mint_msg.clone(),
-        )
+    contract
+        .execute(deps.as_mut(), &env, &spender, propose_msg.clone())
+        .unwrap();
+    let revoke_msg = Cw721ExecuteMsg::Revoke {
+        spender: String::from("spender"),
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &random, revoke_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, accept_msg.clone())
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotMinter {});
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // New owner can mint.
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &new_minter_info, mint_msg)
+    // same for an operator
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("spender"),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &random, approve_all_msg)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &spender, propose_msg)
+        .unwrap();
+    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
+        operator: String::from("spender"),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &random, revoke_all_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, accept_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, token_id, false)
         .unwrap();
+    assert_eq!(res.owner, "random");
 }
 
 #[test]
-fn test_burn() {
+fn test_set_user() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
 
-    let token_id = "petrify".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
-
+    // Mint a token
+    let token_id = "sword".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
         token_id: token_id.clone(),
-        owner: MINTER_ADDR.to_string(),
-        token_uri: Some(token_uri),
+        owner: String::from("venus"),
+        token_uri: None,
         extension: None,
     };
-
-    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
-
-    // mint some NFT
-    let allowed = mock_info(MINTER_ADDR, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &allowed, mint_msg)
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
         .unwrap();
 
-    // random not allowed to burn
+    // random cannot set user
+    let set_user_msg = Cw721ExecuteMsg::SetUser {
+        token_id: token_id.clone(),
+        user: Some(String::from("renter")),
+        expires: Some(Expiration::AtHeight(env.block.height + 10)),
+    };
     let random = mock_info("random", &[]);
-    let env = mock_env();
     let err = contract
-        .execute(deps.as_mut(), &env, &random, burn_msg.clone())
+        .execute(deps.as_mut(), &env, &random, set_user_msg.clone())
         .unwrap_err();
-
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    let _ = contract
-        .execute(deps.as_mut(), &env, &allowed, burn_msg)
+    // owner can set user, ownership does not change
+    let venus = mock_info("venus", &[]);
+    contract
+        .execute(deps.as_mut(), &env, &venus, set_user_msg.clone())
+        .unwrap();
+    let res = contract
+        .query_user_of(deps.as_ref(), &env, token_id.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        UserOfResponse {
+            user: Some(String::from("renter")),
+            expires: Some(Expiration::AtHeight(env.block.height + 10)),
+        }
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, token_id.clone(), false)
         .unwrap();
+    assert_eq!(res.owner, "venus");
 
-    // ensure num tokens decreases
-    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
-    assert_eq!(0, count.count);
+    // user is none after expiry
+    let mut later = mock_env();
+    later.block.height += 10;
+    let res = contract
+        .query_user_of(deps.as_ref(), &later, token_id.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        UserOfResponse {
+            user: None,
+            expires: None,
+        }
+    );
 
-    // trying to get nft returns error
-    let _ = contract
-        .query_nft_info(deps.as_ref().storage, "petrify".to_string())
-        .unwrap_err();
+    // owner can remove user
+    contract
+        .execute(deps.as_mut(), &env, &venus, set_user_msg.clone())
+        .unwrap();
+    let unset_user_msg = Cw721ExecuteMsg::SetUser {
+        token_id: token_id.clone(),
+        user: None,
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &venus, unset_user_msg)
+        .unwrap();
+    let res = contract
+        .query_user_of(deps.as_ref(), &env, token_id.clone())
+        .unwrap();
+    assert_eq!(res.user, None);
 
-    // list the token_ids
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+    // user is cleared on transfer
+    contract
+        .execute(deps.as_mut(), &env, &venus, set_user_msg)
         .unwrap();
-    assert!(tokens.tokens.is_empty());
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("mars"),
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &venus, transfer_msg)
+        .unwrap();
+    let res = contract
+        .query_user_of(deps.as_ref(), &env, token_id)
+        .unwrap();
+    assert_eq!(res.user, None);
 }
 
 #[test]
-fn test_transfer_nft() {
+fn test_send_nft() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
@@ -1182,103 +2493,229 @@ fn test_transfer_nft() {
         .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
         .unwrap();
 
-    // random cannot transfer
-    let random = mock_info("random", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
+    let msg = to_json_binary("You now have the melting power").unwrap();
+    let target = String::from("another_contract");
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: target.clone(),
         token_id: token_id.clone(),
+        msg: msg.clone(),
     };
 
+    let random = mock_info("random", &[]);
     let err = contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(deps.as_mut(), &mock_env(), &random, send_msg.clone())
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // owner can
+    // but owner can
     let random = mock_info("venus", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
-        token_id: token_id.clone(),
-    };
-
     let res = contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
         .unwrap();
 
+    let payload = Cw721ReceiveMsg {
+        sender: String::from("venus"),
+        token_id: token_id.clone(),
+        msg,
+    };
+    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
+    // ensure expected serializes as we think it should
+    match &expected {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, &target)
+        }
+        m => panic!("Unexpected message type: {m:?}"),
+    }
+    // and make sure this is the request sent by the contract
     assert_eq!(
         res,
         Response::new()
-            .add_attribute("action", "transfer_nft")
+            .add_message(expected)
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("venus"),
+                    previous_owner: String::from("venus"),
+                    recipient: target.clone(),
+                }
+                .into()
+            )
+            .add_attribute("action", "send_nft")
             .add_attribute("sender", "venus")
-            .add_attribute("recipient", "random")
+            .add_attribute("recipient", "another_contract")
             .add_attribute("token_id", token_id)
     );
 }
 
 #[test]
-fn test_send_nft() {
+fn test_safe_mint() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    // Mint a token
     let token_id = "melt".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
+    let msg = to_json_binary("You now have the melting power").unwrap();
+    let target = String::from("another_contract");
+    let safe_mint_msg = Cw721ExecuteMsg::SafeMint {
         token_id: token_id.clone(),
-        owner: String::from("venus"),
-        token_uri: Some(token_uri),
+        owner: target.clone(),
+        token_uri: None,
         extension: None,
+        msg: msg.clone(),
     };
 
+    // random cannot mint
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &random, safe_mint_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+
+    // minter can mint, and owner contract is notified
     let minter = mock_info(MINTER_ADDR, &[]);
-    contract
-        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &minter, safe_mint_msg)
         .unwrap();
-
-    let msg = to_json_binary("You now have the melting power").unwrap();
-    let target = String::from("another_contract");
-    let send_msg = Cw721ExecuteMsg::SendNft {
-        contract: target.clone(),
+    let payload = Cw721ReceiveMsg {
+        sender: String::from(MINTER_ADDR),
         token_id: token_id.clone(),
-        msg: msg.clone(),
+        msg,
     };
+    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(expected)
+            .add_event(
+                MintEvent {
+                    token_id: token_id.clone(),
+                    minter: String::from(MINTER_ADDR),
+                    owner: target.clone(),
+                    token_uri: None,
+                }
+                .into()
+            )
+            .add_attribute("action", "mint")
+            .add_attribute("minter", MINTER_ADDR)
+            .add_attribute("owner", target.clone())
+            .add_attribute("token_id", token_id.clone())
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &mock_env(), token_id, false)
+        .unwrap();
+    assert_eq!(res.owner, target);
+}
+
+#[test]
+fn test_safe_transfer_nft() {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::ContractInfo { contract_addr } if contract_addr == "receiver_contract" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(1, CREATOR_ADDR)).unwrap(),
+            ))
+        }
+        WasmQuery::ContractInfo { contract_addr } => {
+            SystemResult::Err(SystemError::NoSuchContract {
+                addr: contract_addr.clone(),
+            })
+        }
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: "wasm".to_string(),
+        }),
+    });
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "melt".to_string();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: token_id.clone(),
+                owner: String::from("venus"),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    // transfer to wallet, no callback
+    let venus = mock_info("venus", &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &venus,
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient: String::from("random"),
+                token_id: token_id.clone(),
+                msg: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("venus"),
+                    previous_owner: String::from("venus"),
+                    recipient: String::from("random"),
+                }
+                .into()
+            )
+            .add_attribute("action", "safe_transfer_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "random")
+            .add_attribute("token_id", token_id.clone())
+    );
 
+    // transfer to contract is delivered via ReceiveNft
     let random = mock_info("random", &[]);
-    let err = contract
-        .execute(deps.as_mut(), &mock_env(), &random, send_msg.clone())
-        .unwrap_err();
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
-
-    // but owner can
-    let random = mock_info("venus", &[]);
+    let msg = to_json_binary("You now have the melting power").unwrap();
     let res = contract
-        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &random,
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient: String::from("receiver_contract"),
+                token_id: token_id.clone(),
+                msg: Some(msg.clone()),
+            },
+        )
         .unwrap();
-
     let payload = Cw721ReceiveMsg {
-        sender: String::from("venus"),
+        sender: String::from("random"),
         token_id: token_id.clone(),
         msg,
     };
-    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
-    // ensure expected serializes as we think it should
-    match &expected {
-        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
-            assert_eq!(contract_addr, &target)
-        }
-        m => panic!("Unexpected message type: {m:?}"),
-    }
-    // and make sure this is the request sent by the contract
+    let expected = payload.into_cosmos_msg("receiver_contract").unwrap();
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                TransferEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("random"),
+                    previous_owner: String::from("random"),
+                    recipient: String::from("receiver_contract"),
+                }
+                .into()
+            )
             .add_message(expected)
-            .add_attribute("action", "send_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "another_contract")
-            .add_attribute("token_id", token_id)
+            .add_attribute("action", "safe_transfer_nft")
+            .add_attribute("sender", "random")
+            .add_attribute("recipient", "receiver_contract")
+            .add_attribute("token_id", token_id.clone())
     );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &mock_env(), token_id, false)
+        .unwrap();
+    assert_eq!(res.owner, "receiver_contract");
 }
 
 #[test]
@@ -1335,6 +2772,16 @@ fn test_approve_revoke() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                ApproveEvent {
+                    token_id: token_id.clone(),
+                    sender: String::from("demeter"),
+                    owner: String::from("demeter"),
+                    spender: String::from("random"),
+                    expires: Expiration::Never {},
+                }
+                .into()
+            )
             .add_attribute("action", "approve")
             .add_attribute("sender", "demeter")
             .add_attribute("spender", "random")
@@ -1387,6 +2834,7 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            pending_transfer: None,
         }
     );
 
@@ -1421,8 +2869,164 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            pending_transfer: None,
+        }
+    );
+}
+
+fn sign_permit(
+    signing_key: &SigningKey,
+    env: &Env,
+    token_id: &str,
+    spender: &str,
+    expires: Option<Expiration>,
+    nonce: u64,
+) -> Binary {
+    let payload = PermitPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        token_id: token_id.to_string(),
+        spender: spender.to_string(),
+        expires,
+        nonce,
+    };
+    let message_hash = Sha256::digest(to_json_vec(&payload).unwrap());
+    let signature: Signature = signing_key.sign_prehash(&message_hash).unwrap();
+    Binary::from(signature.to_bytes().as_slice())
+}
+
+#[test]
+fn test_permit() {
+    let mut deps = mock_dependencies();
+    // permit requires pubkey to derive to owner's bech32 address
+    deps.api = MockApi::default().with_prefix("cosmwasm");
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let creator = deps.api.addr_make(CREATOR_ADDR);
+    let minter = deps.api.addr_make(MINTER_ADDR);
+    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(minter.to_string()),
+        creator: Some(creator.to_string()),
+        withdraw_address: None,
+        ics721_bridge: None,
+    };
+    contract
+        .instantiate(deps.as_mut(), &env, &mock_info(creator.as_str(), &[]), msg)
+        .unwrap();
+
+    // owner's key and address
+    let signing_key = SigningKey::from_bytes(&[1u8; 32].into()).unwrap();
+    let pubkey = Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let owner_canonical = Ripemd160::digest(Sha256::digest(pubkey.as_slice())).to_vec();
+    let owner = deps.api.addr_humanize(&owner_canonical.into()).unwrap();
+
+    // Mint a token
+    let token_id = "grow".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(minter.as_str(), &[]),
+            mint_msg,
+        )
+        .unwrap();
+
+    // anyone (e.g. a relayer) can submit a permit signed by owner
+    let relayer = mock_info(deps.api.addr_make("relayer").as_str(), &[]);
+    let spender = deps.api.addr_make("marketplace").to_string();
+    let signature = sign_permit(&signing_key, &env, &token_id, &spender, None, 0);
+    let permit_msg = Cw721ExecuteMsg::Permit {
+        token_id: token_id.clone(),
+        spender: spender.clone(),
+        expires: None,
+        nonce: 0,
+        signature,
+        pubkey: pubkey.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &relayer, permit_msg.clone())
+        .unwrap();
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            &env,
+            token_id.clone(),
+            spender.clone(),
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        res.approval,
+        Approval {
+            spender: Addr::unchecked(spender.clone()),
+            expires: Expiration::Never {},
+        }
+    );
+    assert_eq!(
+        contract
+            .query_permit_nonce(deps.as_ref(), owner.to_string())
+            .unwrap(),
+        1
+    );
+
+    // permit cannot be replayed
+    let err = contract
+        .execute(deps.as_mut(), &env, &relayer, permit_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidPermitNonce {
+            expected: 1,
+            actual: 0
         }
     );
+
+    // signature must match payload
+    let other_spender = deps.api.addr_make("drainer").to_string();
+    let signature = sign_permit(&signing_key, &env, &token_id, &spender, None, 1);
+    let permit_msg = Cw721ExecuteMsg::Permit {
+        token_id: token_id.clone(),
+        spender: other_spender.clone(),
+        expires: None,
+        nonce: 1,
+        signature,
+        pubkey,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &relayer, permit_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidPermitSignature {});
+
+    // signer must be owner
+    let other_key = SigningKey::from_bytes(&[2u8; 32].into()).unwrap();
+    let other_pubkey = Binary::from(other_key.verifying_key().to_encoded_point(true).as_bytes());
+    let signature = sign_permit(&other_key, &env, &token_id, &other_spender, None, 1);
+    let permit_msg = Cw721ExecuteMsg::Permit {
+        token_id,
+        spender: other_spender,
+        expires: None,
+        nonce: 1,
+        signature,
+        pubkey: other_pubkey,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &relayer, permit_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::PermitSignerNotOwner {});
 }
 
 #[test]
@@ -1703,6 +3307,226 @@ fn test_approve_all_revoke_all() {
     }
 }
 
+#[test]
+fn test_lock_unlock_token() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    // Mint a token
+    let token_id = "grow".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+
+    // owner grants operator, e.g. a malicious one
+    let owner = mock_info("demeter", &[]);
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("drainer"),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &owner, approve_all_msg)
+        .unwrap();
+
+    // only owner can lock, not even operator
+    let lock_msg = Cw721ExecuteMsg::LockToken {
+        token_id: token_id.clone(),
+    };
+    let drainer = mock_info("drainer", &[]);
+    let err = contract
+        .execute(deps.as_mut(), &env, &drainer, lock_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(deps.as_mut(), &env, &owner, lock_msg.clone())
+        .unwrap();
+    let res = contract
+        .query_is_locked(deps.as_ref(), &env, token_id.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        IsLockedResponse {
+            locked: true,
+            unlocks_at: None,
+        }
+    );
+
+    // locked token can neither be transferred nor approved
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("drainer"),
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &drainer, transfer_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: token_id.clone()
+        }
+    );
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, transfer_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: token_id.clone()
+        }
+    );
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("drainer"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &drainer, approve_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: token_id.clone()
+        }
+    );
+
+    // only creator can set unlock delay
+    let set_delay_msg = Cw721ExecuteMsg::SetUnlockDelay {
+        delay: Duration::Height(10),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, set_delay_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(
+        contract.query_unlock_delay(deps.as_ref().storage).unwrap(),
+        DEFAULT_UNLOCK_DELAY
+    );
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_delay_msg)
+        .unwrap();
+
+    // unlock only takes effect after delay
+    let unlock_msg = Cw721ExecuteMsg::UnlockToken {
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &drainer, unlock_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(deps.as_mut(), &env, &owner, unlock_msg.clone())
+        .unwrap();
+    let res = contract
+        .query_is_locked(deps.as_ref(), &env, token_id.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        IsLockedResponse {
+            locked: true,
+            unlocks_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        }
+    );
+    let mut later = mock_env();
+    later.block.height += 9;
+    contract
+        .execute(deps.as_mut(), &later, &owner, transfer_msg.clone())
+        .unwrap_err();
+
+    // locking again cancels requested unlock
+    contract
+        .execute(deps.as_mut(), &later, &owner, lock_msg)
+        .unwrap();
+    later.block.height += 1;
+    let err = contract
+        .execute(deps.as_mut(), &later, &owner, transfer_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: token_id.clone()
+        }
+    );
+
+    // once unlocked, token can be transferred
+    contract
+        .execute(deps.as_mut(), &later, &owner, unlock_msg.clone())
+        .unwrap();
+    later.block.height += 10;
+    let res = contract
+        .query_is_locked(deps.as_ref(), &later, token_id.clone())
+        .unwrap();
+    assert!(!res.locked);
+    let err = contract
+        .execute(deps.as_mut(), &later, &owner, unlock_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenNotLocked {
+            token_id: token_id.clone()
+        }
+    );
+    contract
+        .execute(deps.as_mut(), &later, &owner, transfer_msg)
+        .unwrap();
+}
+
+#[test]
+fn test_lock_token_keeps_extension() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    let token_id = "grow".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some("https://example.com/grow.json".to_string()),
+        extension: Some(NftExtensionMsg {
+            name: Some("Grow".to_string()),
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "season".to_string(),
+                value: "spring".to_string(),
+            }]),
+            ..NftExtensionMsg::default()
+        }),
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+    let nft_info = contract
+        .query_nft_info(deps.as_ref().storage, token_id.clone())
+        .unwrap();
+
+    // locking only touches approvals, onchain metadata is kept
+    let owner = mock_info("demeter", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &owner,
+            Cw721ExecuteMsg::LockToken {
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_info(deps.as_ref().storage, token_id)
+        .unwrap();
+    assert!(res.extension.is_some());
+    assert_eq!(res, nft_info);
+}
+
 #[test]
 fn test_set_withdraw_address() {
     let mut deps = mock_dependencies();
@@ -412,6 +412,7 @@ fn test_operator() {
                 creator: Some(creator.to_string()),
                 collection_info_extension: None,
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             &[],
             "cw721-base",
@@ -1686,6 +1687,7 @@ fn test_instantiate() {
         minter: Some(minter.to_string()),
         creator: Some(creator.to_string()),
         withdraw_address: Some(withdraw_addr.to_string()),
+        ics721_bridge: None,
         collection_info_extension: Some(CollectionExtensionMsg {
             description: Some("description".to_string()),
             image: Some("ipfs://ark.pass".to_string()),
@@ -1872,6 +1874,7 @@ fn test_update_nft_metadata() {
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             &[],
             "cw721-base",
@@ -2537,6 +2540,7 @@ fn test_queries() {
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: Some(withdraw_addr.to_string()),
+                ics721_bridge: None,
             },
             &[],
             "cw721-base",
@@ -2655,3 +2659,472 @@ fn test_queries() {
         }
     );
 }
+
+/// Stand-in for an ICS-721 bridge, forwarding messages to the voucher collection.
+#[cosmwasm_schema::cw_serde]
+struct BridgeForwardMsg {
+    contract: String,
+    msg: Binary,
+}
+
+fn ics721_bridge_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_deps: DepsMut,
+         _env: Env,
+         _info: MessageInfo,
+         msg: BridgeForwardMsg|
+         -> StdResult<Response> {
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: msg.contract,
+                msg: msg.msg,
+                funds: vec![],
+            }))
+        },
+        |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_deps: Deps, _env: Env, _msg: Empty| -> StdResult<Binary> { to_json_binary(&Empty {}) },
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn test_ics721_voucher_collection() {
+    // --- setup ---
+    let mut app = new();
+    let creator = app.api().addr_make(CREATOR_ADDR);
+    let minter = app.api().addr_make(MINTER_ADDR);
+    let nft_owner = app.api().addr_make(NFT_OWNER_ADDR);
+    let bridge_code_id = app.store_code(ics721_bridge_contract());
+    let code_id = app.store_code(cw721_base_latest_contract());
+    let bridge = app
+        .instantiate_contract(
+            bridge_code_id,
+            creator.clone(),
+            &Empty {},
+            &[],
+            "ics721",
+            None,
+        )
+        .unwrap();
+    let init_msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: "voucher".to_string(),
+        symbol: "VOUCHER".to_string(),
+        collection_info_extension: None,
+        minter: None,
+        creator: None,
+        withdraw_address: None,
+        ics721_bridge: Some(bridge.to_string()),
+    };
+
+    // bridge is the only minter
+    let err: Cw721ContractError = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw721InstantiateMsg {
+                minter: Some(minter.to_string()),
+                ..init_msg.clone()
+            },
+            &[],
+            "cw721-voucher",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::Ics721BridgeIsMinter {});
+    let cw721 = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &init_msg,
+            &[],
+            "cw721-voucher",
+            None,
+        )
+        .unwrap();
+    let minter_ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            &cw721,
+            &Cw721QueryMsg::<
+                DefaultOptionalNftExtension,
+                DefaultOptionalCollectionExtension,
+                Empty,
+            >::GetMinterOwnership {},
+        )
+        .unwrap();
+    assert_eq!(minter_ownership.owner, Some(bridge.clone()));
+    let ics721_bridge: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            &cw721,
+            &Cw721QueryMsg::<
+                DefaultOptionalNftExtension,
+                DefaultOptionalCollectionExtension,
+                Empty,
+            >::GetIcs721Bridge {},
+        )
+        .unwrap();
+    assert_eq!(ics721_bridge, Some(bridge.clone()));
+
+    // minter ownership can't be transferred
+    let err: Cw721ContractError = app
+        .execute_contract(
+            creator.clone(),
+            cw721.clone(),
+            &Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::UpdateMinterOwnership(cw_ownable::Action::RenounceOwnership),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::Ics721BridgeIsMinter {});
+
+    // --- mint ---
+    let mint_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::Mint {
+        token_id: "1".to_string(),
+        owner: nft_owner.to_string(),
+        token_uri: Some("ipfs://foo.bar/1".to_string()),
+        extension: Some(NftExtensionMsg {
+            name: Some("name".to_string()),
+            ..NftExtensionMsg::default()
+        }),
+    };
+    let err: Cw721ContractError = app
+        .execute_contract(creator.clone(), cw721.clone(), &mint_msg, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    app.execute_contract(
+        creator.clone(),
+        bridge.clone(),
+        &BridgeForwardMsg {
+            contract: cw721.to_string(),
+            msg: to_json_binary(&mint_msg).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_owner(app.wrap(), &cw721, "1".to_string()), nft_owner);
+
+    // --- class and token data ---
+    let class_data: crate::msg::ClassDataResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw721,
+            &Cw721QueryMsg::<
+                DefaultOptionalNftExtension,
+                DefaultOptionalCollectionExtension,
+                Empty,
+            >::ClassData {},
+        )
+        .unwrap();
+    assert_eq!(class_data.class_id, cw721.to_string());
+    let decoded: crate::msg::ClassData<DefaultOptionalCollectionExtension> =
+        cosmwasm_std::from_json(&class_data.class_data).unwrap();
+    assert_eq!(
+        decoded,
+        crate::msg::ClassData {
+            name: "voucher".to_string(),
+            symbol: "VOUCHER".to_string(),
+            num_tokens: 1,
+            extension: None,
+        }
+    );
+    let token_data: crate::msg::TokenDataResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw721,
+            &Cw721QueryMsg::<
+                DefaultOptionalNftExtension,
+                DefaultOptionalCollectionExtension,
+                Empty,
+            >::TokenData {
+                token_ids: vec!["1".to_string()],
+            },
+        )
+        .unwrap();
+    assert_eq!(token_data.tokens.len(), 1);
+    assert_eq!(
+        token_data.tokens[0].token_uri,
+        Some("ipfs://foo.bar/1".to_string())
+    );
+    let extension: DefaultOptionalNftExtension =
+        cosmwasm_std::from_json(&token_data.tokens[0].token_data).unwrap();
+    assert_eq!(extension.unwrap().name, Some("name".to_string()));
+
+    // --- burn ---
+    let burn_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::Burn {
+        token_id: "1".to_string(),
+    };
+    // not even the owner can burn a voucher
+    let err: Cw721ContractError = app
+        .execute_contract(nft_owner.clone(), cw721.clone(), &burn_msg, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::NotIcs721Bridge {});
+    app.execute_contract(
+        creator,
+        bridge,
+        &BridgeForwardMsg {
+            contract: cw721.to_string(),
+            msg: to_json_binary(&burn_msg).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let num_tokens: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw721,
+            &Cw721QueryMsg::<
+                DefaultOptionalNftExtension,
+                DefaultOptionalCollectionExtension,
+                Empty,
+            >::NumTokens {},
+        )
+        .unwrap();
+    assert_eq!(num_tokens.count, 0);
+}
+
+#[test]
+fn test_nested_tokens() {
+    type ExecuteMsg = Cw721ExecuteMsg<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >;
+    type QueryMsg =
+        Cw721QueryMsg<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>;
+
+    // --- setup ---
+    let mut app = new();
+    let creator = app.api().addr_make(CREATOR_ADDR);
+    let nft_owner = app.api().addr_make(NFT_OWNER_ADDR);
+    let other = app.api().addr_make(OTHER1_ADDR);
+    let code_id = app.store_code(cw721_base_latest_contract());
+    let mut collections = vec![];
+    for name in ["characters", "hats"] {
+        let cw721 = app
+            .instantiate_contract(
+                code_id,
+                creator.clone(),
+                &Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+                    name: name.to_string(),
+                    symbol: name.to_uppercase(),
+                    collection_info_extension: None,
+                    minter: None,
+                    creator: None,
+                    withdraw_address: None,
+                    ics721_bridge: None,
+                },
+                &[],
+                name,
+                None,
+            )
+            .unwrap();
+        collections.push(cw721);
+    }
+    let (characters, hats) = (collections[0].clone(), collections[1].clone());
+    for (cw721, token_id) in [
+        (&characters, "character"),
+        (&characters, "sword"),
+        (&characters, "gem"),
+        (&hats, "hat"),
+    ] {
+        app.execute_contract(
+            creator.clone(),
+            cw721.clone(),
+            &ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: nft_owner.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let attach = |app: &mut MockApp, token_id: &str, parent_token_id: &str| {
+        app.execute_contract(
+            nft_owner.clone(),
+            characters.clone(),
+            &ExecuteMsg::AttachToken {
+                token_id: token_id.to_string(),
+                parent_token_id: parent_token_id.to_string(),
+            },
+            &[],
+        )
+    };
+    let root_owner = |app: &MockApp, token_id: &str| -> crate::msg::RootOwnerResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &characters,
+                &QueryMsg::RootOwner {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // --- attach within collection ---
+    attach(&mut app, "sword", "character").unwrap();
+    attach(&mut app, "gem", "sword").unwrap();
+    assert_eq!(
+        query_owner(app.wrap(), &characters, "sword".to_string()),
+        characters
+    );
+    assert_eq!(
+        root_owner(&app, "gem"),
+        crate::msg::RootOwnerResponse {
+            owner: nft_owner.to_string(),
+            root_token_id: "character".to_string(),
+        }
+    );
+    let err: Cw721ContractError = attach(&mut app, "character", "gem")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::AttachmentCycle {});
+
+    // --- attach from other collection ---
+    app.execute_contract(
+        nft_owner.clone(),
+        hats.clone(),
+        &ExecuteMsg::SendNft {
+            contract: characters.to_string(),
+            token_id: "hat".to_string(),
+            msg: to_json_binary(&crate::msg::AttachTokenMsg {
+                parent_token_id: "character".to_string(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let children: crate::msg::ChildrenResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &characters,
+            &QueryMsg::Children {
+                token_id: "character".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(children.children.len(), 2);
+    assert!(children.children.contains(&crate::msg::ChildToken {
+        collection: hats.clone(),
+        token_id: "hat".to_string(),
+    }));
+
+    // --- receive hook can't be faked ---
+    let fake_receive = ExecuteMsg::ReceiveNft(crate::receiver::Cw721ReceiveMsg {
+        sender: nft_owner.to_string(),
+        token_id: "fake".to_string(),
+        msg: to_json_binary(&crate::msg::AttachTokenMsg {
+            parent_token_id: "character".to_string(),
+        })
+        .unwrap(),
+    });
+    let err: Cw721ContractError = app
+        .execute_contract(nft_owner.clone(), characters.clone(), &fake_receive, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenNotReceived {
+            token_id: "fake".to_string()
+        }
+    );
+
+    // --- children move with parent ---
+    app.execute_contract(
+        nft_owner.clone(),
+        characters.clone(),
+        &ExecuteMsg::TransferNft {
+            recipient: other.to_string(),
+            token_id: "character".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(root_owner(&app, "gem").owner, other.to_string());
+    let err: Cw721ContractError = app
+        .execute_contract(
+            other.clone(),
+            characters.clone(),
+            &ExecuteMsg::Burn {
+                token_id: "character".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenHasChildren {
+            token_id: "character".to_string()
+        }
+    );
+
+    // --- detach by root owner only ---
+    let detach_hat = ExecuteMsg::DetachToken {
+        parent_token_id: "character".to_string(),
+        collection: Some(hats.to_string()),
+        token_id: "hat".to_string(),
+        recipient: None,
+    };
+    let err: Cw721ContractError = app
+        .execute_contract(nft_owner.clone(), characters.clone(), &detach_hat, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::NotRootOwner {});
+    app.execute_contract(other.clone(), characters.clone(), &detach_hat, &[])
+        .unwrap();
+    assert_eq!(query_owner(app.wrap(), &hats, "hat".to_string()), other);
+    app.execute_contract(
+        other.clone(),
+        characters.clone(),
+        &ExecuteMsg::DetachToken {
+            parent_token_id: "character".to_string(),
+            collection: None,
+            token_id: "sword".to_string(),
+            recipient: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_owner(app.wrap(), &characters, "sword".to_string()),
+        other
+    );
+    // gem stays attached to sword
+    assert_eq!(
+        root_owner(&app, "gem"),
+        crate::msg::RootOwnerResponse {
+            owner: other.to_string(),
+            root_token_id: "sword".to_string(),
+        }
+    );
+}
@@ -3,7 +3,8 @@ use crate::{
     extension::Cw721OnchainExtensions,
     msg::{
         CollectionExtensionMsg, CollectionInfoAndExtensionResponse, CollectionInfoMsg,
-        Cw721ExecuteMsg, Cw721InstantiateMsg, NftExtensionMsg, RoyaltyInfoResponse,
+        Cw721ExecuteMsg, Cw721InstantiateMsg, NftAttributes, NftExtensionMsg, NftInfoResponse,
+        OwnerOfResponse, RoyaltyInfoResponse,
     },
     query::MAX_LIMIT,
     state::{
@@ -41,6 +42,7 @@ fn test_instantiation() {
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             "contract_name",
             "contract_version",
@@ -61,6 +63,7 @@ fn test_instantiation() {
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             "contract_name",
             "contract_version",
@@ -80,6 +83,7 @@ fn test_instantiation() {
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
                 withdraw_address: None,
+                ics721_bridge: None,
             },
             "contract_name",
             "contract_version",
@@ -131,6 +135,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -159,6 +164,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -187,6 +193,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: None,
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -215,6 +222,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: None,
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -276,6 +284,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -322,6 +331,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -359,6 +369,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -396,6 +407,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -430,6 +442,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -466,6 +479,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -529,6 +543,7 @@ fn test_collection_info_update() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -704,6 +719,7 @@ fn test_collection_info_update() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -956,6 +972,7 @@ fn test_collection_info_update() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -1073,6 +1090,7 @@ fn test_collection_info_update() {
                     creator: None, // in case of none, sender is creator
                     minter: info_minter.sender.to_string().into(),
                     withdraw_address: None,
+                    ics721_bridge: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -1141,6 +1159,7 @@ fn test_nft_mint() {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         let env = mock_env();
         contract
@@ -1208,6 +1227,7 @@ fn test_nft_mint() {
             minter: None,
             creator: None,
             withdraw_address: None,
+            ics721_bridge: None,
         };
         let env = mock_env();
         contract
@@ -1524,3 +1544,44 @@ fn test_migrate_v16_onchain_metadata_contract() {
         assert_eq!(token.owner.as_str(), "owner");
     }
 }
+
+#[test]
+fn test_owner_of_response_without_pending_transfer() {
+    // responses of older contracts, without pending transfer, still parse
+    let res: OwnerOfResponse =
+        cosmwasm_std::from_json(r#"{"owner":"owner","approvals":[]}"#).unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: "owner".to_string(),
+            approvals: vec![],
+            pending_transfer: None,
+        }
+    );
+    // and older consumers don't get the unknown field, unless there is a pending transfer
+    assert_eq!(
+        cosmwasm_std::to_json_vec(&res).unwrap(),
+        br#"{"owner":"owner","approvals":[]}"#.to_vec()
+    );
+}
+
+#[test]
+fn test_nft_attributes_of_any_extension() {
+    // e.g. a cw2981 extension, with fields unknown to `NftExtension`
+    let res: NftInfoResponse<Option<NftAttributes>> = cosmwasm_std::from_json(
+        r#"{"token_uri":null,"extension":{"name":"Ticket","attributes":[{"display_type":null,"trait_type":"tier","value":"vip"}],"royalty_percentage":5,"royalty_payment_address":"creator"}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        res.extension.unwrap().attributes,
+        Some(vec![Trait {
+            display_type: None,
+            trait_type: "tier".to_string(),
+            value: "vip".to_string(),
+        }])
+    );
+    // extensions without attributes
+    let res: NftInfoResponse<Option<NftAttributes>> =
+        cosmwasm_std::from_json(r#"{"token_uri":null,"extension":{"bundle":[]}}"#).unwrap();
+    assert_eq!(res.extension, Some(NftAttributes::default()));
+}
@@ -2,10 +2,10 @@ use std::fmt::Debug;
 
 use cosmwasm_std::{
     to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, CustomMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, QuerierWrapper, Response, StdResult, Storage, WasmMsg, WasmQuery,
+    MessageInfo, QuerierWrapper, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
 };
 use cw_ownable::{Action, Ownership};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -13,31 +13,45 @@ use serde::{de::DeserializeOwned, Serialize};
 use crate::{
     error::Cw721ContractError,
     execute::{
-        approve, approve_all, burn_nft, initialize_creator, initialize_minter, instantiate,
-        instantiate_with_version, migrate, mint, remove_withdraw_address, revoke, revoke_all,
-        send_nft, set_withdraw_address, transfer_nft, update_collection_info,
-        update_creator_ownership, update_minter_ownership, update_nft_info, withdraw_funds,
+        accept_transfer, approve, approve_all, attach_token, burn_nft, cancel_transfer,
+        detach_token, enable_owner_snapshots, initialize_creator, initialize_minter, instantiate,
+        instantiate_with_version, lock_token, migrate, mint, mint_edition, mint_from_template,
+        mint_with_voucher, permit, propose_transfer, receive_nft, register_edition_master,
+        remove_metadata_editor, remove_withdraw_address, revoke, revoke_all, safe_mint,
+        safe_transfer_nft, send_nft, send_nft_batch, set_metadata_editor, set_template,
+        set_unlock_delay, set_user, set_voucher_signer, set_withdraw_address, transfer_nft,
+        unlock_token, update_collection_info, update_creator_ownership, update_minter_ownership,
+        update_nft_info, withdraw_funds,
     },
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, CollectionInfoMsg, Cw721ExecuteMsg,
-        Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, BalanceAtHeightResponse,
+        ChildToken, ChildrenResponse, ClassDataResponse, CollectionInfoAndExtensionResponse,
+        CollectionInfoMsg, Cw2981RoyaltyQueryMsg, Cw721ExecuteMsg, Cw721InstantiateMsg,
+        Cw721MigrateMsg, Cw721QueryMsg, EditionResponse, IsLockedResponse, MetadataEditorsResponse,
+        MintVoucher, MinterResponse, NftInfoResponse, NumTokensResponse, OperatorResponse,
+        OperatorsResponse, OwnerOfAtHeightResponse, OwnerOfResponse, RootOwnerResponse,
+        RoyaltiesInfoResponse, RoyaltyPayment, TokenDataResponse, TokensResponse, UserOfResponse,
     },
     query::{
         query_all_nft_info, query_all_tokens, query_approval, query_approvals,
+        query_balance_at_height, query_children, query_class_data,
         query_collection_extension_attributes, query_collection_info,
-        query_collection_info_and_extension, query_creator_ownership, query_minter,
-        query_minter_ownership, query_nft_info, query_num_tokens, query_operator, query_operators,
-        query_owner_of, query_tokens, query_withdraw_address,
+        query_collection_info_and_extension, query_creator_ownership, query_edition,
+        query_editions_of, query_ics721_bridge, query_is_locked, query_is_voucher_nonce_used,
+        query_metadata_editor, query_metadata_editors, query_minter, query_minter_ownership,
+        query_nft_info, query_num_tokens, query_operator, query_operators, query_owner_of,
+        query_owner_of_at_height, query_permit_nonce, query_root_owner, query_template,
+        query_token_data, query_tokens, query_unlock_delay, query_user_of, query_voucher_signer,
+        query_withdraw_address,
     },
-    state::CollectionInfo,
+    receiver::Cw721ReceiveMsg,
+    state::{CollectionInfo, MetadataEditorScope},
     Attribute,
 };
 use crate::{
     msg::{AllInfoResponse, ConfigResponse},
     query::{query_all_info, query_config, query_nft_by_extension},
-    Approval,
+    Approval, DefaultOptionalCollectionExtension,
 };
 
 /// This is an exact copy of `CustomMsg`, since implementing a trait for a type from another crate is not possible.
@@ -64,6 +78,17 @@ pub trait Contains {
     fn contains(&self, other: &Self) -> bool;
 }
 
+/// e.g. for merging the per-token data of a token into shared metadata: its overrides into the extension of its
+/// template, or its edition number into the extension of its edition master.
+pub trait WithOverrides: Clone {
+    fn with_overrides(&self, overrides: &Self) -> Self;
+
+    /// Opt-in, by default editions return the master's extension as is.
+    fn with_edition_number(&self, _edition_number: u64) -> Self {
+        self.clone()
+    }
+}
+
 pub trait StateFactory<TState> {
     fn create(
         &self,
@@ -151,7 +176,7 @@ pub trait Cw721Execute<
     // Defines for `CosmosMsg::Custom<T>` in response. Barely used, so `Empty` can be used.
     TCustomResponseMsg,
 > where
-    TNftExtension: Cw721State,
+    TNftExtension: Cw721State + Default,
     TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + ToAttributesState + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg + StateFactory<TCollectionExtension>,
@@ -196,6 +221,50 @@ pub trait Cw721Execute<
                 token_uri,
                 extension,
             } => self.mint(deps, env, info, token_id, owner, token_uri, extension),
+            Cw721ExecuteMsg::SafeMint {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                msg,
+            } => self.safe_mint(deps, env, info, token_id, owner, token_uri, extension, msg),
+            Cw721ExecuteMsg::MintWithVoucher { voucher, signature } => {
+                self.mint_with_voucher(deps, env, info, voucher, signature)
+            }
+            Cw721ExecuteMsg::SetVoucherSigner { pubkey } => {
+                self.set_voucher_signer(deps, &info.sender, pubkey)
+            }
+            Cw721ExecuteMsg::RegisterEditionMaster {
+                master_id,
+                token_uri,
+                extension,
+                max_size,
+            } => self.register_edition_master(
+                deps, env, info, master_id, token_uri, extension, max_size,
+            ),
+            Cw721ExecuteMsg::MintEdition { master_id, owner } => {
+                self.mint_edition(deps, env, info, master_id, owner)
+            }
+            Cw721ExecuteMsg::SetTemplate {
+                template_id,
+                extension,
+            } => self.set_template(deps, env, info, template_id, extension),
+            Cw721ExecuteMsg::MintFromTemplate {
+                token_id,
+                owner,
+                token_uri,
+                template_id,
+                overrides,
+            } => self.mint_from_template(
+                deps,
+                env,
+                info,
+                token_id,
+                owner,
+                token_uri,
+                template_id,
+                overrides,
+            ),
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -204,6 +273,21 @@ pub trait Cw721Execute<
             Cw721ExecuteMsg::Revoke { spender, token_id } => {
                 self.revoke(deps, env, info, spender, token_id)
             }
+            Cw721ExecuteMsg::Permit {
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+                pubkey,
+            } => self.permit(
+                deps, env, info, token_id, spender, expires, nonce, signature, pubkey,
+            ),
+            Cw721ExecuteMsg::SetUser {
+                token_id,
+                user,
+                expires,
+            } => self.set_user(deps, env, info, token_id, user, expires),
             Cw721ExecuteMsg::ApproveAll { operator, expires } => {
                 self.approve_all(deps, env, info, operator, expires)
             }
@@ -217,6 +301,27 @@ pub trait Cw721Execute<
                 token_id,
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient,
+                token_id,
+                msg,
+            } => self.safe_transfer_nft(deps, env, info, recipient, token_id, msg),
+            Cw721ExecuteMsg::SendNftBatch {
+                contract,
+                token_ids,
+                msg,
+            } => self.send_nft_batch(deps, env, info, contract, token_ids, msg),
+            Cw721ExecuteMsg::ProposeTransfer {
+                recipient,
+                token_id,
+                expires,
+            } => self.propose_transfer(deps, env, info, recipient, token_id, expires),
+            Cw721ExecuteMsg::AcceptTransfer { token_id } => {
+                self.accept_transfer(deps, env, info, token_id)
+            }
+            Cw721ExecuteMsg::CancelTransfer { token_id } => {
+                self.cancel_transfer(deps, env, info, token_id)
+            }
             Cw721ExecuteMsg::Burn { token_id } => self.burn_nft(deps, env, info, token_id),
             #[allow(deprecated)]
             Cw721ExecuteMsg::UpdateOwnership(action) => {
@@ -244,6 +349,43 @@ pub trait Cw721Execute<
                 self.remove_withdraw_address(deps.storage, &info.sender)
             }
             Cw721ExecuteMsg::WithdrawFunds { amount } => self.withdraw_funds(deps.storage, &amount),
+            Cw721ExecuteMsg::SetMetadataEditor { editor, scope } => {
+                self.set_metadata_editor(deps, &info.sender, editor, scope)
+            }
+            Cw721ExecuteMsg::RemoveMetadataEditor { editor } => {
+                self.remove_metadata_editor(deps, &info.sender, editor)
+            }
+            Cw721ExecuteMsg::LockToken { token_id } => self.lock_token(deps, env, info, token_id),
+            Cw721ExecuteMsg::UnlockToken { token_id } => {
+                self.unlock_token(deps, env, info, token_id)
+            }
+            Cw721ExecuteMsg::SetUnlockDelay { delay } => {
+                self.set_unlock_delay(deps, &info.sender, delay)
+            }
+            Cw721ExecuteMsg::EnableOwnerSnapshots {} => {
+                self.enable_owner_snapshots(deps, &info.sender)
+            }
+            Cw721ExecuteMsg::AttachToken {
+                token_id,
+                parent_token_id,
+            } => self.attach_token(deps, env, info, token_id, parent_token_id),
+            Cw721ExecuteMsg::DetachToken {
+                parent_token_id,
+                collection,
+                token_id,
+                recipient,
+            } => self.detach_token(
+                deps,
+                env,
+                info,
+                parent_token_id,
+                collection,
+                token_id,
+                recipient,
+            ),
+            Cw721ExecuteMsg::ReceiveNft(receive_msg) => {
+                self.receive_nft(deps, env, info, receive_msg)
+            }
         }
     }
 
@@ -267,9 +409,10 @@ pub trait Cw721Execute<
         recipient: String,
         token_id: String,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        transfer_nft::<TNftExtension>(deps, env, info, &recipient, &token_id)?;
+        let transfer_event = transfer_nft::<TNftExtension>(deps, env, info, &recipient, &token_id)?;
 
         Ok(Response::new()
+            .add_event(transfer_event.into())
             .add_attribute("action", "transfer_nft")
             .add_attribute("sender", info.sender.to_string())
             .add_attribute("recipient", recipient)
@@ -288,6 +431,68 @@ pub trait Cw721Execute<
         send_nft::<TNftExtension, TCustomResponseMsg>(deps, env, info, contract, token_id, msg)
     }
 
+    fn safe_transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        recipient: String,
+        token_id: String,
+        msg: Option<Binary>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        safe_transfer_nft::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, recipient, token_id, msg,
+        )
+    }
+
+    fn send_nft_batch(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        send_nft_batch::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, contract, token_ids, msg,
+        )
+    }
+
+    fn propose_transfer(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        recipient: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        propose_transfer::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, recipient, token_id, expires,
+        )
+    }
+
+    fn accept_transfer(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        accept_transfer::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id)
+    }
+
+    fn cancel_transfer(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        cancel_transfer::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id)
+    }
+
     fn approve(
         &self,
         deps: DepsMut,
@@ -311,6 +516,36 @@ pub trait Cw721Execute<
         revoke::<TNftExtension, TCustomResponseMsg>(deps, env, info, spender, token_id)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn permit(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        permit::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, token_id, spender, expires, nonce, signature, pubkey,
+        )
+    }
+
+    fn set_user(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_user::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id, user, expires)
+    }
+
     fn approve_all(
         &self,
         deps: DepsMut,
@@ -332,6 +567,92 @@ pub trait Cw721Execute<
         revoke_all::<TCustomResponseMsg>(deps, _env, info, operator)
     }
 
+    fn lock_token(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        lock_token::<TNftExtension, TCustomResponseMsg>(deps, info, token_id)
+    }
+
+    fn unlock_token(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        unlock_token::<TCustomResponseMsg>(deps, env, info, token_id)
+    }
+
+    fn set_unlock_delay(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        delay: Duration,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_unlock_delay::<TCustomResponseMsg>(deps, sender, delay)
+    }
+
+    fn enable_owner_snapshots(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        enable_owner_snapshots::<TCustomResponseMsg>(deps, sender)
+    }
+
+    fn attach_token(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        parent_token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        attach_token::<TNftExtension, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            token_id,
+            parent_token_id,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn detach_token(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        parent_token_id: String,
+        collection: Option<String>,
+        token_id: String,
+        recipient: Option<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        detach_token::<TNftExtension, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            parent_token_id,
+            collection,
+            token_id,
+            recipient,
+        )
+    }
+
+    fn receive_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        receive_msg: Cw721ReceiveMsg,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        receive_nft::<TCustomResponseMsg>(deps, env, info, receive_msg)
+    }
+
     fn burn_nft(
         &self,
         deps: DepsMut,
@@ -389,6 +710,113 @@ pub trait Cw721Execute<
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn safe_mint(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        msg: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        safe_mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, token_id, owner, token_uri, extension, msg,
+        )
+    }
+
+    fn mint_with_voucher(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        voucher: MintVoucher<TNftExtensionMsg>,
+        signature: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_with_voucher::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, voucher, signature,
+        )
+    }
+
+    fn set_voucher_signer(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        pubkey: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_voucher_signer::<TCustomResponseMsg>(deps, sender, pubkey)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn register_edition_master(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        master_id: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        max_size: u64,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        register_edition_master::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, master_id, token_uri, extension, max_size,
+        )
+    }
+
+    fn mint_edition(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        master_id: String,
+        owner: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_edition::<TNftExtension, TCustomResponseMsg>(deps, env, info, master_id, owner)
+    }
+
+    fn set_template(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        template_id: String,
+        extension: TNftExtensionMsg,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_template::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            template_id,
+            extension,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn mint_from_template(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        template_id: String,
+        overrides: TNftExtensionMsg,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_from_template::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            token_id,
+            owner,
+            token_uri,
+            template_id,
+            overrides,
+        )
+    }
+
     fn update_minter_ownership(
         &self,
         api: &dyn Api,
@@ -422,7 +850,7 @@ pub trait Cw721Execute<
         Ok(Response::default())
     }
 
-    /// The creator is the only one eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
+    /// The creator and metadata editors (within their scope) are eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
     /// NOTE: approvals and owner are not affected by this call, since they belong to the NFT owner.
     fn update_nft_info(
         &self,
@@ -443,6 +871,25 @@ pub trait Cw721Execute<
         )
     }
 
+    fn set_metadata_editor(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        editor: String,
+        scope: MetadataEditorScope,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_metadata_editor::<TCustomResponseMsg>(deps, sender, editor, scope)
+    }
+
+    fn remove_metadata_editor(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        editor: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        remove_metadata_editor::<TCustomResponseMsg>(deps, sender, editor)
+    }
+
     fn set_withdraw_address(
         &self,
         deps: DepsMut,
@@ -478,7 +925,7 @@ pub trait Cw721Query<
     // Custom query msg for custom contract logic. Default implementation returns an empty binary.
     TExtensionQueryMsg,
 > where
-    TNftExtension: Cw721State + Contains,
+    TNftExtension: Cw721State + Contains + WithOverrides,
     TCollectionExtension: Cw721State + FromAttributesState,
     TExtensionQueryMsg: Cw721CustomMsg,
 {
@@ -527,6 +974,9 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?)?),
+            Cw721QueryMsg::UserOf { token_id } => {
+                Ok(to_json_binary(&self.query_user_of(deps, env, token_id)?)?)
+            }
             Cw721QueryMsg::AllNftInfo {
                 token_id,
                 include_expired,
@@ -614,6 +1064,69 @@ pub trait Cw721Query<
             Cw721QueryMsg::GetWithdrawAddress {} => {
                 Ok(to_json_binary(&self.query_withdraw_address(deps)?)?)
             }
+            Cw721QueryMsg::GetMetadataEditor { editor } => {
+                Ok(to_json_binary(&self.query_metadata_editor(deps, editor)?)?)
+            }
+            Cw721QueryMsg::GetMetadataEditors { start_after, limit } => Ok(to_json_binary(
+                &self.query_metadata_editors(deps, start_after, limit)?,
+            )?),
+            Cw721QueryMsg::IsLocked { token_id } => {
+                Ok(to_json_binary(&self.query_is_locked(deps, env, token_id)?)?)
+            }
+            Cw721QueryMsg::GetVoucherSigner {} => {
+                Ok(to_json_binary(&self.query_voucher_signer(deps.storage)?)?)
+            }
+            Cw721QueryMsg::IsVoucherNonceUsed { nonce } => Ok(to_json_binary(
+                &self.query_is_voucher_nonce_used(deps.storage, nonce)?,
+            )?),
+            Cw721QueryMsg::GetPermitNonce { owner } => {
+                Ok(to_json_binary(&self.query_permit_nonce(deps, owner)?)?)
+            }
+            Cw721QueryMsg::GetUnlockDelay {} => {
+                Ok(to_json_binary(&self.query_unlock_delay(deps.storage)?)?)
+            }
+            Cw721QueryMsg::OwnerOfAtHeight { token_id, height } => Ok(to_json_binary(
+                &self.query_owner_of_at_height(deps.storage, token_id, height)?,
+            )?),
+            Cw721QueryMsg::BalanceAtHeight { owner, height } => Ok(to_json_binary(
+                &self.query_balance_at_height(deps, owner, height)?,
+            )?),
+            Cw721QueryMsg::ClassData {} => Ok(to_json_binary(&self.query_class_data(deps, env)?)?),
+            Cw721QueryMsg::TokenData { token_ids } => Ok(to_json_binary(
+                &self.query_token_data(deps.storage, token_ids)?,
+            )?),
+            Cw721QueryMsg::GetIcs721Bridge {} => {
+                Ok(to_json_binary(&self.query_ics721_bridge(deps.storage)?)?)
+            }
+            Cw721QueryMsg::Children {
+                token_id,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(&self.query_children(
+                deps,
+                token_id,
+                start_after,
+                limit,
+            )?)?),
+            Cw721QueryMsg::RootOwner { token_id } => Ok(to_json_binary(
+                &self.query_root_owner(deps, env, token_id)?,
+            )?),
+            Cw721QueryMsg::Edition { master_id } => {
+                Ok(to_json_binary(&self.query_edition(deps, master_id)?)?)
+            }
+            Cw721QueryMsg::EditionsOf {
+                master_id,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(&self.query_editions_of(
+                deps,
+                master_id,
+                start_after,
+                limit,
+            )?)?),
+            Cw721QueryMsg::Template { template_id } => Ok(to_json_binary(
+                &self.query_template(deps.storage, template_id)?,
+            )?),
         }
     }
 
@@ -697,6 +1210,10 @@ pub trait Cw721Query<
         query_owner_of(deps, env, token_id, include_expired_approval)
     }
 
+    fn query_user_of(&self, deps: Deps, env: &Env, token_id: String) -> StdResult<UserOfResponse> {
+        query_user_of(deps, env, token_id)
+    }
+
     /// operator returns the approval status of an operator for a given owner if exists
     fn query_operator(
         &self,
@@ -808,6 +1325,134 @@ pub trait Cw721Query<
     fn query_withdraw_address(&self, deps: Deps) -> StdResult<Option<String>> {
         query_withdraw_address(deps)
     }
+
+    fn query_metadata_editor(
+        &self,
+        deps: Deps,
+        editor: String,
+    ) -> StdResult<Option<MetadataEditorScope>> {
+        query_metadata_editor(deps, editor)
+    }
+
+    fn query_metadata_editors(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<MetadataEditorsResponse> {
+        query_metadata_editors(deps, start_after, limit)
+    }
+
+    fn query_is_locked(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<IsLockedResponse> {
+        query_is_locked(deps, env, token_id)
+    }
+
+    fn query_voucher_signer(&self, storage: &dyn Storage) -> StdResult<Option<Binary>> {
+        query_voucher_signer(storage)
+    }
+
+    fn query_is_voucher_nonce_used(&self, storage: &dyn Storage, nonce: u64) -> StdResult<bool> {
+        query_is_voucher_nonce_used(storage, nonce)
+    }
+
+    fn query_permit_nonce(&self, deps: Deps, owner: String) -> StdResult<u64> {
+        query_permit_nonce(deps, owner)
+    }
+
+    fn query_unlock_delay(&self, storage: &dyn Storage) -> StdResult<Duration> {
+        query_unlock_delay(storage)
+    }
+
+    fn query_owner_of_at_height(
+        &self,
+        storage: &dyn Storage,
+        token_id: String,
+        height: u64,
+    ) -> StdResult<OwnerOfAtHeightResponse> {
+        query_owner_of_at_height(storage, token_id, height)
+    }
+
+    fn query_balance_at_height(
+        &self,
+        deps: Deps,
+        owner: String,
+        height: u64,
+    ) -> StdResult<BalanceAtHeightResponse> {
+        query_balance_at_height(deps, owner, height)
+    }
+
+    fn query_class_data(
+        &self,
+        deps: Deps,
+        env: &Env,
+    ) -> Result<ClassDataResponse, Cw721ContractError>
+    where
+        TCollectionExtension: FromAttributesState,
+    {
+        query_class_data::<TCollectionExtension>(deps, env)
+    }
+
+    fn query_token_data(
+        &self,
+        storage: &dyn Storage,
+        token_ids: Vec<String>,
+    ) -> StdResult<TokenDataResponse> {
+        query_token_data::<TNftExtension>(storage, token_ids)
+    }
+
+    fn query_ics721_bridge(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        query_ics721_bridge(storage)
+    }
+
+    fn query_children(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<ChildToken>,
+        limit: Option<u32>,
+    ) -> StdResult<ChildrenResponse> {
+        query_children(deps, token_id, start_after, limit)
+    }
+
+    fn query_root_owner(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<RootOwnerResponse> {
+        query_root_owner(deps, env, token_id)
+    }
+
+    fn query_edition(
+        &self,
+        deps: Deps,
+        master_id: String,
+    ) -> StdResult<EditionResponse<TNftExtension>> {
+        query_edition::<TNftExtension>(deps, master_id)
+    }
+
+    fn query_editions_of(
+        &self,
+        deps: Deps,
+        master_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        query_editions_of(deps, master_id, start_after, limit)
+    }
+
+    fn query_template(
+        &self,
+        storage: &dyn Storage,
+        template_id: String,
+    ) -> StdResult<TNftExtension> {
+        query_template::<TNftExtension>(storage, template_id)
+    }
 }
 
 /// Generic trait with onchain nft and collection extensions used to call query and execute messages for a given CW721 addr.
@@ -871,6 +1516,60 @@ pub trait Cw721Calls<
         self.query(querier, req)
     }
 
+    /// Returns the owner of the token at given height, e.g. for voting on a past proposal.
+    /// Requires owner snapshots to be enabled.
+    fn owner_of_at_height<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        height: u64,
+    ) -> StdResult<OwnerOfAtHeightResponse> {
+        let req = Cw721QueryMsg::OwnerOfAtHeight {
+            token_id: token_id.into(),
+            height,
+        };
+        self.query(querier, req)
+    }
+
+    /// Returns the number of tokens held by owner at given height, e.g. for voting power.
+    /// Requires owner snapshots to be enabled.
+    fn balance_at_height<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        height: u64,
+    ) -> StdResult<BalanceAtHeightResponse> {
+        let req = Cw721QueryMsg::BalanceAtHeight {
+            owner: owner.into(),
+            height,
+        };
+        self.query(querier, req)
+    }
+
+    /// Returns the owner of the top-most parent of the token, who controls all tokens attached below it.
+    fn root_owner<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<RootOwnerResponse> {
+        let req = Cw721QueryMsg::RootOwner {
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
+    /// Returns the active user of the token, e.g. for checking usage rights of a renter.
+    fn user_of<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<UserOfResponse> {
+        let req = Cw721QueryMsg::UserOf {
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
     fn approval<T: Into<String>>(
         &self,
         querier: &QuerierWrapper,
@@ -943,6 +1642,22 @@ pub trait Cw721Calls<
         self.query(querier, req)
     }
 
+    /// Returns the ICS-721 class data, e.g. for a bridge sending tokens to another chain.
+    fn class_data(&self, querier: &QuerierWrapper) -> StdResult<ClassDataResponse> {
+        let req = Cw721QueryMsg::ClassData {};
+        self.query(querier, req)
+    }
+
+    /// Returns the ICS-721 token data of the given tokens.
+    fn token_data(
+        &self,
+        querier: &QuerierWrapper,
+        token_ids: Vec<String>,
+    ) -> StdResult<TokenDataResponse> {
+        let req = Cw721QueryMsg::TokenData { token_ids };
+        self.query(querier, req)
+    }
+
     /// With NFT onchain metadata
     fn nft_info<T: Into<String>, U: DeserializeOwned>(
         &self,
@@ -996,6 +1711,47 @@ pub trait Cw721Calls<
         self.query(querier, req)
     }
 
+    /// Returns the royalty owed on a sale. The royalty is looked up in the collection's `royalty_info`
+    /// (`CollectionExtension<RoyaltyInfo>`), otherwise via cw2981 `RoyaltyInfo` query. Collections
+    /// supporting neither owe no royalty.
+    fn royalty<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        sale_price: Uint128,
+    ) -> Option<RoyaltyPayment> {
+        let collection_royalty = self
+            .collection_info::<DefaultOptionalCollectionExtension>(querier)
+            .ok()
+            .and_then(|info| info.extension)
+            .and_then(|extension| extension.royalty_info)
+            .map(|royalty_info| RoyaltyPayment {
+                recipient: royalty_info.payment_address,
+                amount: sale_price * royalty_info.share,
+            });
+        let royalty = collection_royalty.or_else(|| {
+            querier
+                .query_wasm_smart::<RoyaltiesInfoResponse>(
+                    self.addr(),
+                    &Cw2981RoyaltyQueryMsg::RoyaltyInfo {
+                        token_id: token_id.into(),
+                        sale_price,
+                    },
+                )
+                .ok()
+                .map(|res| RoyaltyPayment {
+                    recipient: Addr::unchecked(res.address),
+                    amount: res.royalty_amount,
+                })
+        })?;
+        // royalty can't exceed sale price
+        let amount = royalty.amount.min(sale_price);
+        if amount.is_zero() {
+            return None;
+        }
+        Some(RoyaltyPayment { amount, ..royalty })
+    }
+
     /// returns true if the contract supports the metadata extension
     fn has_metadata(&self, querier: &QuerierWrapper) -> bool {
         self.collection_info::<Empty>(querier).is_ok()